@@ -1,9 +1,14 @@
+use super::attachments::{attachment_path, AttachmentEndpoint};
+use super::order::{AccountOrderField, OrderBy};
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::{account, attachment};
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
+use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Resource accessor for Accounts.
@@ -22,6 +27,27 @@ impl<'a> AccountsResource<'a> {
         AccountsListRequest::new(self.api)
     }
 
+    /// Retrieves only bank accounts, filtering on `Type=="BANK"` server-side.
+    ///
+    /// Bank accounts carry `bank_account_number` and `bank_account_type`,
+    /// which distinguishes ordinary bank, credit card and PayPal accounts.
+    pub async fn bank(&self) -> Result<Vec<account::Account>, XeroError> {
+        self.list()
+            .where_filter(format!("Type==\"{}\"", account::AccountType::Bank))
+            .send()
+            .await
+    }
+
+    /// Retrieves the whole chart of accounts in one request, indexed by code
+    /// and by ID.
+    ///
+    /// Also refreshes the code cache used by [`Self::resolve_id`].
+    pub async fn chart(&self) -> Result<account::ChartOfAccounts, XeroError> {
+        let accounts = self.list().send().await?;
+        self.fill_code_cache(&accounts);
+        Ok(account::ChartOfAccounts::new(accounts))
+    }
+
     /// Retrieves a single account by ID.
     pub async fn get(&self, account_id: Uuid) -> Result<Vec<account::Account>, XeroError> {
         let path = format!("/Accounts/{account_id}");
@@ -38,16 +64,13 @@ impl<'a> AccountsResource<'a> {
         &self,
         accounts: Vec<account::Account>,
     ) -> Result<Vec<account::Account>, XeroError> {
-        let body = if accounts.len() == 1 {
-            serde_json::to_value(accounts.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(account::AccountsRequest { accounts })?
-        };
+        let body = single_or_wrapped(accounts, |accounts| account::AccountsRequest { accounts })?;
         let resp: account::AccountsResponse = self
             .api
             .client
             .send_request(Method::PUT, "/Accounts", None, Some(body))
             .await?;
+        self.clear_code_cache();
         Ok(resp.accounts)
     }
 
@@ -63,6 +86,7 @@ impl<'a> AccountsResource<'a> {
             .client
             .send_request(Method::POST, &path, None, Some(account_data))
             .await?;
+        self.clear_code_cache();
         Ok(resp.accounts)
     }
 
@@ -72,7 +96,57 @@ impl<'a> AccountsResource<'a> {
         self.api
             .client
             .send_request_empty_response(Method::DELETE, &path, None::<()>)
-            .await
+            .await?;
+        self.clear_code_cache();
+        Ok(())
+    }
+
+    /// Resolves an account code (e.g. `"200"`) to its `AccountID`.
+    ///
+    /// The chart of accounts is fetched on the first lookup for the tenant and
+    /// cached on the client for an hour; account writes made through this
+    /// crate clear it. A code missing from the cached chart refetches it once,
+    /// so accounts added elsewhere are found. Returns `Ok(None)` if no account
+    /// has the code.
+    pub async fn resolve_id(&self, code: &str) -> Result<Option<Uuid>, XeroError> {
+        let tenant_id = self.api.client.tenant_id();
+        let cached = self.api.account_codes.get(tenant_id, &());
+        if let Some(account_id) = cached.and_then(|codes| codes.get(code).copied()) {
+            return Ok(Some(account_id));
+        }
+        let accounts = self.list().send().await?;
+        Ok(self.fill_code_cache(&accounts).get(code).copied())
+    }
+
+    /// Refetches the chart of accounts into the code cache used by [`Self::resolve_id`].
+    ///
+    /// Use this after a cached code was moved to another account outside this
+    /// client; unknown codes are refetched by [`Self::resolve_id`] itself.
+    pub async fn refresh_code_cache(&self) -> Result<(), XeroError> {
+        let accounts = self.list().send().await?;
+        self.fill_code_cache(&accounts);
+        Ok(())
+    }
+
+    /// Replaces this tenant's cached codes with those of `accounts`.
+    fn fill_code_cache(&self, accounts: &[account::Account]) -> Arc<HashMap<String, Uuid>> {
+        let codes: Arc<HashMap<String, Uuid>> = Arc::new(
+            accounts
+                .iter()
+                .filter_map(|account| Some((account.code.clone()?, account.account_id?)))
+                .collect(),
+        );
+        self.api
+            .account_codes
+            .insert(self.api.client.tenant_id(), (), codes.clone());
+        codes
+    }
+
+    /// Drops this tenant's cached account codes.
+    pub fn clear_code_cache(&self) {
+        self.api
+            .account_codes
+            .clear_tenant(self.api.client.tenant_id());
     }
 
     /// Attaches a file to an account.
@@ -82,8 +156,7 @@ impl<'a> AccountsResource<'a> {
         file_name: String,
         body: Vec<u8>,
     ) -> Result<Vec<attachment::Attachment>, XeroError> {
-        let encoded_file_name = file_name.replace('[', "%5B").replace(']', "%5D");
-        let path = format!("/Accounts/{account_id}/Attachments/{encoded_file_name}");
+        let path = attachment_path(AttachmentEndpoint::Accounts, account_id, &file_name);
         let content_type = "application/octet-stream";
         let resp: attachment::AttachmentsResponse = self
             .api
@@ -123,6 +196,11 @@ impl<'a> AccountsListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<AccountOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<account::Account>, XeroError> {
         let mut query = QueryParams::default();
@@ -161,6 +239,16 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves the chart of accounts as lookup maps. See [`AccountsResource::chart`].
+    pub async fn get_chart_of_accounts(&self) -> Result<account::ChartOfAccounts, XeroError> {
+        self.accounts().chart().await
+    }
+
+    /// Retrieves only bank accounts.
+    pub async fn get_bank_accounts(&self) -> Result<Vec<account::Account>, XeroError> {
+        self.accounts().bank().await
+    }
+
     /// Creates one or more new accounts.
     pub async fn create_accounts(
         &self,
@@ -183,6 +271,11 @@ impl AccountingApi {
         self.accounts().delete(account_id).await
     }
 
+    /// Resolves an account code to its `AccountID`, using the client's cached chart of accounts.
+    pub async fn resolve_account_id(&self, code: &str) -> Result<Option<Uuid>, XeroError> {
+        self.accounts().resolve_id(code).await
+    }
+
     /// Attaches a file to an account.
     pub async fn create_account_attachment_by_file_name(
         &self,
@@ -195,3 +288,26 @@ impl AccountingApi {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{self, MockServer};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn resolve_id_refetches_the_chart_for_unknown_codes() {
+        let (sales, rent) = (Uuid::from_u128(200), Uuid::from_u128(400));
+        let sales_only = format!(r#"{{"Accounts":[{{"AccountID":"{sales}","Code":"200"}}]}}"#);
+        let with_rent = format!(
+            r#"{{"Accounts":[{{"AccountID":"{sales}","Code":"200"}},{{"AccountID":"{rent}","Code":"400"}}]}}"#
+        );
+        let server = MockServer::ok([&sales_only, &with_rent, &with_rent]).await;
+        let api = test_support::accounting_api(&server).await;
+
+        assert_eq!(api.accounts().resolve_id("200").await.unwrap(), Some(sales));
+        assert_eq!(api.accounts().resolve_id("200").await.unwrap(), Some(sales));
+        assert_eq!(api.accounts().resolve_id("400").await.unwrap(), Some(rent));
+        assert_eq!(api.accounts().resolve_id("999").await.unwrap(), None);
+        assert_eq!(server.paths().await, vec!["/Accounts"; 3]);
+    }
+}
@@ -1,10 +1,25 @@
+//! Accounting attachment endpoints.
+//!
+//! Unlike the Files API, the Accounting API does not accept
+//! `multipart/form-data` for attachments. Every upload is a raw body sent to
+//! `/{Endpoint}/{ParentID}/Attachments/{FileName}` with the file's own MIME
+//! type as the `Content-Type`; a multipart body is stored verbatim (boundary
+//! and all) or rejected with a 400.
+
+use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::http::RequestContext;
 use crate::models::accounting::attachment::{Attachment, AttachmentsResponse};
-use reqwest::Method;
+use log::warn;
+use reqwest::{Method, RequestBuilder};
+use std::collections::VecDeque;
 use uuid::Uuid;
 
 /// Supported Accounting endpoints for attachments.
+///
+/// Expense claims have no endpoint of their own; attach documents to the
+/// claim's receipts instead (see `ExpenseClaimsResource::receipt_attachments`).
 #[derive(Debug, Clone, Copy)]
 pub enum AttachmentEndpoint {
     Invoices,
@@ -38,6 +53,41 @@ impl AttachmentEndpoint {
     }
 }
 
+/// Percent-encodes an attachment file name so spaces, brackets, slashes and
+/// other reserved characters stay within a single path segment.
+pub(crate) fn encode_attachment_filename(file_name: &str) -> String {
+    urlencoding::encode(file_name).into_owned()
+}
+
+/// Builds the attachment path for a parent resource, encoding the file name
+/// with [`encode_attachment_filename`].
+pub(crate) fn attachment_path(
+    endpoint: AttachmentEndpoint,
+    parent_id: Uuid,
+    file_name: &str,
+) -> String {
+    format!(
+        "/{}/{}/Attachments/{}",
+        endpoint.as_str(),
+        parent_id,
+        encode_attachment_filename(file_name)
+    )
+}
+
+/// Builds the path addressing a single attachment by its `AttachmentID`.
+pub(crate) fn attachment_id_path(
+    endpoint: AttachmentEndpoint,
+    parent_id: Uuid,
+    attachment_id: Uuid,
+) -> String {
+    format!(
+        "/{}/{}/Attachments/{}",
+        endpoint.as_str(),
+        parent_id,
+        attachment_id
+    )
+}
+
 /// Resource accessor for Attachments.
 #[derive(Debug, Clone, Copy)]
 pub struct AttachmentsResource<'a> {
@@ -71,19 +121,54 @@ impl<'a> AttachmentsResource<'a> {
         parent_id: Uuid,
         filename: &str,
     ) -> Result<Vec<u8>, XeroError> {
-        let path = format!(
-            "/{}/{}/Attachments/{}",
-            endpoint.as_str(),
-            parent_id,
-            filename
-        );
+        let path = attachment_path(endpoint, parent_id, filename);
+        self.api
+            .client
+            .send_request_bytes(Method::GET, &path, None)
+            .await
+    }
+
+    /// Downloads the raw content of an attachment by its `AttachmentID`.
+    ///
+    /// Useful when the file name is ambiguous or was changed after upload.
+    pub async fn download_by_id(
+        &self,
+        endpoint: AttachmentEndpoint,
+        parent_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<Vec<u8>, XeroError> {
+        let path = attachment_id_path(endpoint, parent_id, attachment_id);
         self.api
             .client
             .send_request_bytes(Method::GET, &path, None)
             .await
     }
 
+    /// Lists a resource's attachments and returns a cursor that downloads them
+    /// one at a time, e.g. for writing every file on an invoice to an archive.
+    ///
+    /// Each download goes through the client's rate limiter when
+    /// [`AttachmentDownloads::next`] is awaited, so only one file is held in
+    /// memory at a time.
+    pub async fn download_all(
+        &self,
+        endpoint: AttachmentEndpoint,
+        parent_id: Uuid,
+    ) -> Result<AttachmentDownloads<'a>, XeroError> {
+        let pending = self.list(endpoint, parent_id).await?.into();
+        Ok(AttachmentDownloads {
+            resource: *self,
+            endpoint,
+            parent_id,
+            pending,
+        })
+    }
+
     /// Uploads an attachment using POST.
+    ///
+    /// `include_online` controls whether the file is shown on the online
+    /// invoice and linked from invoices emailed by Xero; it only has an effect
+    /// for sales invoices and credit notes.
     pub async fn upload_post<B>(
         &self,
         endpoint: AttachmentEndpoint,
@@ -109,6 +194,10 @@ impl<'a> AttachmentsResource<'a> {
     }
 
     /// Uploads an attachment using PUT.
+    ///
+    /// `include_online` controls whether the file is shown on the online
+    /// invoice and linked from invoices emailed by Xero; it only has an effect
+    /// for sales invoices and credit notes.
     pub async fn upload_put<B>(
         &self,
         endpoint: AttachmentEndpoint,
@@ -133,6 +222,7 @@ impl<'a> AttachmentsResource<'a> {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn upload<B>(
         &self,
         method: Method,
@@ -146,25 +236,83 @@ impl<'a> AttachmentsResource<'a> {
     where
         B: Into<reqwest::Body>,
     {
-        let mut path = format!(
-            "/{}/{}/Attachments/{}",
-            endpoint.as_str(),
-            parent_id,
-            filename
-        );
-        if let Some(include_online) = include_online {
-            path.push_str(&format!(
-                "?IncludeOnline={}",
-                if include_online { "true" } else { "false" }
-            ));
+        let (ctx, builder) = self
+            .upload_request(
+                method,
+                endpoint,
+                parent_id,
+                filename,
+                content_type,
+                body,
+                include_online,
+            )
+            .await?;
+        let resp: AttachmentsResponse = self.api.client.send_built_request(&ctx, builder).await?;
+        if include_online == Some(true)
+            && matches!(
+                endpoint,
+                AttachmentEndpoint::Invoices | AttachmentEndpoint::CreditNotes
+            )
+            && !resp.attachments.iter().all(Attachment::is_online)
+        {
+            warn!("{ctx}: IncludeOnline was requested but Xero did not confirm it");
         }
+        Ok(resp.attachments)
+    }
 
-        let resp: AttachmentsResponse = self
-            .api
+    #[allow(clippy::too_many_arguments)]
+    async fn upload_request<B>(
+        &self,
+        method: Method,
+        endpoint: AttachmentEndpoint,
+        parent_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+        include_online: Option<bool>,
+    ) -> Result<(RequestContext, RequestBuilder), XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        let path = attachment_path(endpoint, parent_id, filename);
+        let mut query = QueryParams::default();
+        query.push_opt("IncludeOnline", include_online);
+        self.api
             .client
-            .send_request_raw_body(method, &path, content_type, body)
-            .await?;
-        Ok(resp.attachments)
+            .build_raw_body_request(method, &path, query.as_slice(), content_type, body)
+            .await
+    }
+}
+
+/// Downloads a resource's attachments in sequence, created by
+/// [`AttachmentsResource::download_all`].
+#[derive(Debug)]
+pub struct AttachmentDownloads<'a> {
+    resource: AttachmentsResource<'a>,
+    endpoint: AttachmentEndpoint,
+    parent_id: Uuid,
+    pending: VecDeque<Attachment>,
+}
+
+impl AttachmentDownloads<'_> {
+    /// Downloads the next attachment, returning its file name and content, or
+    /// `None` once every attachment has been returned.
+    ///
+    /// A failed download is returned as an error and skipped; calling `next`
+    /// again continues with the following attachment.
+    pub async fn next(&mut self) -> Option<Result<(String, Vec<u8>), XeroError>> {
+        let attachment = self.pending.pop_front()?;
+        let content = self
+            .resource
+            .download_by_id(self.endpoint, self.parent_id, attachment.attachment_id)
+            .await;
+        Some(content.map(|bytes| (attachment.file_name, bytes)))
+    }
+
+    /// The attachments not yet downloaded.
+    #[must_use]
+    pub fn remaining(&self) -> &VecDeque<Attachment> {
+        &self.pending
     }
 }
 
@@ -190,6 +338,27 @@ impl AccountingApi {
             .await
     }
 
+    /// Downloads an attachment's raw content by its `AttachmentID`.
+    pub async fn download_attachment_by_id(
+        &self,
+        endpoint: AttachmentEndpoint,
+        parent_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<Vec<u8>, XeroError> {
+        self.attachments()
+            .download_by_id(endpoint, parent_id, attachment_id)
+            .await
+    }
+
+    /// Lists a resource's attachments and returns a cursor that downloads each in turn.
+    pub async fn download_all_attachments(
+        &self,
+        endpoint: AttachmentEndpoint,
+        parent_id: Uuid,
+    ) -> Result<AttachmentDownloads<'_>, XeroError> {
+        self.attachments().download_all(endpoint, parent_id).await
+    }
+
     /// Uploads an attachment using POST.
     pub async fn upload_attachment_post<B>(
         &self,
@@ -240,3 +409,188 @@ impl AccountingApi {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, MockServer};
+
+    async fn test_api() -> AccountingApi {
+        test_support::tenant().await.accounting()
+    }
+
+    #[tokio::test]
+    async fn upload_sends_raw_body_with_file_content_type() {
+        let api = test_api().await;
+        let parent_id = Uuid::new_v4();
+        let (_, builder) = api
+            .attachments()
+            .upload_request(
+                Method::PUT,
+                AttachmentEndpoint::Invoices,
+                parent_id,
+                "scan [1].pdf",
+                "application/pdf",
+                b"%PDF-1.4".to_vec(),
+                Some(true),
+            )
+            .await
+            .unwrap();
+        let request = builder.build().unwrap();
+
+        assert_eq!(request.method(), Method::PUT);
+        assert_eq!(
+            request.url().path(),
+            format!("/api.xro/2.0/Invoices/{parent_id}/Attachments/scan%20%5B1%5D.pdf")
+        );
+        assert_eq!(request.url().query(), Some("IncludeOnline=true"));
+        assert_eq!(
+            request.headers()["Content-Type"],
+            "application/pdf",
+            "attachments must not be sent as multipart/form-data"
+        );
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(&b"%PDF-1.4"[..])
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_post_omits_include_online_when_unset() {
+        let api = test_api().await;
+        let (_, builder) = api
+            .attachments()
+            .upload_request(
+                Method::POST,
+                AttachmentEndpoint::Receipts,
+                Uuid::nil(),
+                "receipt.png",
+                "image/png",
+                vec![0u8, 1, 2],
+                None,
+            )
+            .await
+            .unwrap();
+        let request = builder.build().unwrap();
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.url().query(), None);
+        assert_eq!(request.headers()["Content-Type"], "image/png");
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(&[0u8, 1, 2][..])
+        );
+    }
+
+    #[test]
+    fn attachment_id_path_addresses_single_attachment() {
+        let parent_id = Uuid::nil();
+        let attachment_id = Uuid::from_u128(1);
+        assert_eq!(
+            attachment_id_path(AttachmentEndpoint::PurchaseOrders, parent_id, attachment_id),
+            format!("/PurchaseOrders/{parent_id}/Attachments/{attachment_id}")
+        );
+    }
+
+    #[test]
+    fn attachment_filenames_are_single_segments() {
+        assert_eq!(
+            encode_attachment_filename("scan [1].pdf"),
+            "scan%20%5B1%5D.pdf"
+        );
+        assert_eq!(encode_attachment_filename("a/b?.png"), "a%2Fb%3F.png");
+        assert_eq!(
+            encode_attachment_filename("résumé.pdf"),
+            "r%C3%A9sum%C3%A9.pdf"
+        );
+        assert_eq!(
+            encode_attachment_filename("plain-name_1.txt"),
+            "plain-name_1.txt"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_all_lists_then_fetches_each_attachment() {
+        let parent_id = Uuid::from_u128(7);
+        let first = Uuid::from_u128(1);
+        let second = Uuid::from_u128(2);
+        let listing = format!(
+            r#"{{"Attachments":[
+                {{"AttachmentID":"{first}","FileName":"a.pdf","Url":"u","MimeType":"application/pdf"}},
+                {{"AttachmentID":"{second}","FileName":"b.png","Url":"u","MimeType":"image/png"}}
+            ]}}"#
+        );
+        let server = MockServer::ok([listing.as_str(), "AAA", "BB"]).await;
+
+        let api = test_api().await.with_base_url(server.url());
+        let mut downloads = api
+            .download_all_attachments(AttachmentEndpoint::Invoices, parent_id)
+            .await
+            .unwrap();
+        assert_eq!(downloads.remaining().len(), 2);
+
+        let mut files = Vec::new();
+        while let Some(file) = downloads.next().await {
+            files.push(file.unwrap());
+        }
+        assert_eq!(
+            files,
+            vec![
+                ("a.pdf".to_string(), b"AAA".to_vec()),
+                ("b.png".to_string(), b"BB".to_vec()),
+            ]
+        );
+        assert_eq!(
+            server.paths().await,
+            vec![
+                format!("/Invoices/{parent_id}/Attachments"),
+                format!("/Invoices/{parent_id}/Attachments/{first}"),
+                format!("/Invoices/{parent_id}/Attachments/{second}"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn expense_claim_attachments_come_from_its_receipts() {
+        let claim_id = Uuid::from_u128(9);
+        let with_files = Uuid::from_u128(1);
+        let without_files = Uuid::from_u128(2);
+        let claim = serde_json::json!({ "ExpenseClaims": [{
+            "ExpenseClaimID": claim_id,
+            "User": { "FirstName": "Sam", "LastName": "Lee" },
+            "Receipts": [
+                {
+                    "ReceiptID": with_files,
+                    "User": { "FirstName": "Sam", "LastName": "Lee" },
+                    "Date": "/Date(1700000000000+0000)/",
+                    "HasAttachments": true
+                },
+                {
+                    "ReceiptID": without_files,
+                    "User": { "FirstName": "Sam", "LastName": "Lee" },
+                    "Date": "/Date(1700000000000+0000)/",
+                    "HasAttachments": false
+                }
+            ]
+        }]})
+        .to_string();
+        let listing = r#"{"Attachments":[{"AttachmentID":"00000000-0000-0000-0000-000000000003","FileName":"taxi.jpg","Url":"u","MimeType":"image/jpeg"}]}"#;
+        let server = MockServer::ok([claim.as_str(), listing]).await;
+
+        let api = test_api().await.with_base_url(server.url());
+        let attachments = api
+            .get_expense_claim_receipt_attachments(claim_id)
+            .await
+            .unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].0, with_files);
+        assert_eq!(attachments[0].1[0].file_name, "taxi.jpg");
+        assert_eq!(
+            server.paths().await,
+            vec![
+                format!("/ExpenseClaims/{claim_id}"),
+                format!("/Receipts/{with_files}/Attachments"),
+            ]
+        );
+    }
+}
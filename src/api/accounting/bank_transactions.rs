@@ -1,7 +1,11 @@
-use super::query::QueryParams;
-use super::AccountingApi;
+use super::filter::WhereBuilder;
+use super::order::{BankTransactionOrderField, OrderBy};
+use super::query::{check_page_size, QueryParams};
+use super::{AccountingApi, MAX_PAGE_SIZE};
 use crate::error::XeroError;
 use crate::models::accounting::bank_transaction;
+use crate::models::accounting::common::WithWarnings;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use uuid::Uuid;
@@ -28,30 +32,45 @@ impl<'a> BankTransactionsResource<'a> {
         bank_transaction_id: Uuid,
     ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
         let path = format!("/BankTransactions/{bank_transaction_id}");
+        let query = self.api.unitdp_query();
         let resp: bank_transaction::BankTransactionsResponse = self
             .api
             .client
-            .send_request(Method::GET, &path, None, None::<()>)
+            .send_request(Method::GET, &path, query.as_slice(), None::<()>)
             .await?;
         Ok(resp.bank_transactions)
     }
 
     /// Creates one or more new spend or receive money transactions.
+    ///
+    /// Transfer types are rejected with [`XeroError::Validation`]; use
+    /// [`AccountingApi::create_bank_transfer`] to move money between accounts.
     pub async fn create(
         &self,
         transactions: Vec<bank_transaction::BankTransaction>,
         summarize_errors: Option<bool>,
     ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
-        let mut query = QueryParams::default();
+        Ok(self
+            .create_with_warnings(transactions, summarize_errors)
+            .await?
+            .items)
+    }
+
+    /// Creates one or more new bank transactions, also returning Xero's response-level warnings.
+    pub async fn create_with_warnings(
+        &self,
+        transactions: Vec<bank_transaction::BankTransaction>,
+        summarize_errors: Option<bool>,
+    ) -> Result<WithWarnings<bank_transaction::BankTransaction>, XeroError> {
+        reject_transfers(&transactions)?;
+        let mut query = self.api.unitdp_query();
         query.push_opt("summarizeErrors", summarize_errors);
 
-        let body = if transactions.len() == 1 {
-            serde_json::to_value(transactions.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(bank_transaction::BankTransactionsRequest {
+        let body = single_or_wrapped(transactions, |transactions| {
+            bank_transaction::BankTransactionsRequest {
                 bank_transactions: transactions,
-            })?
-        };
+            }
+        })?;
         let resp: bank_transaction::BankTransactionsResponse = self
             .api
             .client
@@ -62,7 +81,10 @@ impl<'a> BankTransactionsResource<'a> {
                 Some(body),
             )
             .await?;
-        Ok(resp.bank_transactions)
+        Ok(WithWarnings {
+            items: resp.bank_transactions,
+            warnings: resp.warnings,
+        })
     }
 
     /// Updates an existing spend or receive money transaction.
@@ -71,14 +93,94 @@ impl<'a> BankTransactionsResource<'a> {
         bank_transaction_id: Uuid,
         transaction_data: bank_transaction::BankTransaction,
     ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        reject_transfers(std::slice::from_ref(&transaction_data))?;
         let path = format!("/BankTransactions/{bank_transaction_id}");
+        let query = self.api.unitdp_query();
         let resp: bank_transaction::BankTransactionsResponse = self
             .api
             .client
-            .send_request(Method::POST, &path, None, Some(transaction_data))
+            .send_request(
+                Method::POST,
+                &path,
+                query.as_slice(),
+                Some(transaction_data),
+            )
+            .await?;
+        Ok(resp.bank_transactions)
+    }
+
+    /// Deletes a spend or receive money transaction by setting its status to
+    /// `DELETED`. Xero refuses reconciled transactions.
+    pub async fn delete(
+        &self,
+        bank_transaction_id: Uuid,
+    ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        let path = format!("/BankTransactions/{bank_transaction_id}");
+        let resp: bank_transaction::BankTransactionsResponse = self
+            .api
+            .set_status(&path, bank_transaction::BankTransactionStatus::Deleted)
             .await?;
         Ok(resp.bank_transactions)
     }
+
+    /// Retrieves every unreconciled transaction on the bank account
+    /// `bank_account_id`, across all pages.
+    pub async fn unreconciled(
+        &self,
+        bank_account_id: Uuid,
+    ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        let filter = unreconciled_filter(bank_account_id);
+        let mut all = Vec::new();
+        for page in 1.. {
+            let transactions = self
+                .list()
+                .where_filter(filter.clone())
+                .page(page)
+                .page_size(MAX_PAGE_SIZE)
+                .send()
+                .await?;
+            let last = transactions.len() < MAX_PAGE_SIZE as usize;
+            all.extend(transactions);
+            if last {
+                break;
+            }
+        }
+        Ok(all)
+    }
+
+    /// Marks a transaction as reconciled or unreconciled without matching a
+    /// statement line.
+    ///
+    /// Xero only accepts this in limited cases, chiefly conversion apps
+    /// bringing across transactions that have no bank statement line to match;
+    /// otherwise it responds with a validation error. The transaction is
+    /// fetched first, because an update must carry the whole record.
+    pub async fn set_reconciled(
+        &self,
+        bank_transaction_id: Uuid,
+        is_reconciled: bool,
+    ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        let mut transaction = self
+            .get(bank_transaction_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                XeroError::Validation(format!(
+                    "Bank transaction {bank_transaction_id} was not found"
+                ))
+            })?;
+        transaction.is_reconciled = Some(is_reconciled);
+        self.update(bank_transaction_id, transaction).await
+    }
+}
+
+/// The `where` clause selecting unreconciled transactions on one bank account.
+fn unreconciled_filter(bank_account_id: Uuid) -> String {
+    WhereBuilder::new()
+        .raw("IsReconciled==false")
+        .eq_guid("BankAccount.AccountID", bank_account_id)
+        .build()
 }
 
 /// Builder for Bank Transactions list requests.
@@ -89,6 +191,7 @@ pub struct BankTransactionsListRequest<'a> {
     order_by: Option<String>,
     page: Option<u32>,
     page_size: Option<u32>,
+    unitdp: Option<u8>,
 }
 
 impl<'a> BankTransactionsListRequest<'a> {
@@ -99,6 +202,7 @@ impl<'a> BankTransactionsListRequest<'a> {
             order_by: None,
             page: None,
             page_size: None,
+            unitdp: api.unitdp,
         }
     }
 
@@ -114,25 +218,38 @@ impl<'a> BankTransactionsListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<BankTransactionOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
     }
 
+    /// Set unit decimal places for line item unit amounts.
+    pub fn unitdp(mut self, unitdp: u8) -> Self {
+        self.unitdp = Some(unitdp);
+        self
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        check_page_size("/BankTransactions", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("where", self.where_filter);
         query.push_opt_string("order", self.order_by);
         query.push_opt("page", self.page);
         query.push_opt("pageSize", self.page_size);
+        query.push_opt("unitdp", self.unitdp);
 
         let resp: bank_transaction::BankTransactionsResponse = self
             .api
@@ -191,6 +308,14 @@ impl AccountingApi {
             .await
     }
 
+    /// Retrieves every unreconciled transaction on a bank account.
+    pub async fn get_unreconciled_transactions(
+        &self,
+        bank_account_id: Uuid,
+    ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        self.bank_transactions().unreconciled(bank_account_id).await
+    }
+
     /// Updates an existing spend or receive money transaction.
     pub async fn update_bank_transaction(
         &self,
@@ -201,4 +326,63 @@ impl AccountingApi {
             .update(bank_transaction_id, transaction_data)
             .await
     }
+
+    /// Deletes a spend or receive money transaction.
+    pub async fn delete_bank_transaction(
+        &self,
+        bank_transaction_id: Uuid,
+    ) -> Result<Vec<bank_transaction::BankTransaction>, XeroError> {
+        self.bank_transactions().delete(bank_transaction_id).await
+    }
+}
+
+/// Rejects transfer-typed transactions, which Xero only accepts through
+/// `/BankTransfers`.
+fn reject_transfers(transactions: &[bank_transaction::BankTransaction]) -> Result<(), XeroError> {
+    let transfer = transactions.iter().enumerate().find_map(|(index, t)| {
+        t.transaction_type
+            .as_ref()
+            .filter(|kind| kind.is_transfer())
+            .map(|kind| (index, kind))
+    });
+    match transfer {
+        Some((index, kind)) => Err(XeroError::Validation(format!(
+            "bank transaction {index} has type {kind}; create transfers with \
+             AccountingApi::create_bank_transfer instead"
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreconciled_filter_matches_the_bank_account() {
+        assert_eq!(
+            unreconciled_filter(Uuid::nil()),
+            "IsReconciled==false AND \
+             BankAccount.AccountID==Guid(\"00000000-0000-0000-0000-000000000000\")"
+        );
+    }
+
+    #[test]
+    fn transfer_types_are_rejected() {
+        let transaction = |kind| bank_transaction::BankTransaction {
+            transaction_type: Some(kind),
+            ..Default::default()
+        };
+        assert!(
+            reject_transfers(&[transaction(bank_transaction::BankTransactionType::Spend)]).is_ok()
+        );
+        let err = reject_transfers(&[
+            transaction(bank_transaction::BankTransactionType::Receive),
+            transaction(bank_transaction::BankTransactionType::SpendTransfer),
+        ])
+        .unwrap_err();
+        assert!(
+            matches!(err, XeroError::Validation(msg) if msg.contains("transaction 1") && msg.contains("create_bank_transfer"))
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::bank_transaction::BankTransaction;
 use crate::models::accounting::bank_transfer;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
@@ -36,6 +37,51 @@ impl<'a> BankTransfersResource<'a> {
         Ok(resp.bank_transfers)
     }
 
+    /// Retrieves a bank transfer with both of its bank transactions.
+    ///
+    /// Any side Xero did not nest in the response is fetched by its
+    /// `FromBankTransactionID`/`ToBankTransactionID`, so both sides can be
+    /// reconciled from one call. Returns `Ok(None)` if no transfer has that ID.
+    pub async fn get_with_transactions(
+        &self,
+        bank_transfer_id: Uuid,
+    ) -> Result<Option<bank_transfer::BankTransfer>, XeroError> {
+        let mut transfer = match self.get(bank_transfer_id).await {
+            Ok(transfers) => match transfers.into_iter().next() {
+                Some(transfer) => transfer,
+                None => return Ok(None),
+            },
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if transfer.from_bank_transaction.is_none() {
+            if let Some(id) = transfer.from_bank_transaction_id {
+                transfer.from_bank_transaction = self.bank_transaction(id).await?;
+            }
+        }
+        if transfer.to_bank_transaction.is_none() {
+            if let Some(id) = transfer.to_bank_transaction_id {
+                transfer.to_bank_transaction = self.bank_transaction(id).await?;
+            }
+        }
+        Ok(Some(transfer))
+    }
+
+    async fn bank_transaction(
+        &self,
+        bank_transaction_id: Uuid,
+    ) -> Result<Option<Box<BankTransaction>>, XeroError> {
+        let transactions = self
+            .api
+            .bank_transactions()
+            .get(bank_transaction_id)
+            .await?;
+        Ok(transactions.into_iter().next().map(Box::new))
+    }
+
     /// Creates a new bank transfer.
     pub async fn create(
         &self,
@@ -126,6 +172,16 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves a bank transfer with both of its bank transactions.
+    pub async fn get_bank_transfer(
+        &self,
+        bank_transfer_id: Uuid,
+    ) -> Result<Option<bank_transfer::BankTransfer>, XeroError> {
+        self.bank_transfers()
+            .get_with_transactions(bank_transfer_id)
+            .await
+    }
+
     /// Creates a new bank transfer.
     pub async fn create_bank_transfer(
         &self,
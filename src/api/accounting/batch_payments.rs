@@ -4,7 +4,6 @@ use crate::error::XeroError;
 use crate::models::accounting::batch_payment;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
-use serde::Serialize;
 use uuid::Uuid;
 
 /// Resource accessor for Batch Payments.
@@ -55,19 +54,10 @@ impl<'a> BatchPaymentsResource<'a> {
         &self,
         batch_payment_id: Uuid,
     ) -> Result<Vec<batch_payment::BatchPayment>, XeroError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "PascalCase")]
-        struct DeleteRequest {
-            status: batch_payment::BatchPaymentStatus,
-        }
         let path = format!("/BatchPayments/{batch_payment_id}");
-        let body = DeleteRequest {
-            status: batch_payment::BatchPaymentStatus::Deleted,
-        };
         let resp: batch_payment::BatchPaymentsResponse = self
             .api
-            .client
-            .send_request(Method::POST, &path, None, Some(body))
+            .set_status(&path, batch_payment::BatchPaymentStatus::Deleted)
             .await?;
         Ok(resp.batch_payments)
     }
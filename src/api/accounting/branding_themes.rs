@@ -49,6 +49,9 @@ impl<'a> BrandingThemesResource<'a> {
     }
 
     /// Adds a payment service to a branding theme.
+    ///
+    /// Xero has no endpoint for detaching a payment service from a theme; that
+    /// can only be done in the Xero UI under the theme's payment settings.
     pub async fn add_payment_service(
         &self,
         branding_theme_id: Uuid,
@@ -2,6 +2,7 @@ use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::budget;
+use chrono::NaiveDate;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -50,23 +51,47 @@ impl<'a> BudgetsListRequest<'a> {
         }
     }
 
-    /// Filter budgets on the end date.
+    /// Filter budgets on the end date, as `YYYY-MM-DD` or `YYYY-MM`.
+    ///
+    /// Other formats are rejected when the request is sent.
     pub fn date_to(mut self, date_to: impl Into<String>) -> Self {
         self.date_to = Some(date_to.into());
         self
     }
 
-    /// Filter budgets on the start date.
+    /// Filter budgets on the start date, as `YYYY-MM-DD` or `YYYY-MM`.
+    ///
+    /// Other formats are rejected when the request is sent.
     pub fn date_from(mut self, date_from: impl Into<String>) -> Self {
         self.date_from = Some(date_from.into());
         self
     }
 
+    /// Filter budgets to those covering `date_from` through `date_to`.
+    pub fn date_range(self, date_from: NaiveDate, date_to: NaiveDate) -> Self {
+        self.date_from(date_from.to_string())
+            .date_to(date_to.to_string())
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<budget::Budget>, XeroError> {
+        let date_from = self
+            .date_from
+            .as_deref()
+            .map(parse_budget_date)
+            .transpose()?;
+        let date_to = self.date_to.as_deref().map(parse_budget_date).transpose()?;
+        if let (Some(from), Some(to)) = (date_from, date_to) {
+            if from > to {
+                return Err(XeroError::Validation(format!(
+                    "budget DateFrom {from} is after DateTo {to}"
+                )));
+            }
+        }
+
         let mut query = QueryParams::default();
-        query.push_opt_string("DateTo", self.date_to);
-        query.push_opt_string("DateFrom", self.date_from);
+        query.push_opt("DateTo", date_to);
+        query.push_opt("DateFrom", date_from);
 
         let resp: budget::BudgetsResponse = self
             .api
@@ -77,7 +102,36 @@ impl<'a> BudgetsListRequest<'a> {
     }
 }
 
+/// Parses a budget filter date given as `YYYY-MM-DD`, or as `YYYY-MM` for the
+/// first of the month.
+///
+/// Xero quietly returns no budget lines for a date it cannot read, so a
+/// malformed date is rejected here instead.
+fn parse_budget_date(value: &str) -> Result<NaiveDate, XeroError> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d"))
+        .map_err(|_| {
+            XeroError::Validation(format!(
+                "budget date {value:?} must be YYYY-MM-DD or YYYY-MM"
+            ))
+        })
+}
+
 impl AccountingApi {
+    /// Retrieves the budgets covering `date_from` through `date_to`.
+    pub async fn get_budgets_in_range(
+        &self,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> Result<Vec<budget::Budget>, XeroError> {
+        self.budgets()
+            .list()
+            .date_range(date_from, date_to)
+            .send()
+            .await
+    }
+
     /// Retrieves one or many budgets.
     pub async fn get_budgets(
         &self,
@@ -99,3 +153,21 @@ impl AccountingApi {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_dates_accept_days_and_months_only() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(parse_budget_date("2024-03-15").unwrap(), date(2024, 3, 15));
+        assert_eq!(parse_budget_date("2024-03").unwrap(), date(2024, 3, 1));
+        for bad in ["15/03/2024", "2024", "2024-13", ""] {
+            assert!(matches!(
+                parse_budget_date(bad),
+                Err(XeroError::Validation(_))
+            ));
+        }
+    }
+}
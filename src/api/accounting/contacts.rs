@@ -1,11 +1,36 @@
-use super::query::QueryParams;
-use super::AccountingApi;
+use super::attachments::AttachmentEndpoint;
+use super::filter::WhereBuilder;
+use super::order::{ContactOrderField, OrderBy};
+use super::pagination::Paginated;
+use super::query::{check_page_size, check_pager_page_size, QueryParams};
+use super::{AccountingApi, MAX_PAGE_SIZE};
 use crate::error::XeroError;
-use crate::models::accounting::contact::{self, CISSettings, CISSettingsResponse};
+use crate::models::accounting::attachment::Attachment;
+use crate::models::accounting::common::WithWarnings;
+use crate::models::accounting::contact::{
+    self, CISSettings, CISSettingsResponse, ContactPatch, UpsertedContacts,
+};
+use crate::response::Response;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
+use log::{debug, warn};
 use reqwest::Method;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+/// Maximum number of `MergedToContactID` hops followed by
+/// [`ContactsResource::get_following_merge`].
+pub const MAX_MERGE_DEPTH: usize = 5;
+
+/// Account numbers looked up per `where` query in
+/// [`ContactsResource::upsert_by_account_number`], keeping the URL short.
+const UPSERT_LOOKUP_BATCH: usize = 25;
+/// Contacts sent per `POST /Contacts` in [`ContactsResource::upsert_by_account_number`].
+const UPSERT_WRITE_BATCH: usize = 50;
+
+/// Most additional persons Xero keeps on a contact.
+pub const MAX_CONTACT_PERSONS: usize = 5;
+
 /// Resource accessor for Contacts.
 #[derive(Debug, Clone, Copy)]
 pub struct ContactsResource<'a> {
@@ -22,6 +47,18 @@ impl<'a> ContactsResource<'a> {
         ContactsListRequest::new(self.api)
     }
 
+    /// Returns a pager over every contact in summary form, for bulk exports.
+    /// See [`ContactsExport`].
+    pub fn export(&self, include_archived: bool) -> ContactsExport<'a> {
+        ContactsExport {
+            api: self.api,
+            include_archived,
+            page: 1,
+            page_size: MAX_PAGE_SIZE,
+            exhausted: false,
+        }
+    }
+
     /// Retrieves a single contact by ID.
     pub async fn get(&self, contact_id: Uuid) -> Result<Vec<contact::Contact>, XeroError> {
         let path = format!("/Contacts/{contact_id}");
@@ -33,22 +70,182 @@ impl<'a> ContactsResource<'a> {
         Ok(resp.contacts)
     }
 
+    /// Searches contacts by `term`, best matches first, returning at most `limit`.
+    ///
+    /// Xero's `searchTerm` is a case-insensitive "contains" match across
+    /// `Name`, `FirstName`, `LastName`, `ContactNumber`, `CompanyNumber` and
+    /// `EmailAddress`, returned in Xero's own order. The first page of hits is
+    /// re-ranked by how closely `Name` matches: an exact name, then a name that
+    /// starts with the term, then a word in the name that does, then a name
+    /// containing it anywhere, then contacts that only matched on another
+    /// field. Ties keep Xero's order.
+    pub async fn search(
+        &self,
+        term: &str,
+        limit: usize,
+    ) -> Result<Vec<contact::Contact>, XeroError> {
+        let contacts = self.list().search_term(term).send().await?;
+        Ok(rank_search_results(contacts, term, limit))
+    }
+
+    /// Retrieves a single contact by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get_one(&self, contact_id: Uuid) -> Result<Option<contact::Contact>, XeroError> {
+        match self.get(contact_id).await {
+            Ok(contacts) => Ok(contacts.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves a contact, following `MergedToContactID` to the contact it was merged into.
+    ///
+    /// Xero keeps a merged contact around with `MergedToContactID` pointing at
+    /// the surviving one, which may itself have been merged later. The chain is
+    /// followed for up to [`MAX_MERGE_DEPTH`] hops; if it loops or runs longer,
+    /// the last contact fetched is returned and a warning is logged. Returns
+    /// `Ok(None)` if any contact along the chain does not exist.
+    pub async fn get_following_merge(
+        &self,
+        contact_id: Uuid,
+    ) -> Result<Option<contact::Contact>, XeroError> {
+        let mut seen = HashSet::from([contact_id]);
+        let Some(mut contact) = self.get_one(contact_id).await? else {
+            return Ok(None);
+        };
+        while let Some(target) = contact.merged_to_contact_id {
+            if seen.len() > MAX_MERGE_DEPTH || !seen.insert(target) {
+                warn!(
+                    "Stopped following merged contacts from {contact_id} at {}",
+                    contact.contact_id.unwrap_or(target)
+                );
+                break;
+            }
+            debug!("Contact {contact_id} was merged; following it to {target}");
+            match self.get_one(target).await? {
+                Some(next) => contact = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(contact))
+    }
+
+    /// Retrieves the AR/AP balances for a contact.
+    ///
+    /// Balances are only populated on a single-contact GET, so this fetches the
+    /// full contact and returns just its `Balances`. A contact Xero returns
+    /// without balances yields zeroed amounts.
+    pub async fn balances(&self, contact_id: Uuid) -> Result<contact::ContactBalances, XeroError> {
+        let contact = self
+            .get(contact_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: "Contact not found in response".to_string(),
+            })?;
+        Ok(contact.balances.unwrap_or_default())
+    }
+
     /// Creates one or more new contacts.
     pub async fn create(
         &self,
         contacts: Vec<contact::Contact>,
     ) -> Result<Vec<contact::Contact>, XeroError> {
-        let body = if contacts.len() == 1 {
-            serde_json::to_value(contacts.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(contact::ContactsRequest { contacts })?
-        };
+        Ok(self.create_with_warnings(contacts).await?.items)
+    }
+
+    /// Creates one or more new contacts, also returning Xero's response-level warnings.
+    pub async fn create_with_warnings(
+        &self,
+        contacts: Vec<contact::Contact>,
+    ) -> Result<WithWarnings<contact::Contact>, XeroError> {
+        let body = single_or_wrapped(contacts, |contacts| contact::ContactsRequest { contacts })?;
         let resp: contact::ContactsResponse = self
             .api
             .client
             .send_request(Method::PUT, "/Contacts", None, Some(body))
             .await?;
-        Ok(resp.contacts)
+        Ok(WithWarnings {
+            items: resp.contacts,
+            warnings: resp.warnings,
+        })
+    }
+
+    /// Creates or updates contacts, matching existing ones by `AccountNumber`.
+    ///
+    /// Existing contacts (archived ones included, since Xero keeps account
+    /// numbers unique across them) are looked up in batches with a `where`
+    /// filter, and any match has its `ContactID` filled in. Everything is
+    /// then sent with `POST /Contacts`, which updates contacts that carry a
+    /// `ContactID` and creates the rest. Contacts without an account number
+    /// are always created. A `ContactID` already set by the caller is kept and
+    /// counts as an update.
+    pub async fn upsert_by_account_number(
+        &self,
+        mut contacts: Vec<contact::Contact>,
+    ) -> Result<UpsertedContacts, XeroError> {
+        let numbers: Vec<&str> = contacts
+            .iter()
+            .filter(|c| c.contact_id.is_none())
+            .filter_map(|c| c.account_number.as_deref())
+            .collect();
+        let mut existing = HashMap::new();
+        for batch in numbers.chunks(UPSERT_LOOKUP_BATCH) {
+            let filter = batch
+                .iter()
+                .map(|number| WhereBuilder::new().eq("AccountNumber", number).build())
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            let found = self
+                .list()
+                .where_filter(filter)
+                .include_archived(true)
+                .send()
+                .await?;
+            existing.extend(
+                found
+                    .into_iter()
+                    .filter_map(|c| Some((c.account_number?, c.contact_id?))),
+            );
+        }
+        for contact in &mut contacts {
+            if contact.contact_id.is_none() {
+                contact.contact_id = contact
+                    .account_number
+                    .as_ref()
+                    .and_then(|number| existing.get(number).copied());
+            }
+        }
+
+        let mut result = UpsertedContacts::default();
+        for batch in contacts.chunks(UPSERT_WRITE_BATCH) {
+            let is_update: Vec<bool> = batch.iter().map(|c| c.contact_id.is_some()).collect();
+            let body = single_or_wrapped(batch.to_vec(), |contacts| contact::ContactsRequest {
+                contacts,
+            })?;
+            let resp: contact::ContactsResponse = self
+                .api
+                .client
+                .send_request(Method::POST, "/Contacts", None, Some(body))
+                .await?;
+            // Xero returns the contacts in the order they were sent.
+            for (saved, was_update) in resp.contacts.into_iter().zip(is_update) {
+                if was_update {
+                    result.updated.push(saved);
+                } else {
+                    result.created.push(saved);
+                }
+            }
+        }
+        debug!(
+            "Upserted contacts by account number: {} created, {} updated",
+            result.created.len(),
+            result.updated.len()
+        );
+        Ok(result)
     }
 
     /// Updates an existing contact.
@@ -66,6 +263,92 @@ impl<'a> ContactsResource<'a> {
         Ok(resp.contacts)
     }
 
+    /// Applies a partial update to an existing contact.
+    ///
+    /// Only the fields set on `patch` are sent. See [`ContactPatch`] for which
+    /// fields Xero merges and which collections it replaces wholesale.
+    pub async fn patch(
+        &self,
+        contact_id: Uuid,
+        patch: ContactPatch,
+    ) -> Result<Vec<contact::Contact>, XeroError> {
+        if patch.is_empty() {
+            return Err(XeroError::Validation(
+                "ContactPatch has no fields set".to_string(),
+            ));
+        }
+        if patch.replaces_collections() {
+            debug!("Contact patch for {contact_id} replaces one or more collections wholesale");
+        }
+        let path = format!("/Contacts/{contact_id}");
+        let resp: contact::ContactsResponse = self
+            .api
+            .client
+            .send_request(Method::POST, &path, None, Some(patch))
+            .await?;
+        Ok(resp.contacts)
+    }
+
+    /// Adds `person` to the contact's additional persons.
+    ///
+    /// Xero has no endpoint for a single person: writing `ContactPersons`
+    /// replaces the whole list. This fetches the contact, appends `person` and
+    /// posts the full list back, so it races with concurrent edits to the same
+    /// contact. Fails with [`XeroError::Validation`] if the contact already has
+    /// [`MAX_CONTACT_PERSONS`] persons.
+    pub async fn add_person(
+        &self,
+        contact_id: Uuid,
+        person: contact::ContactPerson,
+    ) -> Result<contact::Contact, XeroError> {
+        let persons = self.persons(contact_id).await?;
+        let persons = with_person(persons, person)?;
+        self.replace_persons(contact_id, persons).await
+    }
+
+    /// Removes the additional person with `email_address` (compared
+    /// case-insensitively) from the contact.
+    ///
+    /// Like [`Self::add_person`], this fetches the contact and posts back the
+    /// full remaining list. Fails with [`XeroError::Validation`] if no person
+    /// has that email address.
+    pub async fn remove_person(
+        &self,
+        contact_id: Uuid,
+        email_address: &str,
+    ) -> Result<contact::Contact, XeroError> {
+        let persons = self.persons(contact_id).await?;
+        let persons = without_person(persons, email_address)?;
+        self.replace_persons(contact_id, persons).await
+    }
+
+    async fn persons(&self, contact_id: Uuid) -> Result<Vec<contact::ContactPerson>, XeroError> {
+        let contact = self.get_one(contact_id).await?.ok_or(XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: format!("Contact {contact_id} not found"),
+        })?;
+        Ok(contact.contact_persons)
+    }
+
+    async fn replace_persons(
+        &self,
+        contact_id: Uuid,
+        persons: Vec<contact::ContactPerson>,
+    ) -> Result<contact::Contact, XeroError> {
+        let patch = ContactPatch {
+            contact_persons: Some(persons),
+            ..Default::default()
+        };
+        self.patch(contact_id, patch)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Contact {contact_id} missing from update response"),
+            })
+    }
+
     /// Retrieves CIS settings for a contact (UK only).
     pub async fn cis_settings(&self, contact_id: Uuid) -> Result<Vec<CISSettings>, XeroError> {
         let path = format!("/Contacts/{contact_id}/CISSettings");
@@ -76,6 +359,38 @@ impl<'a> ContactsResource<'a> {
             .await?;
         Ok(resp.cis_settings)
     }
+
+    /// Retrieves the attachments on a contact.
+    pub async fn attachments(&self, contact_id: Uuid) -> Result<Vec<Attachment>, XeroError> {
+        self.api
+            .attachments()
+            .list(AttachmentEndpoint::Contacts, contact_id)
+            .await
+    }
+
+    /// Attaches a file (e.g. a contract or logo) to a contact.
+    pub async fn upload_attachment<B>(
+        &self,
+        contact_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.api
+            .attachments()
+            .upload_put(
+                AttachmentEndpoint::Contacts,
+                contact_id,
+                filename,
+                content_type,
+                body,
+                None,
+            )
+            .await
+    }
 }
 
 /// Builder for Contacts list requests.
@@ -128,13 +443,18 @@ impl<'a> ContactsListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<ContactOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
@@ -160,26 +480,232 @@ impl<'a> ContactsListRequest<'a> {
 
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<contact::Contact>, XeroError> {
+        check_page_size("/Contacts", self.page_size)?;
+        let query = self.query();
+
+        let resp: contact::ContactsResponse = self
+            .api
+            .client
+            .send_request(Method::GET, "/Contacts", query.as_slice(), None::<()>)
+            .await?;
+        Ok(resp.contacts)
+    }
+
+    /// Executes the list request, also returning the untouched response body.
+    ///
+    /// Use the raw JSON to read fields the [`contact::Contact`] model does not
+    /// cover yet.
+    pub async fn send_with_raw(
+        self,
+    ) -> Result<(Vec<contact::Contact>, serde_json::Value), XeroError> {
+        check_page_size("/Contacts", self.page_size)?;
+        let query = self.query();
+
+        let (resp, raw): (contact::ContactsResponse, _) = self
+            .api
+            .client
+            .send_request_with_raw(Method::GET, "/Contacts", query.as_slice(), None::<()>)
+            .await?;
+        Ok((resp.contacts, raw))
+    }
+
+    /// Executes the list request, also returning the response's rate limit
+    /// headroom, correlation id and warnings.
+    pub async fn send_detailed(self) -> Result<Response<Vec<contact::Contact>>, XeroError> {
+        check_page_size("/Contacts", self.page_size)?;
+        let query = self.query();
+
+        let (resp, meta): (contact::ContactsResponse, _) = self
+            .api
+            .client
+            .send_request_detailed(Method::GET, "/Contacts", query.as_slice(), None::<()>)
+            .await?;
+        Ok(meta.into_response(resp.contacts, resp.warnings))
+    }
+
+    fn query(&self) -> QueryParams {
         let mut query = QueryParams::default();
-        query.push_opt_csv("IDs", self.ids);
-        query.push_opt_string("where", self.where_filter);
-        query.push_opt_string("order", self.order_by);
+        query.push_opt_csv("IDs", self.ids.as_ref());
+        query.push_opt_string("where", self.where_filter.clone());
+        query.push_opt_string("order", self.order_by.clone());
         query.push_opt("page", self.page);
         query.push_opt("pageSize", self.page_size);
         query.push_opt("includeArchived", self.include_archived);
         query.push_opt("summaryOnly", self.summary_only);
-        query.push_opt_string("searchTerm", self.search_term);
+        query.push_opt_string("searchTerm", self.search_term.clone());
+        query
+    }
+}
 
-        let resp: contact::ContactsResponse = self
+/// Async cursor over all contacts, fetched with `summaryOnly=true`.
+///
+/// Summary responses leave out addresses, phones and other nested detail, so
+/// each page is cheap to fetch and parse. The pager requests the largest page
+/// size Xero allows and stops after the first short page.
+#[derive(Debug, Clone)]
+pub struct ContactsExport<'a> {
+    api: &'a AccountingApi,
+    include_archived: bool,
+    page: u32,
+    page_size: u32,
+    exhausted: bool,
+}
+
+impl ContactsExport<'_> {
+    /// Overrides the number of contacts requested per page, which defaults to
+    /// [`MAX_PAGE_SIZE`].
+    ///
+    /// Returns [`XeroError::Validation`] for zero or more than [`MAX_PAGE_SIZE`].
+    pub fn with_page_size(mut self, page_size: u32) -> Result<Self, XeroError> {
+        check_pager_page_size("/Contacts", page_size)?;
+        self.page_size = page_size;
+        Ok(self)
+    }
+
+    /// The page the next call to [`ContactsExport::next_page`] will request.
+    #[must_use]
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Fetches the next page of contacts, or `None` once all have been returned.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<contact::Contact>>, XeroError> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let contacts = self
             .api
-            .client
-            .send_request(Method::GET, "/Contacts", query.as_slice(), None::<()>)
+            .contacts()
+            .list()
+            .page(self.page)
+            .page_size(self.page_size)
+            .include_archived(self.include_archived)
+            .summary_only(true)
+            .send()
             .await?;
-        Ok(resp.contacts)
+        self.page += 1;
+        if contacts.len() < self.page_size as usize {
+            self.exhausted = true;
+        }
+        debug!(
+            "Exported {} contacts on page {}",
+            contacts.len(),
+            self.page - 1
+        );
+        if contacts.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(contacts))
+    }
+
+    /// Drains the pager, returning every remaining contact.
+    pub async fn collect_all(mut self) -> Result<Vec<contact::Contact>, XeroError> {
+        let mut all = Vec::new();
+        while let Some(page) = self.next_page().await? {
+            all.extend(page);
+        }
+        Ok(all)
+    }
+}
+
+impl Paginated for ContactsExport<'_> {
+    type Item = contact::Contact;
+
+    fn next_page(
+        &mut self,
+    ) -> impl std::future::Future<Output = Result<Option<Vec<contact::Contact>>, XeroError>> + Send
+    {
+        ContactsExport::next_page(self)
     }
 }
 
+/// Orders `contacts` by how closely their name matches `term` and keeps the first `limit`.
+fn rank_search_results(
+    mut contacts: Vec<contact::Contact>,
+    term: &str,
+    limit: usize,
+) -> Vec<contact::Contact> {
+    let term = term.trim().to_lowercase();
+    contacts.sort_by_cached_key(|contact| {
+        let name = contact.name.to_lowercase();
+        if name == term {
+            0
+        } else if name.starts_with(&term) {
+            1
+        } else if name.split_whitespace().any(|word| word.starts_with(&term)) {
+            2
+        } else if name.contains(&term) {
+            3
+        } else {
+            4
+        }
+    });
+    contacts.truncate(limit);
+    contacts
+}
+
+/// Appends `person`, refusing to go past [`MAX_CONTACT_PERSONS`].
+fn with_person(
+    mut persons: Vec<contact::ContactPerson>,
+    person: contact::ContactPerson,
+) -> Result<Vec<contact::ContactPerson>, XeroError> {
+    if persons.len() >= MAX_CONTACT_PERSONS {
+        return Err(XeroError::Validation(format!(
+            "Contact already has the maximum of {MAX_CONTACT_PERSONS} contact persons"
+        )));
+    }
+    persons.push(person);
+    Ok(persons)
+}
+
+/// Drops every person whose email address matches `email_address`.
+fn without_person(
+    mut persons: Vec<contact::ContactPerson>,
+    email_address: &str,
+) -> Result<Vec<contact::ContactPerson>, XeroError> {
+    let before = persons.len();
+    persons.retain(|person| {
+        !person
+            .email_address
+            .as_deref()
+            .is_some_and(|email| email.eq_ignore_ascii_case(email_address))
+    });
+    if persons.len() == before {
+        return Err(XeroError::Validation(format!(
+            "Contact has no contact person with email address {email_address}"
+        )));
+    }
+    Ok(persons)
+}
+
 impl AccountingApi {
+    /// Retrieves the AR/AP balances for a contact.
+    pub async fn get_contact_balances(
+        &self,
+        contact_id: Uuid,
+    ) -> Result<contact::ContactBalances, XeroError> {
+        self.contacts().balances(contact_id).await
+    }
+
+    /// Retrieves a contact, landing on the live contact if it has been merged.
+    /// See [`ContactsResource::get_following_merge`].
+    pub async fn get_contact_following_merge(
+        &self,
+        contact_id: Uuid,
+    ) -> Result<Option<contact::Contact>, XeroError> {
+        self.contacts().get_following_merge(contact_id).await
+    }
+
+    /// Retrieves every contact in summary form, including archived contacts
+    /// if asked. See [`ContactsResource::export`].
+    pub async fn export_all_contacts(
+        &self,
+        include_archived: bool,
+    ) -> Result<Vec<contact::Contact>, XeroError> {
+        self.contacts().export(include_archived).collect_all().await
+    }
+
     /// Retrieves one or many contacts.
     #[allow(clippy::too_many_arguments)]
     pub async fn get_contacts(
@@ -198,35 +724,103 @@ impl AccountingApi {
         if let Some(id) = contact_id {
             self.contacts().get(id).await
         } else {
-            let mut request = self.contacts().list();
-            if let Some(ids) = ids {
-                request = request.ids(ids);
-            }
-            if let Some(filter) = where_filter {
-                request = request.where_filter(filter);
-            }
-            if let Some(order) = order_by {
-                request = request.order_by(order);
-            }
-            if let Some(page) = page {
-                request = request.page(page);
-            }
-            if let Some(page_size) = page_size {
-                request = request.page_size(page_size);
-            }
-            if let Some(include_archived) = include_archived {
-                request = request.include_archived(include_archived);
-            }
-            if let Some(summary_only) = summary_only {
-                request = request.summary_only(summary_only);
-            }
-            if let Some(search_term) = search_term {
-                request = request.search_term(search_term);
-            }
-            request.send().await
+            self.legacy_contacts_request(
+                ids,
+                where_filter,
+                order_by,
+                page,
+                page_size,
+                include_archived,
+                summary_only,
+                search_term,
+            )
+            .send()
+            .await
         }
     }
 
+    /// Like [`AccountingApi::get_contacts`], but also returns the untouched
+    /// response body. See [`ContactsListRequest::send_with_raw`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_contacts_with_raw(
+        &self,
+        contact_id: Option<Uuid>,
+        ids: Option<Vec<Uuid>>,
+        where_filter: Option<String>,
+        order_by: Option<String>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        include_archived: Option<bool>,
+        summary_only: Option<bool>,
+        search_term: Option<String>,
+    ) -> Result<(Vec<contact::Contact>, serde_json::Value), XeroError> {
+        let ids = match contact_id {
+            Some(id) => Some(vec![id]),
+            None => ids,
+        };
+        self.legacy_contacts_request(
+            ids,
+            where_filter,
+            order_by,
+            page,
+            page_size,
+            include_archived,
+            summary_only,
+            search_term,
+        )
+        .send_with_raw()
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn legacy_contacts_request(
+        &self,
+        ids: Option<Vec<Uuid>>,
+        where_filter: Option<String>,
+        order_by: Option<String>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        include_archived: Option<bool>,
+        summary_only: Option<bool>,
+        search_term: Option<String>,
+    ) -> ContactsListRequest<'_> {
+        let mut request = self.contacts().list();
+        if let Some(ids) = ids {
+            request = request.ids(ids);
+        }
+        if let Some(filter) = where_filter {
+            request = request.where_filter(filter);
+        }
+        if let Some(order) = order_by {
+            request = request.order_by(order);
+        }
+        if let Some(page) = page {
+            request = request.page(page);
+        }
+        if let Some(page_size) = page_size {
+            request = request.page_size(page_size);
+        }
+        if let Some(include_archived) = include_archived {
+            request = request.include_archived(include_archived);
+        }
+        if let Some(summary_only) = summary_only {
+            request = request.summary_only(summary_only);
+        }
+        if let Some(search_term) = search_term {
+            request = request.search_term(search_term);
+        }
+        request
+    }
+
+    /// Searches contacts by `term`, best matches first. See [`ContactsResource::search`].
+    pub async fn search_contacts(
+        &self,
+        term: &str,
+        limit: usize,
+    ) -> Result<Vec<contact::Contact>, XeroError> {
+        self.contacts().search(term, limit).await
+    }
+
     /// Creates one or more new contacts.
     pub async fn create_contacts(
         &self,
@@ -235,6 +829,15 @@ impl AccountingApi {
         self.contacts().create(contacts).await
     }
 
+    /// Creates or updates contacts keyed by account number.
+    /// See [`ContactsResource::upsert_by_account_number`].
+    pub async fn upsert_contacts_by_account_number(
+        &self,
+        contacts: Vec<contact::Contact>,
+    ) -> Result<UpsertedContacts, XeroError> {
+        self.contacts().upsert_by_account_number(contacts).await
+    }
+
     /// Updates an existing contact.
     pub async fn update_contact(
         &self,
@@ -244,6 +847,37 @@ impl AccountingApi {
         self.contacts().update(contact_id, contact_data).await
     }
 
+    /// Applies a partial update to an existing contact.
+    pub async fn patch_contact(
+        &self,
+        contact_id: Uuid,
+        patch: ContactPatch,
+    ) -> Result<Vec<contact::Contact>, XeroError> {
+        self.contacts().patch(contact_id, patch).await
+    }
+
+    /// Adds an additional person to a contact, re-posting the full list.
+    /// See [`ContactsResource::add_person`].
+    pub async fn add_contact_person(
+        &self,
+        contact_id: Uuid,
+        person: contact::ContactPerson,
+    ) -> Result<contact::Contact, XeroError> {
+        self.contacts().add_person(contact_id, person).await
+    }
+
+    /// Removes an additional person from a contact by email address.
+    /// See [`ContactsResource::remove_person`].
+    pub async fn remove_contact_person(
+        &self,
+        contact_id: Uuid,
+        email_address: &str,
+    ) -> Result<contact::Contact, XeroError> {
+        self.contacts()
+            .remove_person(contact_id, email_address)
+            .await
+    }
+
     /// Retrieves CIS settings for a contact (UK only).
     pub async fn get_contact_cis_settings(
         &self,
@@ -251,4 +885,212 @@ impl AccountingApi {
     ) -> Result<Vec<CISSettings>, XeroError> {
         self.contacts().cis_settings(contact_id).await
     }
+
+    /// Retrieves the attachments on a contact.
+    pub async fn get_contact_attachments(
+        &self,
+        contact_id: Uuid,
+    ) -> Result<Vec<Attachment>, XeroError> {
+        self.contacts().attachments(contact_id).await
+    }
+
+    /// Attaches a file to a contact.
+    pub async fn upload_contact_attachment<B>(
+        &self,
+        contact_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.contacts()
+            .upload_attachment(contact_id, filename, content_type, body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, MockServer};
+
+    fn contact(name: &str) -> contact::Contact {
+        serde_json::from_value(serde_json::json!({ "Name": name })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_pages_summaries_until_a_short_page() {
+        let page = |names: &[&str]| {
+            let contacts: Vec<_> = names
+                .iter()
+                .map(|name| serde_json::json!({ "Name": name }))
+                .collect();
+            serde_json::json!({ "Contacts": contacts }).to_string()
+        };
+        let bodies = vec![page(&["A", "B"]), page(&["C"])];
+        let server = MockServer::ok(bodies).await;
+
+        let api = test_support::accounting_api(&server).await;
+        let mut export = api.contacts().export(true);
+        export.page_size = 2;
+        let names: Vec<_> = export
+            .collect_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+
+        assert_eq!(names, vec!["A", "B", "C"]);
+        assert_eq!(
+            server.paths().await,
+            vec![
+                "/Contacts?page=1&pageSize=2&includeArchived=true&summaryOnly=true",
+                "/Contacts?page=2&pageSize=2&includeArchived=true&summaryOnly=true",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn adding_a_person_posts_back_the_full_list() {
+        let contact_id = Uuid::new_v4();
+        let existing = format!(
+            r#"{{"Contacts":[{{"ContactID":"{contact_id}","Name":"Acme","ContactPersons":[{{"FirstName":"Ann","EmailAddress":"ann@acme.test"}}]}}]}}"#
+        );
+        let server = MockServer::ok([&existing, &existing]).await;
+
+        let api = test_support::accounting_api(&server).await;
+        let person = contact::ContactPerson {
+            first_name: Some("Bob".to_string()),
+            email_address: Some("bob@acme.test".to_string()),
+            ..Default::default()
+        };
+        api.add_contact_person(contact_id, person).await.unwrap();
+
+        let requests = server.requests().await;
+        assert!(requests[0].starts_with("GET "));
+        assert!(requests[1].starts_with(&format!("POST /Contacts/{contact_id}")));
+        let body = requests[1].split("\r\n\r\n").nth(1).unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "ContactPersons": [
+                { "FirstName": "Ann", "EmailAddress": "ann@acme.test" },
+                { "FirstName": "Bob", "EmailAddress": "bob@acme.test" },
+            ]})
+        );
+    }
+
+    #[test]
+    fn person_list_is_capped_and_removal_must_match() {
+        let person = |email: &str| contact::ContactPerson {
+            email_address: Some(email.to_string()),
+            ..Default::default()
+        };
+        let full: Vec<_> = (0..MAX_CONTACT_PERSONS)
+            .map(|i| person(&format!("p{i}@acme.test")))
+            .collect();
+        assert!(matches!(
+            with_person(full.clone(), person("extra@acme.test")),
+            Err(XeroError::Validation(_))
+        ));
+
+        let remaining = without_person(full.clone(), "P0@ACME.test").unwrap();
+        assert_eq!(remaining.len(), MAX_CONTACT_PERSONS - 1);
+        assert!(without_person(full, "nobody@acme.test").is_err());
+        assert_eq!(
+            without_person(vec![person("a@acme.test")], "a@acme.test").unwrap(),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn search_results_rank_name_matches_first() {
+        let contacts = vec![
+            contact("Bayside Club"),
+            contact("The Acme Company"),
+            contact("Acme"),
+            contact("Boxacme Ltd"),
+            contact("Acme Supplies"),
+        ];
+        let names: Vec<_> = rank_search_results(contacts, "ACME", 4)
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Acme", "Acme Supplies", "The Acme Company", "Boxacme Ltd"]
+        );
+    }
+
+    #[tokio::test]
+    async fn following_merge_lands_on_live_contact() {
+        let merged = Uuid::new_v4();
+        let live = Uuid::new_v4();
+        let bodies = [
+            format!(
+                r#"{{"Contacts":[{{"ContactID":"{merged}","Name":"Old","MergedToContactID":"{live}"}}]}}"#
+            ),
+            format!(r#"{{"Contacts":[{{"ContactID":"{live}","Name":"New"}}]}}"#),
+        ];
+        let server = MockServer::ok(bodies).await;
+
+        let api = test_support::accounting_api(&server).await;
+
+        let contact = api
+            .get_contact_following_merge(merged)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(contact.contact_id, Some(live));
+        assert_eq!(contact.name, "New");
+        assert_eq!(
+            server.paths().await,
+            vec![format!("/Contacts/{merged}"), format!("/Contacts/{live}")]
+        );
+    }
+
+    #[tokio::test]
+    async fn upsert_updates_contacts_matched_by_account_number() {
+        let existing_id = Uuid::new_v4();
+        let created_id = Uuid::new_v4();
+        let bodies = [
+            format!(
+                r#"{{"Contacts":[{{"ContactID":"{existing_id}","Name":"Old","AccountNumber":"A-1"}}]}}"#
+            ),
+            format!(
+                r#"{{"Contacts":[
+                    {{"ContactID":"{existing_id}","Name":"Acme","AccountNumber":"A-1"}},
+                    {{"ContactID":"{created_id}","Name":"Bravo","AccountNumber":"B-2"}}
+                ]}}"#
+            ),
+        ];
+        let server = MockServer::ok(bodies).await;
+
+        let api = test_support::accounting_api(&server).await;
+
+        let with_number = |name: &str, number: &str| {
+            let mut c = contact(name);
+            c.account_number = Some(number.to_string());
+            c
+        };
+        let result = api
+            .upsert_contacts_by_account_number(vec![
+                with_number("Acme", "A-1"),
+                with_number("Bravo", "B-2"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].contact_id, Some(existing_id));
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.created[0].contact_id, Some(created_id));
+
+        let requests = server.request_lines().await;
+        assert!(requests[0].starts_with("GET /Contacts?where=AccountNumber"));
+        assert!(requests[0].contains("OR"));
+        assert_eq!(requests[1], "POST /Contacts");
+    }
 }
@@ -1,12 +1,31 @@
-use super::query::QueryParams;
+use super::order::{CreditNoteOrderField, OrderBy};
+use super::query::{check_page_size, QueryParams};
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::common::Allocation;
+use crate::models::accounting::common::WithWarnings;
 use crate::models::accounting::credit_note;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
+use log::warn;
 use reqwest::Method;
 use uuid::Uuid;
 
+/// Outcome of [`CreditNotesResource::reallocate`].
+///
+/// The old allocation has been removed once this is returned. If `allocated`
+/// is an error, the credit note is left with that amount unallocated; retry
+/// with [`CreditNotesResource::allocate`] or restore `removed`. Marked
+/// `#[must_use]` because dropping it unread would hide that failure.
+#[derive(Debug)]
+#[must_use = "`allocated` may hold an error that leaves the credit note unallocated"]
+pub struct CreditNoteReallocation {
+    /// The deleted allocation, as Xero returned it.
+    pub removed: Allocation,
+    /// The new allocation, or why it could not be made.
+    pub allocated: Result<Vec<Allocation>, XeroError>,
+}
+
 /// Resource accessor for Credit Notes.
 #[derive(Debug, Clone, Copy)]
 pub struct CreditNotesResource<'a> {
@@ -29,10 +48,11 @@ impl<'a> CreditNotesResource<'a> {
         credit_note_id: Uuid,
     ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
         let path = format!("/CreditNotes/{credit_note_id}");
+        let query = self.api.unitdp_query();
         let resp: credit_note::CreditNotesResponse = self
             .api
             .client
-            .send_request(Method::GET, &path, None, None::<()>)
+            .send_request(Method::GET, &path, query.as_slice(), None::<()>)
             .await?;
         Ok(resp.credit_notes)
     }
@@ -43,19 +63,32 @@ impl<'a> CreditNotesResource<'a> {
         credit_notes: Vec<credit_note::CreditNote>,
         summarize_errors: Option<bool>,
     ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
-        let mut query = QueryParams::default();
+        Ok(self
+            .create_with_warnings(credit_notes, summarize_errors)
+            .await?
+            .items)
+    }
+
+    /// Creates one or more new credit notes, also returning Xero's response-level warnings.
+    pub async fn create_with_warnings(
+        &self,
+        credit_notes: Vec<credit_note::CreditNote>,
+        summarize_errors: Option<bool>,
+    ) -> Result<WithWarnings<credit_note::CreditNote>, XeroError> {
+        let mut query = self.api.unitdp_query();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if credit_notes.len() == 1 {
-            serde_json::to_value(credit_notes.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(credit_note::CreditNotesRequest { credit_notes })?
-        };
+        let body = single_or_wrapped(credit_notes, |credit_notes| {
+            credit_note::CreditNotesRequest { credit_notes }
+        })?;
         let resp: credit_note::CreditNotesResponse = self
             .api
             .client
             .send_request(Method::PUT, "/CreditNotes", query.as_slice(), Some(body))
             .await?;
-        Ok(resp.credit_notes)
+        Ok(WithWarnings {
+            items: resp.credit_notes,
+            warnings: resp.warnings,
+        })
     }
 
     /// Updates an existing credit note.
@@ -65,14 +98,48 @@ impl<'a> CreditNotesResource<'a> {
         credit_note_data: credit_note::CreditNote,
     ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
         let path = format!("/CreditNotes/{credit_note_id}");
+        let query = self.api.unitdp_query();
         let resp: credit_note::CreditNotesResponse = self
             .api
             .client
-            .send_request(Method::POST, &path, None, Some(credit_note_data))
+            .send_request(
+                Method::POST,
+                &path,
+                query.as_slice(),
+                Some(credit_note_data),
+            )
             .await?;
         Ok(resp.credit_notes)
     }
 
+    /// Deletes a `DRAFT` or `SUBMITTED` credit note by setting its status to `DELETED`.
+    pub async fn delete(
+        &self,
+        credit_note_id: Uuid,
+    ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        self.set_status(credit_note_id, credit_note::CreditNoteStatus::Deleted)
+            .await
+    }
+
+    /// Voids an `AUTHORISED` credit note that has not been allocated.
+    pub async fn void(
+        &self,
+        credit_note_id: Uuid,
+    ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        self.set_status(credit_note_id, credit_note::CreditNoteStatus::Voided)
+            .await
+    }
+
+    async fn set_status(
+        &self,
+        credit_note_id: Uuid,
+        status: credit_note::CreditNoteStatus,
+    ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        let path = format!("/CreditNotes/{credit_note_id}");
+        let resp: credit_note::CreditNotesResponse = self.api.set_status(&path, status).await?;
+        Ok(resp.credit_notes)
+    }
+
     /// Allocates a credit note.
     pub async fn allocate(
         &self,
@@ -89,17 +156,51 @@ impl<'a> CreditNotesResource<'a> {
         Ok(resp.allocations)
     }
 
-    /// Deletes a credit note allocation.
+    /// Deletes a credit note allocation, returning the deleted allocation.
+    ///
+    /// Xero returns only the allocation (marked `is_deleted`); fetch the
+    /// credit note again with `get` to see its updated remaining credit.
     pub async fn delete_allocation(
         &self,
         credit_note_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         let path = format!("/CreditNotes/{credit_note_id}/Allocations/{allocation_id}");
-        self.api
+        let resp: credit_note::DeletedAllocationResponse = self
+            .api
             .client
-            .send_request_empty_response(Method::DELETE, &path, None::<()>)
-            .await
+            .send_request(Method::DELETE, &path, None, None::<()>)
+            .await?;
+        resp.into_allocation().ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: format!("Allocation {allocation_id} not returned after deletion"),
+        })
+    }
+
+    /// Moves a credit note allocation: deletes `old_allocation_id`, then
+    /// allocates `new_allocation`.
+    ///
+    /// Xero has no transactions, so the two steps can fail independently. A
+    /// failed delete returns `Err` with nothing changed. A failed allocation is
+    /// reported in [`CreditNoteReallocation::allocated`], alongside the removed
+    /// allocation needed to recover.
+    pub async fn reallocate(
+        &self,
+        credit_note_id: Uuid,
+        old_allocation_id: Uuid,
+        new_allocation: Allocation,
+    ) -> Result<CreditNoteReallocation, XeroError> {
+        let removed = self
+            .delete_allocation(credit_note_id, old_allocation_id)
+            .await?;
+        let allocated = self.allocate(credit_note_id, vec![new_allocation]).await;
+        if let Err(e) = &allocated {
+            warn!(
+                "Credit note {credit_note_id}: allocation {old_allocation_id} was removed \
+                 but the replacement failed, leaving it unallocated: {e}"
+            );
+        }
+        Ok(CreditNoteReallocation { removed, allocated })
     }
 }
 
@@ -111,6 +212,7 @@ pub struct CreditNotesListRequest<'a> {
     order_by: Option<String>,
     page: Option<u32>,
     page_size: Option<u32>,
+    unitdp: Option<u8>,
 }
 
 impl<'a> CreditNotesListRequest<'a> {
@@ -121,6 +223,7 @@ impl<'a> CreditNotesListRequest<'a> {
             order_by: None,
             page: None,
             page_size: None,
+            unitdp: api.unitdp,
         }
     }
 
@@ -136,25 +239,38 @@ impl<'a> CreditNotesListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<CreditNoteOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
     }
 
+    /// Set unit decimal places for line item unit amounts.
+    pub fn unitdp(mut self, unitdp: u8) -> Self {
+        self.unitdp = Some(unitdp);
+        self
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        check_page_size("/CreditNotes", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("where", self.where_filter);
         query.push_opt_string("order", self.order_by);
         query.push_opt("page", self.page);
         query.push_opt("pageSize", self.page_size);
+        query.push_opt("unitdp", self.unitdp);
 
         let resp: credit_note::CreditNotesResponse = self
             .api
@@ -219,6 +335,22 @@ impl AccountingApi {
             .await
     }
 
+    /// Deletes a `DRAFT` or `SUBMITTED` credit note.
+    pub async fn delete_credit_note(
+        &self,
+        credit_note_id: Uuid,
+    ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        self.credit_notes().delete(credit_note_id).await
+    }
+
+    /// Voids an `AUTHORISED` credit note.
+    pub async fn void_credit_note(
+        &self,
+        credit_note_id: Uuid,
+    ) -> Result<Vec<credit_note::CreditNote>, XeroError> {
+        self.credit_notes().void(credit_note_id).await
+    }
+
     /// Allocates a credit note.
     pub async fn allocate_credit_note(
         &self,
@@ -230,14 +362,26 @@ impl AccountingApi {
             .await
     }
 
-    /// Deletes a credit note allocation.
+    /// Deletes a credit note allocation, returning the deleted allocation.
     pub async fn delete_credit_note_allocation(
         &self,
         credit_note_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         self.credit_notes()
             .delete_allocation(credit_note_id, allocation_id)
             .await
     }
+
+    /// Deletes a credit note allocation and allocates a replacement.
+    pub async fn reallocate_credit_note(
+        &self,
+        credit_note_id: Uuid,
+        old_allocation_id: Uuid,
+        new_allocation: Allocation,
+    ) -> Result<CreditNoteReallocation, XeroError> {
+        self.credit_notes()
+            .reallocate(credit_note_id, old_allocation_id, new_allocation)
+            .await
+    }
 }
@@ -1,7 +1,8 @@
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::currency;
-use reqwest::Method;
+use log::debug;
+use reqwest::{Method, StatusCode};
 
 /// Resource accessor for Currencies.
 #[derive(Debug, Clone, Copy)]
@@ -36,6 +37,61 @@ impl<'a> CurrenciesResource<'a> {
             .await?;
         Ok(resp.currencies)
     }
+
+    /// Ensures a currency is enabled for the organisation, adding it if missing.
+    ///
+    /// `code` must be a three-letter ISO 4217 code (case-insensitive). If another
+    /// task adds the same currency between the lookup and the create, Xero's
+    /// validation error is treated as success and the existing currency returned.
+    pub async fn ensure(&self, code: &str) -> Result<currency::Currency, XeroError> {
+        let code = normalize_iso_code(code)?;
+        if let Some(existing) = self.find(&code).await? {
+            return Ok(existing);
+        }
+
+        debug!("Currency {code} not enabled; adding it");
+        let new_currency = currency::Currency {
+            code: code.clone(),
+            description: None,
+        };
+        match self.create(new_currency).await {
+            Ok(created) => {
+                Ok(created
+                    .into_iter()
+                    .find(|c| c.code == code)
+                    .unwrap_or(currency::Currency {
+                        code,
+                        description: None,
+                    }))
+            }
+            Err(XeroError::Api { status, message }) if status == StatusCode::BAD_REQUEST => {
+                match self.find(&code).await? {
+                    Some(existing) => {
+                        debug!("Currency {code} was added concurrently");
+                        Ok(existing)
+                    }
+                    None => Err(XeroError::Api { status, message }),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn find(&self, code: &str) -> Result<Option<currency::Currency>, XeroError> {
+        Ok(self.list().await?.into_iter().find(|c| c.code == code))
+    }
+}
+
+/// Validates and upper-cases a three-letter ISO 4217 currency code.
+fn normalize_iso_code(code: &str) -> Result<String, XeroError> {
+    let code = code.trim();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(code.to_ascii_uppercase())
+    } else {
+        Err(XeroError::Validation(format!(
+            "'{code}' is not a three-letter ISO 4217 currency code"
+        )))
+    }
 }
 
 impl AccountingApi {
@@ -55,4 +111,27 @@ impl AccountingApi {
     ) -> Result<Vec<currency::Currency>, XeroError> {
         self.currencies().create(currency).await
     }
+
+    /// Ensures a currency is enabled for the organisation, adding it if missing.
+    pub async fn ensure_currency(&self, code: &str) -> Result<currency::Currency, XeroError> {
+        self.currencies().ensure(code).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_iso_code;
+
+    #[test]
+    fn normalizes_valid_codes() {
+        assert_eq!(normalize_iso_code("usd").unwrap(), "USD");
+        assert_eq!(normalize_iso_code(" NZD ").unwrap(), "NZD");
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        assert!(normalize_iso_code("US").is_err());
+        assert!(normalize_iso_code("US1").is_err());
+        assert!(normalize_iso_code("EURO").is_err());
+    }
 }
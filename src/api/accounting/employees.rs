@@ -2,6 +2,7 @@ use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::employee;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use uuid::Uuid;
@@ -38,11 +39,9 @@ impl<'a> EmployeesResource<'a> {
         &self,
         employees: Vec<employee::Employee>,
     ) -> Result<Vec<employee::Employee>, XeroError> {
-        let body = if employees.len() == 1 {
-            serde_json::to_value(employees.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(employee::EmployeesRequest { employees })?
-        };
+        let body = single_or_wrapped(employees, |employees| employee::EmployeesRequest {
+            employees,
+        })?;
         let resp: employee::EmployeesResponse = self
             .api
             .client
@@ -1,7 +1,9 @@
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
-use crate::models::accounting::expense_claim;
+use crate::models::accounting::attachment::Attachment;
+use crate::models::accounting::{expense_claim, receipt};
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use uuid::Uuid;
@@ -45,13 +47,9 @@ impl<'a> ExpenseClaimsResource<'a> {
         let mut query = QueryParams::default();
         query.push_opt("summarizeErrors", summarize_errors);
 
-        let body = if claims.len() == 1 {
-            serde_json::to_value(claims.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(expense_claim::ExpenseClaimsRequest {
-                expense_claims: claims,
-            })?
-        };
+        let body = single_or_wrapped(claims, |claims| expense_claim::ExpenseClaimsRequest {
+            expense_claims: claims,
+        })?;
         let resp: expense_claim::ExpenseClaimsResponse = self
             .api
             .client
@@ -59,6 +57,67 @@ impl<'a> ExpenseClaimsResource<'a> {
             .await?;
         Ok(resp.expense_claims)
     }
+
+    /// Retrieves the full receipts of an expense claim, in the claim's order.
+    ///
+    /// A claim embeds only receipt summaries (no contact or line items), and a
+    /// receipt does not name the claim it belongs to, so each receipt listed on
+    /// the claim is fetched by ID.
+    pub async fn receipts(
+        &self,
+        expense_claim_id: Uuid,
+    ) -> Result<Vec<receipt::Receipt>, XeroError> {
+        let receipt_ids: Vec<Uuid> = self
+            .get(expense_claim_id)
+            .await?
+            .iter()
+            .flat_map(expense_claim::ExpenseClaim::receipt_ids)
+            .collect();
+
+        let mut receipts = Vec::with_capacity(receipt_ids.len());
+        for receipt_id in receipt_ids {
+            receipts.extend(self.api.receipts().get(receipt_id).await?);
+        }
+        Ok(receipts)
+    }
+
+    /// Retrieves the attachments on each receipt of an expense claim, keyed by
+    /// receipt ID.
+    ///
+    /// Xero has no attachments endpoint for the claim itself; the supporting
+    /// documents live on its receipts. Receipts Xero reports as having no
+    /// attachments are skipped without a request.
+    pub async fn receipt_attachments(
+        &self,
+        expense_claim_id: Uuid,
+    ) -> Result<Vec<(Uuid, Vec<Attachment>)>, XeroError> {
+        let receipt_ids: Vec<Uuid> = self
+            .get(expense_claim_id)
+            .await?
+            .into_iter()
+            .flat_map(|claim| claim.receipts)
+            .filter(|receipt| receipt.has_attachments != Some(false))
+            .filter_map(|receipt| receipt.receipt_id)
+            .collect();
+
+        let mut attachments = Vec::with_capacity(receipt_ids.len());
+        for receipt_id in receipt_ids {
+            let receipt_attachments = self.api.receipts().attachments(receipt_id).await?;
+            attachments.push((receipt_id, receipt_attachments));
+        }
+        Ok(attachments)
+    }
+
+    /// Moves an expense claim to a new status, e.g. `AUTHORISED` or `VOIDED`.
+    pub async fn update_status(
+        &self,
+        expense_claim_id: Uuid,
+        status: expense_claim::ExpenseClaimStatus,
+    ) -> Result<Vec<expense_claim::ExpenseClaim>, XeroError> {
+        let path = format!("/ExpenseClaims/{expense_claim_id}");
+        let resp: expense_claim::ExpenseClaimsResponse = self.api.set_status(&path, status).await?;
+        Ok(resp.expense_claims)
+    }
 }
 
 /// Builder for Expense Claims list requests.
@@ -138,4 +197,33 @@ impl AccountingApi {
             .create_or_update(claims, summarize_errors)
             .await
     }
+
+    /// Retrieves the full receipts of an expense claim.
+    pub async fn get_receipts_for_expense_claim(
+        &self,
+        expense_claim_id: Uuid,
+    ) -> Result<Vec<receipt::Receipt>, XeroError> {
+        self.expense_claims().receipts(expense_claim_id).await
+    }
+
+    /// Retrieves the attachments on each receipt of an expense claim.
+    pub async fn get_expense_claim_receipt_attachments(
+        &self,
+        expense_claim_id: Uuid,
+    ) -> Result<Vec<(Uuid, Vec<Attachment>)>, XeroError> {
+        self.expense_claims()
+            .receipt_attachments(expense_claim_id)
+            .await
+    }
+
+    /// Moves an expense claim to a new status.
+    pub async fn update_expense_claim_status(
+        &self,
+        expense_claim_id: Uuid,
+        status: expense_claim::ExpenseClaimStatus,
+    ) -> Result<Vec<expense_claim::ExpenseClaim>, XeroError> {
+        self.expense_claims()
+            .update_status(expense_claim_id, status)
+            .await
+    }
 }
@@ -0,0 +1,136 @@
+//! Helpers for building values for the Accounting API `where` query parameter.
+//!
+//! Xero parses `where` clauses with its own expression syntax, where dates must
+//! be written as `DateTime(...)` literals rather than ISO 8601 strings.
+
+use chrono::{DateTime, Utc};
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// Formats a timestamp as a Xero `DateTime(y,m,d,H,M,S)` filter literal.
+pub fn datetime_literal(dt: DateTime<Utc>) -> String {
+    dt.format("DateTime(%Y,%m,%d,%H,%M,%S)").to_string()
+}
+
+/// Returns a `where` clause matching records updated at or after `dt`.
+///
+/// This is the filter form of the `If-Modified-Since` header, for combining
+/// with other clauses in a single `where` parameter.
+pub fn modified_since_filter(dt: DateTime<Utc>) -> String {
+    format!("UpdatedDateUTC >= {}", datetime_literal(dt))
+}
+
+/// Builds a `where` filter from clauses joined with `AND`.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use xero_rs_async::api::accounting::filter::WhereBuilder;
+///
+/// let filter = WhereBuilder::new()
+///     .eq("Status", "AUTHORISED")
+///     .modified_since(Utc.with_ymd_and_hms(2024, 1, 31, 13, 45, 0).unwrap())
+///     .build();
+/// assert_eq!(
+///     filter,
+///     "Status==\"AUTHORISED\" AND UpdatedDateUTC >= DateTime(2024,01,31,13,45,00)"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WhereBuilder {
+    clauses: Vec<String>,
+}
+
+impl WhereBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches a field against a string value, e.g. `Status=="AUTHORISED"`.
+    ///
+    /// Backslashes and double quotes in `value` are escaped, so it cannot end
+    /// or break out of the string literal.
+    pub fn eq(self, field: &str, value: impl Display) -> Self {
+        let value = value.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+        self.raw(format!("{field}==\"{value}\""))
+    }
+
+    /// Matches a field against an ID, e.g. `Contact.ContactID==Guid("...")`.
+    pub fn eq_guid(self, field: &str, id: Uuid) -> Self {
+        self.raw(format!("{field}==Guid(\"{id}\")"))
+    }
+
+    /// Matches a date field at or after `dt`.
+    pub fn on_or_after(self, field: &str, dt: DateTime<Utc>) -> Self {
+        self.raw(format!("{field} >= {}", datetime_literal(dt)))
+    }
+
+    /// Matches a date field strictly before `dt`.
+    pub fn before(self, field: &str, dt: DateTime<Utc>) -> Self {
+        self.raw(format!("{field} < {}", datetime_literal(dt)))
+    }
+
+    /// Matches records updated at or after `dt`. See [`modified_since_filter`].
+    pub fn modified_since(self, dt: DateTime<Utc>) -> Self {
+        self.raw(modified_since_filter(dt))
+    }
+
+    /// Adds a clause verbatim.
+    pub fn raw(mut self, clause: impl Into<String>) -> Self {
+        self.clauses.push(clause.into());
+        self
+    }
+
+    /// Returns `true` if no clauses have been added.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Joins the clauses into a single `where` value.
+    pub fn build(&self) -> String {
+        self.clauses.join(" AND ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn modified_since_uses_datetime_literal() {
+        let dt = Utc.with_ymd_and_hms(2023, 3, 5, 7, 8, 9).unwrap();
+        assert_eq!(
+            modified_since_filter(dt),
+            "UpdatedDateUTC >= DateTime(2023,03,05,07,08,09)"
+        );
+    }
+
+    #[test]
+    fn builder_joins_clauses() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let filter = WhereBuilder::new()
+            .eq_guid("Contact.ContactID", Uuid::nil())
+            .on_or_after("Date", start)
+            .before("Date", end)
+            .eq("Reference", "say \"hi\"")
+            .build();
+        assert_eq!(
+            filter,
+            "Contact.ContactID==Guid(\"00000000-0000-0000-0000-000000000000\") \
+             AND Date >= DateTime(2024,01,01,00,00,00) \
+             AND Date < DateTime(2024,02,01,00,00,00) \
+             AND Reference==\"say \\\"hi\\\"\""
+        );
+    }
+
+    #[test]
+    fn eq_escapes_backslashes_before_quotes() {
+        let trailing = WhereBuilder::new().eq("Name", "abc\\").build();
+        assert_eq!(trailing, r#"Name=="abc\\""#);
+
+        let breakout = WhereBuilder::new().eq("Name", r#"x\" OR Name!=""#).build();
+        assert_eq!(breakout, r#"Name=="x\\\" OR Name!=\"""#);
+    }
+}
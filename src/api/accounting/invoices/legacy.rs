@@ -1,4 +1,5 @@
 use super::super::AccountingApi;
+use super::{InvoiceWithAttachment, InvoicesListRequest};
 use crate::error::XeroError;
 use crate::models::accounting::invoice;
 use chrono::{DateTime, Utc};
@@ -24,38 +25,133 @@ impl AccountingApi {
         if let Some(id) = invoice_id {
             self.invoices().get(id).await
         } else {
-            let mut request = self.invoices().list();
-            if let Some(invoice_numbers) = invoice_numbers {
-                request = request.invoice_numbers(invoice_numbers);
-            }
-            if let Some(contact_ids) = contact_ids {
-                request = request.contact_ids(contact_ids);
-            }
-            if let Some(statuses) = statuses {
-                request = request.statuses(statuses);
-            }
-            if let Some(filter) = where_filter {
-                request = request.where_filter(filter);
-            }
-            if let Some(order) = order_by {
-                request = request.order_by(order);
-            }
-            if let Some(page) = page {
-                request = request.page(page);
-            }
-            if let Some(page_size) = page_size {
-                request = request.page_size(page_size);
-            }
-            if let Some(summary_only) = summary_only {
-                request = request.summary_only(summary_only);
-            }
-            if let Some(search_term) = search_term {
-                request = request.search_term(search_term);
-            }
-            request.send().await
+            self.legacy_invoices_request(
+                invoice_numbers,
+                contact_ids,
+                statuses,
+                where_filter,
+                order_by,
+                page,
+                page_size,
+                summary_only,
+                search_term,
+            )
+            .send()
+            .await
         }
     }
 
+    /// Retrieves the headline fields of invoices matching `where_filter`, one
+    /// page at a time. See [`InvoicesListRequest::send_headers`].
+    pub async fn get_invoices_summary(
+        &self,
+        where_filter: Option<String>,
+        page: Option<u32>,
+    ) -> Result<Vec<invoice::InvoiceHeader>, XeroError> {
+        let mut request = self.invoices().list();
+        if let Some(filter) = where_filter {
+            request = request.where_filter(filter);
+        }
+        if let Some(page) = page {
+            request = request.page(page);
+        }
+        request.send_headers().await
+    }
+
+    /// Like [`AccountingApi::get_invoices`], but also returns the untouched
+    /// response body. See [`InvoicesListRequest::send_with_raw`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_invoices_with_raw(
+        &self,
+        invoice_id: Option<Uuid>,
+        invoice_numbers: Option<Vec<String>>,
+        contact_ids: Option<Vec<Uuid>>,
+        statuses: Option<Vec<String>>,
+        where_filter: Option<String>,
+        order_by: Option<String>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        summary_only: Option<bool>,
+        search_term: Option<String>,
+    ) -> Result<(Vec<invoice::Invoice>, serde_json::Value), XeroError> {
+        let mut request = self.legacy_invoices_request(
+            invoice_numbers,
+            contact_ids,
+            statuses,
+            where_filter,
+            order_by,
+            page,
+            page_size,
+            summary_only,
+            search_term,
+        );
+        if let Some(id) = invoice_id {
+            request = request.ids([id]);
+        }
+        request.send_with_raw().await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn legacy_invoices_request(
+        &self,
+        invoice_numbers: Option<Vec<String>>,
+        contact_ids: Option<Vec<Uuid>>,
+        statuses: Option<Vec<String>>,
+        where_filter: Option<String>,
+        order_by: Option<String>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        summary_only: Option<bool>,
+        search_term: Option<String>,
+    ) -> InvoicesListRequest<'_> {
+        let mut request = self.invoices().list();
+        if let Some(invoice_numbers) = invoice_numbers {
+            request = request.invoice_numbers(invoice_numbers);
+        }
+        if let Some(contact_ids) = contact_ids {
+            request = request.contact_ids(contact_ids);
+        }
+        if let Some(statuses) = statuses {
+            request = request.statuses(statuses);
+        }
+        if let Some(filter) = where_filter {
+            request = request.where_filter(filter);
+        }
+        if let Some(order) = order_by {
+            request = request.order_by(order);
+        }
+        if let Some(page) = page {
+            request = request.page(page);
+        }
+        if let Some(page_size) = page_size {
+            request = request.page_size(page_size);
+        }
+        if let Some(summary_only) = summary_only {
+            request = request.summary_only(summary_only);
+        }
+        if let Some(search_term) = search_term {
+            request = request.search_term(search_term);
+        }
+        request
+    }
+
+    /// Retrieves an invoice with its payments populated, or `None` if not found.
+    pub async fn get_invoice_with_payments(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Option<invoice::Invoice>, XeroError> {
+        self.invoices().get_with_payments(invoice_id).await
+    }
+
+    /// Retrieves the invoice with the given number, or `None` if not found.
+    /// See [`InvoicesResource::get_by_number`](super::InvoicesResource::get_by_number).
+    pub async fn get_invoice_by_number(
+        &self,
+        number: &str,
+    ) -> Result<Option<invoice::Invoice>, XeroError> {
+        self.invoices().get_by_number(number).await
+    }
+
     /// Creates one or more new invoices.
     pub async fn create_invoices(
         &self,
@@ -65,6 +161,19 @@ impl AccountingApi {
         self.invoices().create(invoices, summarize_errors).await
     }
 
+    /// Creates invoices, splitting them into requests that stay under the
+    /// size limit. See [`InvoicesResource::create_batched`](super::InvoicesResource::create_batched).
+    pub async fn create_invoices_batched(
+        &self,
+        invoices: Vec<invoice::Invoice>,
+        summarize_errors: Option<bool>,
+        max_request_bytes: Option<usize>,
+    ) -> Result<Vec<invoice::Invoice>, XeroError> {
+        self.invoices()
+            .create_batched(invoices, summarize_errors, max_request_bytes)
+            .await
+    }
+
     /// Updates an existing invoice.
     pub async fn update_invoice(
         &self,
@@ -74,6 +183,28 @@ impl AccountingApi {
         self.invoices().update(invoice_id, invoice_data).await
     }
 
+    /// Deletes a `DRAFT` or `SUBMITTED` invoice.
+    pub async fn delete_invoice(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Vec<invoice::Invoice>, XeroError> {
+        self.invoices().delete(invoice_id).await
+    }
+
+    /// Voids an `AUTHORISED` invoice.
+    pub async fn void_invoice(&self, invoice_id: Uuid) -> Result<Vec<invoice::Invoice>, XeroError> {
+        self.invoices().void(invoice_id).await
+    }
+
+    /// Sets an invoice's `SentToContact` flag without emailing it.
+    pub async fn mark_invoice_sent(
+        &self,
+        invoice_id: Uuid,
+        sent: bool,
+    ) -> Result<invoice::Invoice, XeroError> {
+        self.invoices().mark_sent(invoice_id, sent).await
+    }
+
     /// Retrieves the online invoice URL for a sales invoice.
     pub async fn get_online_invoice_url(
         &self,
@@ -82,8 +213,57 @@ impl AccountingApi {
         self.invoices().online_invoice_url(invoice_id).await
     }
 
+    /// Fills in the invoice's `online_url` if it is not already set, returning the URL.
+    pub async fn ensure_online_invoice_url<'i>(
+        &self,
+        invoice: &'i mut invoice::Invoice,
+    ) -> Result<&'i str, XeroError> {
+        self.invoices().ensure_online_url(invoice).await
+    }
+
+    /// Returns the IDs of invoices matching `where_filter` that have attachments.
+    pub async fn invoices_with_attachments(
+        &self,
+        where_filter: &str,
+    ) -> Result<Vec<Uuid>, XeroError> {
+        self.invoices().with_attachments(where_filter).await
+    }
+
+    /// Returns the IDs of invoices matching `where_filter` that have no attachments yet.
+    pub async fn invoices_needing_attachments(
+        &self,
+        where_filter: &str,
+    ) -> Result<Vec<Uuid>, XeroError> {
+        self.invoices().without_attachments(where_filter).await
+    }
+
     /// Emails a sales invoice from Xero.
     pub async fn email_invoice(&self, invoice_id: Uuid) -> Result<(), XeroError> {
         self.invoices().email(invoice_id).await
     }
+
+    /// Emails several sales invoices, returning the outcome for each.
+    pub async fn email_invoices(
+        &self,
+        invoice_ids: Vec<Uuid>,
+    ) -> Vec<(Uuid, Result<(), XeroError>)> {
+        self.invoices().email_many(invoice_ids).await
+    }
+
+    /// Creates an invoice and attaches a file to it, returning both outcomes.
+    pub async fn create_invoice_with_attachment<B>(
+        &self,
+        invoice: invoice::Invoice,
+        filename: &str,
+        content_type: &str,
+        body: B,
+        include_online: Option<bool>,
+    ) -> Result<InvoiceWithAttachment, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.invoices()
+            .create_with_attachment(invoice, filename, content_type, body, include_online)
+            .await
+    }
 }
@@ -1,44 +1,112 @@
-use super::super::query::QueryParams;
-use super::super::AccountingApi;
+use super::super::order::{InvoiceOrderField, OrderBy};
+use super::super::pagination::Paginated;
+use super::super::query::{check_page_size, check_pager_page_size, QueryParams};
+use super::super::{AccountingApi, MAX_PAGE_SIZE};
 use crate::error::XeroError;
-use crate::models::accounting::invoice;
+use crate::models::accounting::invoice::{self, InvoiceStatus};
+use crate::response::Response;
 use reqwest::Method;
 use uuid::Uuid;
 
+/// Filters and paging for an Invoices list request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InvoiceQuery {
+    pub(crate) invoice_numbers: Option<Vec<String>>,
+    pub(crate) contact_ids: Option<Vec<Uuid>>,
+    pub(crate) statuses: Option<Vec<String>>,
+    pub(crate) where_filter: Option<String>,
+    pub(crate) order_by: Option<String>,
+    pub(crate) page: Option<u32>,
+    pub(crate) page_size: Option<u32>,
+    pub(crate) summary_only: Option<bool>,
+    pub(crate) search_term: Option<String>,
+    pub(crate) ids: Option<Vec<Uuid>>,
+    pub(crate) created_by_my_app: Option<bool>,
+    pub(crate) unitdp: Option<u8>,
+}
+
+impl InvoiceQuery {
+    /// Rejects filter values Xero accepts but silently misapplies.
+    ///
+    /// Xero drops an empty `IDs`, `InvoiceNumbers`, `ContactIDs`, `Statuses` or
+    /// `SearchTerm` and returns every invoice, and matches an unknown status
+    /// (e.g. the US spelling `AUTHORIZED`) against nothing, returning none.
+    fn validate(&self) -> Result<(), XeroError> {
+        let empty = [
+            ("IDs", self.ids.as_ref().is_some_and(Vec::is_empty)),
+            (
+                "InvoiceNumbers",
+                self.invoice_numbers.as_ref().is_some_and(Vec::is_empty),
+            ),
+            (
+                "ContactIDs",
+                self.contact_ids.as_ref().is_some_and(Vec::is_empty),
+            ),
+            (
+                "Statuses",
+                self.statuses.as_ref().is_some_and(Vec::is_empty),
+            ),
+            (
+                "SearchTerm",
+                self.search_term
+                    .as_ref()
+                    .is_some_and(|term| term.trim().is_empty()),
+            ),
+        ];
+        if let Some((name, _)) = empty.iter().find(|(_, is_empty)| *is_empty) {
+            return Err(XeroError::Validation(format!(
+                "{name} filter for /Invoices is empty; Xero would ignore it and return every invoice"
+            )));
+        }
+
+        for status in self.statuses.iter().flatten() {
+            let known =
+                serde_json::from_value::<InvoiceStatus>(serde_json::Value::String(status.clone()))
+                    .is_ok();
+            if !known {
+                return Err(XeroError::Validation(format!(
+                    "unknown invoice status {status:?}; expected one of DRAFT, SUBMITTED, \
+                     DELETED, AUTHORISED, PAID or VOIDED"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Assembles the `GET /Invoices` query string parameters, in a stable order.
+pub(crate) fn build_invoice_query(query: &InvoiceQuery) -> QueryParams {
+    let mut params = QueryParams::default();
+    params.push_opt_csv("IDs", query.ids.as_ref());
+    params.push_opt_csv("InvoiceNumbers", query.invoice_numbers.as_ref());
+    params.push_opt_csv("ContactIDs", query.contact_ids.as_ref());
+    params.push_opt_csv("Statuses", query.statuses.as_ref());
+    params.push_opt_string("where", query.where_filter.clone());
+    params.push_opt_string("order", query.order_by.clone());
+    params.push_opt("page", query.page);
+    params.push_opt("pageSize", query.page_size);
+    params.push_opt("summaryOnly", query.summary_only);
+    params.push_opt_string("SearchTerm", query.search_term.clone());
+    params.push_opt("createdByMyApp", query.created_by_my_app);
+    params.push_opt("unitdp", query.unitdp);
+    params
+}
+
 /// Builder for Invoice list requests.
 #[derive(Debug, Clone)]
 pub struct InvoicesListRequest<'a> {
     api: &'a AccountingApi,
-    invoice_numbers: Option<Vec<String>>,
-    contact_ids: Option<Vec<Uuid>>,
-    statuses: Option<Vec<String>>,
-    where_filter: Option<String>,
-    order_by: Option<String>,
-    page: Option<u32>,
-    page_size: Option<u32>,
-    summary_only: Option<bool>,
-    search_term: Option<String>,
-    ids: Option<Vec<Uuid>>,
-    created_by_my_app: Option<bool>,
-    unitdp: Option<u8>,
+    query: InvoiceQuery,
 }
 
 impl<'a> InvoicesListRequest<'a> {
     pub(crate) fn new(api: &'a AccountingApi) -> Self {
         Self {
             api,
-            invoice_numbers: None,
-            contact_ids: None,
-            statuses: None,
-            where_filter: None,
-            order_by: None,
-            page: None,
-            page_size: None,
-            summary_only: None,
-            search_term: None,
-            ids: None,
-            created_by_my_app: None,
-            unitdp: None,
+            query: InvoiceQuery {
+                unitdp: api.unitdp,
+                ..InvoiceQuery::default()
+            },
         }
     }
 
@@ -47,7 +115,7 @@ impl<'a> InvoicesListRequest<'a> {
     where
         I: IntoIterator<Item = Uuid>,
     {
-        self.ids = Some(ids.into_iter().collect());
+        self.query.ids = Some(ids.into_iter().collect());
         self
     }
 
@@ -57,7 +125,7 @@ impl<'a> InvoicesListRequest<'a> {
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
-        self.invoice_numbers = Some(invoice_numbers.into_iter().map(Into::into).collect());
+        self.query.invoice_numbers = Some(invoice_numbers.into_iter().map(Into::into).collect());
         self
     }
 
@@ -66,87 +134,97 @@ impl<'a> InvoicesListRequest<'a> {
     where
         I: IntoIterator<Item = Uuid>,
     {
-        self.contact_ids = Some(contact_ids.into_iter().collect());
+        self.query.contact_ids = Some(contact_ids.into_iter().collect());
         self
     }
 
-    /// Filter by a list of statuses.
+    /// Filter by a list of statuses, using Xero's codes (e.g. `AUTHORISED`).
+    ///
+    /// Unknown codes are rejected when the request is sent.
     pub fn statuses<I, S>(mut self, statuses: I) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
-        self.statuses = Some(statuses.into_iter().map(Into::into).collect());
+        self.query.statuses = Some(statuses.into_iter().map(Into::into).collect());
         self
     }
 
     /// Filter using the `where` query parameter.
     pub fn where_filter(mut self, filter: impl Into<String>) -> Self {
-        self.where_filter = Some(filter.into());
+        self.query.where_filter = Some(filter.into());
         self
     }
 
     /// Order by a field.
     pub fn order_by(mut self, order: impl Into<String>) -> Self {
-        self.order_by = Some(order.into());
+        self.query.order_by = Some(order.into());
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<InvoiceOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
-        self.page = Some(page);
+        self.query.page = Some(page);
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
-        self.page_size = Some(page_size);
+        self.query.page_size = Some(page_size);
         self
     }
 
-    /// Returns a lightweight response.
+    /// Returns a lightweight response without line items, payments, credit
+    /// notes, prepayments or overpayments. See also [`Self::send_headers`].
     pub fn summary_only(mut self, summary_only: bool) -> Self {
-        self.summary_only = Some(summary_only);
+        self.query.summary_only = Some(summary_only);
         self
     }
 
     /// Sets the search term.
     pub fn search_term(mut self, search_term: impl Into<String>) -> Self {
-        self.search_term = Some(search_term.into());
+        self.query.search_term = Some(search_term.into());
         self
     }
 
     /// Limit results to invoices created by your app.
     pub fn created_by_my_app(mut self, created_by_my_app: bool) -> Self {
-        self.created_by_my_app = Some(created_by_my_app);
+        self.query.created_by_my_app = Some(created_by_my_app);
         self
     }
 
     /// Set unit decimal places for line item unit amounts.
     pub fn unitdp(mut self, unitdp: u8) -> Self {
-        self.unitdp = Some(unitdp);
+        self.query.unitdp = Some(unitdp);
         self
     }
 
+    /// Returns a pager that walks every page of this request, starting from
+    /// its `page` (or the first page).
+    ///
+    /// Without a `page_size`, the pager asks for [`MAX_PAGE_SIZE`] invoices per
+    /// page rather than Xero's default of 100, so bulk pulls take a tenth of
+    /// the requests.
+    pub fn pager(mut self) -> InvoicesPager<'a> {
+        let page_size = *self.query.page_size.get_or_insert(MAX_PAGE_SIZE);
+        InvoicesPager {
+            page: self.query.page.unwrap_or(1),
+            page_size,
+            request: self,
+            exhausted: false,
+        }
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<invoice::Invoice>, XeroError> {
-        let mut query = QueryParams::default();
-        query.push_opt_csv("IDs", self.ids);
-        if let Some(numbers) = self.invoice_numbers {
-            query.push_string("InvoiceNumbers", numbers.join(","));
-        }
-        query.push_opt_csv("ContactIDs", self.contact_ids);
-        if let Some(statuses) = self.statuses {
-            query.push_string("Statuses", statuses.join(","));
-        }
-        query.push_opt_string("where", self.where_filter);
-        query.push_opt_string("order", self.order_by);
-        query.push_opt("page", self.page);
-        query.push_opt("pageSize", self.page_size);
-        query.push_opt("summaryOnly", self.summary_only);
-        query.push_opt_string("SearchTerm", self.search_term);
-        query.push_opt("createdByMyApp", self.created_by_my_app);
-        query.push_opt("unitdp", self.unitdp);
+        check_page_size("/Invoices", self.query.page_size)?;
+        self.query.validate()?;
+        let query = build_invoice_query(&self.query);
 
         let resp: invoice::InvoicesResponse = self
             .api
@@ -155,4 +233,187 @@ impl<'a> InvoicesListRequest<'a> {
             .await?;
         Ok(resp.invoices)
     }
+
+    /// Executes the list request, also returning the untouched response body.
+    ///
+    /// Use the raw JSON to read fields the [`invoice::Invoice`] model does not
+    /// cover yet.
+    pub async fn send_with_raw(
+        self,
+    ) -> Result<(Vec<invoice::Invoice>, serde_json::Value), XeroError> {
+        check_page_size("/Invoices", self.query.page_size)?;
+        self.query.validate()?;
+        let query = build_invoice_query(&self.query);
+
+        let (resp, raw): (invoice::InvoicesResponse, _) = self
+            .api
+            .client
+            .send_request_with_raw(Method::GET, "/Invoices", query.as_slice(), None::<()>)
+            .await?;
+        Ok((resp.invoices, raw))
+    }
+
+    /// Executes the list request with `summaryOnly=true`, returning only the
+    /// headline fields of each invoice.
+    pub async fn send_headers(mut self) -> Result<Vec<invoice::InvoiceHeader>, XeroError> {
+        self.query.summary_only = Some(true);
+        check_page_size("/Invoices", self.query.page_size)?;
+        self.query.validate()?;
+        let query = build_invoice_query(&self.query);
+
+        let resp: invoice::InvoiceHeadersResponse = self
+            .api
+            .client
+            .send_request(Method::GET, "/Invoices", query.as_slice(), None::<()>)
+            .await?;
+        Ok(resp.invoices)
+    }
+
+    /// Executes the list request, also returning the response's rate limit
+    /// headroom, correlation id and warnings.
+    pub async fn send_detailed(self) -> Result<Response<Vec<invoice::Invoice>>, XeroError> {
+        check_page_size("/Invoices", self.query.page_size)?;
+        self.query.validate()?;
+        let query = build_invoice_query(&self.query);
+
+        let (resp, meta): (invoice::InvoicesResponse, _) = self
+            .api
+            .client
+            .send_request_detailed(Method::GET, "/Invoices", query.as_slice(), None::<()>)
+            .await?;
+        Ok(meta.into_response(resp.invoices, resp.warnings))
+    }
+}
+
+/// Async cursor over the pages of an Invoices list request.
+///
+/// Stops after the first page shorter than the page size. Created with
+/// [`InvoicesListRequest::pager`].
+#[derive(Debug, Clone)]
+pub struct InvoicesPager<'a> {
+    request: InvoicesListRequest<'a>,
+    page: u32,
+    page_size: u32,
+    exhausted: bool,
+}
+
+impl InvoicesPager<'_> {
+    /// Overrides the number of invoices requested per page.
+    ///
+    /// Returns [`XeroError::Validation`] for zero or more than [`MAX_PAGE_SIZE`].
+    pub fn with_page_size(mut self, page_size: u32) -> Result<Self, XeroError> {
+        check_pager_page_size("/Invoices", page_size)?;
+        self.page_size = page_size;
+        self.request.query.page_size = Some(page_size);
+        Ok(self)
+    }
+
+    /// The page the next call to [`InvoicesPager::next_page`] will request.
+    #[must_use]
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Fetches the next page of invoices, or `None` once all have been returned.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<invoice::Invoice>>, XeroError> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let invoices = self.request.clone().page(self.page).send().await?;
+        self.page += 1;
+        if invoices.len() < self.page_size as usize {
+            self.exhausted = true;
+        }
+        if invoices.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(invoices))
+    }
+}
+
+impl Paginated for InvoicesPager<'_> {
+    type Item = invoice::Invoice;
+
+    fn next_page(
+        &mut self,
+    ) -> impl std::future::Future<Output = Result<Option<Vec<invoice::Invoice>>, XeroError>> + Send
+    {
+        InvoicesPager::next_page(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(query: &InvoiceQuery) -> Vec<(String, String)> {
+        build_invoice_query(query)
+            .as_slice()
+            .map(<[_]>::to_vec)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn empty_query_sends_no_parameters() {
+        assert!(params(&InvoiceQuery::default()).is_empty());
+    }
+
+    #[test]
+    fn lists_are_comma_joined_and_names_match_xero() {
+        let query = InvoiceQuery {
+            ids: Some(vec![Uuid::nil(), Uuid::from_u128(1)]),
+            invoice_numbers: Some(vec!["INV-1".to_string(), "INV-2".to_string()]),
+            statuses: Some(vec!["DRAFT".to_string(), "AUTHORISED".to_string()]),
+            where_filter: Some("Type==\"ACCREC\"".to_string()),
+            page: Some(2),
+            page_size: Some(100),
+            summary_only: Some(true),
+            unitdp: Some(4),
+            ..Default::default()
+        };
+        let expected: Vec<(String, String)> = [
+            (
+                "IDs",
+                "00000000-0000-0000-0000-000000000000,00000000-0000-0000-0000-000000000001",
+            ),
+            ("InvoiceNumbers", "INV-1,INV-2"),
+            ("Statuses", "DRAFT,AUTHORISED"),
+            ("where", "Type==\"ACCREC\""),
+            ("page", "2"),
+            ("pageSize", "100"),
+            ("summaryOnly", "true"),
+            ("unitdp", "4"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(params(&query), expected);
+    }
+
+    #[test]
+    fn empty_filters_and_unknown_statuses_are_rejected() {
+        let empty_ids = InvoiceQuery {
+            ids: Some(Vec::new()),
+            ..Default::default()
+        };
+        let blank_search = InvoiceQuery {
+            search_term: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let misspelled = InvoiceQuery {
+            statuses: Some(vec!["AUTHORIZED".to_string()]),
+            ..Default::default()
+        };
+        for query in [empty_ids, blank_search, misspelled] {
+            assert!(matches!(query.validate(), Err(XeroError::Validation(_))));
+        }
+
+        let valid = InvoiceQuery {
+            statuses: Some(vec!["AUTHORISED".to_string(), "PAID".to_string()]),
+            search_term: Some("INV-1".to_string()),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+    }
 }
@@ -1,13 +1,35 @@
+use super::attachments::AttachmentEndpoint;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::attachment::Attachment;
+use crate::models::accounting::common::WithWarnings;
 use crate::models::accounting::invoice;
+use crate::util::{single_or_wrapped, split_by_json_size};
 use reqwest::Method;
 use uuid::Uuid;
 
 mod legacy;
 mod list;
 
-pub use list::InvoicesListRequest;
+pub use list::{InvoicesListRequest, InvoicesPager};
+
+/// Largest request body [`InvoicesResource::create_batched`] sends by default,
+/// kept under the size at which Xero rejects a request outright.
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 3_500_000;
+
+/// Outcome of [`InvoicesResource::create_with_attachment`].
+///
+/// The invoice exists in Xero once this is returned; if the upload failed,
+/// retry just the attachment against `invoice.invoice_id`. Marked
+/// `#[must_use]` so a failed upload is not dropped unnoticed.
+#[derive(Debug)]
+#[must_use = "`attachment` may hold an upload error for the created invoice"]
+pub struct InvoiceWithAttachment {
+    /// The created invoice.
+    pub invoice: invoice::Invoice,
+    /// The uploaded attachment, or why the upload failed.
+    pub attachment: Result<Vec<Attachment>, XeroError>,
+}
 
 /// Resource accessor for Invoices.
 #[derive(Debug, Clone, Copy)]
@@ -28,33 +50,159 @@ impl<'a> InvoicesResource<'a> {
     /// Retrieves a single invoice by ID.
     pub async fn get(&self, invoice_id: Uuid) -> Result<Vec<invoice::Invoice>, XeroError> {
         let path = format!("/Invoices/{invoice_id}");
+        let query = self.api.unitdp_query();
         let resp: invoice::InvoicesResponse = self
             .api
             .client
-            .send_request(Method::GET, &path, None, None::<()>)
+            .send_request(Method::GET, &path, query.as_slice(), None::<()>)
             .await?;
         Ok(resp.invoices)
     }
 
+    /// Retrieves the full invoice, including its `Payments` array.
+    ///
+    /// Fetching by ID always returns the detailed record, unlike list and
+    /// `summaryOnly` responses which omit payments. Returns `Ok(None)` if no
+    /// invoice has that ID.
+    pub async fn get_with_payments(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Option<invoice::Invoice>, XeroError> {
+        match self.get(invoice_id).await {
+            Ok(invoices) => Ok(invoices.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves the invoice with the given `InvoiceNumber`, or `None` if there
+    /// is none.
+    ///
+    /// Uses the `InvoiceNumbers` filter, so `number` must be the exact number
+    /// (compared case-insensitively, as Xero does).
+    pub async fn get_by_number(&self, number: &str) -> Result<Option<invoice::Invoice>, XeroError> {
+        let number = number.trim();
+        if number.is_empty() || number.contains(',') {
+            // An empty filter would list every invoice; a comma would split it.
+            return Err(XeroError::Validation(format!(
+                "Invalid invoice number {number:?}"
+            )));
+        }
+        let invoices = self.list().invoice_numbers([number]).send().await?;
+        Ok(invoices.into_iter().find(|invoice| {
+            invoice
+                .invoice_number
+                .as_deref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(number))
+        }))
+    }
+
     /// Creates one or more new invoices.
     pub async fn create(
         &self,
         invoices: Vec<invoice::Invoice>,
         summarize_errors: Option<bool>,
     ) -> Result<Vec<invoice::Invoice>, XeroError> {
-        let mut query = super::query::QueryParams::default();
+        Ok(self
+            .create_with_warnings(invoices, summarize_errors)
+            .await?
+            .items)
+    }
+
+    /// Creates one or more new invoices, also returning Xero's response-level warnings.
+    pub async fn create_with_warnings(
+        &self,
+        invoices: Vec<invoice::Invoice>,
+        summarize_errors: Option<bool>,
+    ) -> Result<WithWarnings<invoice::Invoice>, XeroError> {
+        let mut query = self.api.unitdp_query();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if invoices.len() == 1 {
-            serde_json::to_value(invoices.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(invoice::InvoicesRequest { invoices })?
-        };
+        let body = single_or_wrapped(invoices, |invoices| invoice::InvoicesRequest { invoices })?;
         let resp: invoice::InvoicesResponse = self
             .api
             .client
             .send_request(Method::PUT, "/Invoices", query.as_slice(), Some(body))
             .await?;
-        Ok(resp.invoices)
+        Ok(WithWarnings {
+            items: resp.invoices,
+            warnings: resp.warnings,
+        })
+    }
+
+    /// Creates invoices in as many requests as it takes to keep each body
+    /// under `max_request_bytes` ([`DEFAULT_MAX_REQUEST_BYTES`] if `None`).
+    ///
+    /// Batches are sent one after another in the order given, and the created
+    /// invoices come back in that order. An invoice too large to send on its
+    /// own fails the call with [`XeroError::Validation`] before anything is
+    /// sent; if a later batch fails, the batches before it have already been
+    /// created.
+    pub async fn create_batched(
+        &self,
+        invoices: Vec<invoice::Invoice>,
+        summarize_errors: Option<bool>,
+        max_request_bytes: Option<usize>,
+    ) -> Result<Vec<invoice::Invoice>, XeroError> {
+        let max_request_bytes = max_request_bytes.unwrap_or(DEFAULT_MAX_REQUEST_BYTES);
+        // `{"Invoices":` and the closing brace around the array.
+        let overhead = r#"{"Invoices":}"#.len();
+        let batches = split_by_json_size(invoices, max_request_bytes, overhead)?;
+        let mut created = Vec::new();
+        for batch in batches {
+            created.extend(self.create(batch, summarize_errors).await?);
+        }
+        Ok(created)
+    }
+
+    /// Creates an invoice and attaches a file (e.g. its PDF) to it.
+    ///
+    /// Fails outright only if the invoice could not be created. An upload
+    /// failure is returned in [`InvoiceWithAttachment::attachment`] alongside
+    /// the created invoice, so the caller can retry the upload alone.
+    pub async fn create_with_attachment<B>(
+        &self,
+        invoice: invoice::Invoice,
+        filename: &str,
+        content_type: &str,
+        body: B,
+        include_online: Option<bool>,
+    ) -> Result<InvoiceWithAttachment, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        let invoice = self
+            .create(vec![invoice], None)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: "Created invoice not found in response".to_string(),
+            })?;
+        let attachment = match invoice.invoice_id {
+            Some(invoice_id) => {
+                self.api
+                    .attachments()
+                    .upload_put(
+                        AttachmentEndpoint::Invoices,
+                        invoice_id,
+                        filename,
+                        content_type,
+                        body,
+                        include_online,
+                    )
+                    .await
+            }
+            None => Err(XeroError::Validation(
+                "Created invoice has no InvoiceID to attach to".to_string(),
+            )),
+        };
+        Ok(InvoiceWithAttachment {
+            invoice,
+            attachment,
+        })
     }
 
     /// Updates an existing invoice.
@@ -64,14 +212,69 @@ impl<'a> InvoicesResource<'a> {
         invoice_data: invoice::Invoice,
     ) -> Result<Vec<invoice::Invoice>, XeroError> {
         let path = format!("/Invoices/{invoice_id}");
+        let query = self.api.unitdp_query();
         let resp: invoice::InvoicesResponse = self
             .api
             .client
-            .send_request(Method::POST, &path, None, Some(invoice_data))
+            .send_request(Method::POST, &path, query.as_slice(), Some(invoice_data))
             .await?;
         Ok(resp.invoices)
     }
 
+    /// Deletes a `DRAFT` or `SUBMITTED` invoice by setting its status to `DELETED`.
+    pub async fn delete(&self, invoice_id: Uuid) -> Result<Vec<invoice::Invoice>, XeroError> {
+        self.set_status(invoice_id, invoice::InvoiceStatus::Deleted)
+            .await
+    }
+
+    /// Voids an `AUTHORISED` invoice with no payments applied.
+    pub async fn void(&self, invoice_id: Uuid) -> Result<Vec<invoice::Invoice>, XeroError> {
+        self.set_status(invoice_id, invoice::InvoiceStatus::Voided)
+            .await
+    }
+
+    async fn set_status(
+        &self,
+        invoice_id: Uuid,
+        status: invoice::InvoiceStatus,
+    ) -> Result<Vec<invoice::Invoice>, XeroError> {
+        let path = format!("/Invoices/{invoice_id}");
+        let resp: invoice::InvoicesResponse = self.api.set_status(&path, status).await?;
+        Ok(resp.invoices)
+    }
+
+    /// Sets the invoice's `SentToContact` flag without emailing it, e.g. after
+    /// sending it through your own mail system.
+    ///
+    /// The invoice is fetched and posted back whole with the flag changed,
+    /// because an update built from scratch would reset the fields it left
+    /// out. A change made to the invoice between the two requests is
+    /// overwritten.
+    pub async fn mark_sent(
+        &self,
+        invoice_id: Uuid,
+        sent: bool,
+    ) -> Result<invoice::Invoice, XeroError> {
+        let mut invoice = self
+            .get(invoice_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Invoice {invoice_id} not found"),
+            })?;
+        invoice.sent_to_contact = Some(sent);
+        self.update(invoice_id, invoice)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Invoice {invoice_id} not returned after update"),
+            })
+    }
+
     /// Retrieves the online invoice URL for a sales invoice.
     pub async fn online_invoice_url(
         &self,
@@ -89,12 +292,272 @@ impl<'a> InvoicesResource<'a> {
         })
     }
 
+    /// Fills in [`invoice::Invoice::online_url`] if it is not already set,
+    /// returning the URL.
+    ///
+    /// Only sales invoices that are not drafts have an online invoice; Xero
+    /// returns an error for the rest.
+    pub async fn ensure_online_url<'i>(
+        &self,
+        invoice: &'i mut invoice::Invoice,
+    ) -> Result<&'i str, XeroError> {
+        if invoice.online_url.is_none() {
+            let invoice_id = invoice.invoice_id.ok_or_else(|| {
+                XeroError::Validation("Invoice has no InvoiceID to look up".to_string())
+            })?;
+            let online = self.online_invoice_url(invoice_id).await?;
+            invoice.online_url = Some(online.online_invoice_url);
+        }
+        Ok(invoice.online_url.as_deref().unwrap_or_default())
+    }
+
     /// Emails a sales invoice from Xero.
+    ///
+    /// The email links to the online invoice, which lists the attachments
+    /// uploaded with `include_online` set; other attachments are not sent.
+    ///
+    /// Xero only emails `ACCREC` invoices that are `SUBMITTED`, `AUTHORISED` or
+    /// `PAID`, and otherwise responds with a bare 400. The invoice is fetched
+    /// first so those cases fail with a [`XeroError::Validation`] naming the
+    /// problem; any remaining API error has its message replaced by Xero's
+    /// validation messages where the body contains them.
     pub async fn email(&self, invoice_id: Uuid) -> Result<(), XeroError> {
+        let invoice = self
+            .get(invoice_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Invoice {invoice_id} not found"),
+            })?;
+        check_emailable(&invoice)?;
+
         let path = format!("/Invoices/{invoice_id}/Email");
         self.api
             .client
             .send_request_empty_response(Method::POST, &path, None::<()>)
             .await
+            .map_err(
+                |e| match (e.api_exception().and_then(|ex| ex.summary()), e) {
+                    (Some(summary), XeroError::Api { status, .. }) => XeroError::Api {
+                        status,
+                        message: format!("Failed to email invoice {invoice_id}: {summary}"),
+                    },
+                    (_, e) => e,
+                },
+            )
+    }
+
+    /// Returns the IDs of invoices matching `where_filter` that have at least one attachment.
+    ///
+    /// Uses the `HasAttachments` flag Xero includes on list responses, so only
+    /// the list request is made. Pass a filter that keeps the result to a page
+    /// or two (e.g. a date range).
+    pub async fn with_attachments(&self, where_filter: &str) -> Result<Vec<Uuid>, XeroError> {
+        let invoices = self.list().where_filter(where_filter).send().await?;
+        Ok(ids_with_attachment_flag(&invoices, true))
+    }
+
+    /// Returns the IDs of invoices matching `where_filter` that have no attachments.
+    /// See [`InvoicesResource::with_attachments`].
+    pub async fn without_attachments(&self, where_filter: &str) -> Result<Vec<Uuid>, XeroError> {
+        let invoices = self.list().where_filter(where_filter).send().await?;
+        Ok(ids_with_attachment_flag(&invoices, false))
+    }
+
+    /// Emails several sales invoices one after another, reporting each outcome.
+    ///
+    /// Requests go through the client's rate limiter; a failure for one
+    /// invoice does not stop the others.
+    pub async fn email_many(&self, invoice_ids: Vec<Uuid>) -> Vec<(Uuid, Result<(), XeroError>)> {
+        let mut results = Vec::with_capacity(invoice_ids.len());
+        for invoice_id in invoice_ids {
+            let result = self.email(invoice_id).await;
+            results.push((invoice_id, result));
+        }
+        results
+    }
+}
+
+/// IDs of the invoices whose `HasAttachments` flag equals `has_attachments`.
+///
+/// Invoices Xero returned without the flag are left out of both lists.
+fn ids_with_attachment_flag(invoices: &[invoice::Invoice], has_attachments: bool) -> Vec<Uuid> {
+    invoices
+        .iter()
+        .filter(|invoice| invoice.has_attachments == Some(has_attachments))
+        .filter_map(|invoice| invoice.invoice_id)
+        .collect()
+}
+
+/// Checks Xero's prerequisites for emailing an invoice.
+fn check_emailable(invoice: &invoice::Invoice) -> Result<(), XeroError> {
+    let label = invoice
+        .invoice_number
+        .clone()
+        .or_else(|| invoice.invoice_id.map(|id| id.to_string()))
+        .unwrap_or_default();
+    if invoice.invoice_type != invoice::InvoiceType::Accrec {
+        return Err(XeroError::Validation(format!(
+            "Invoice {label} is a bill (ACCPAY); only sales invoices can be emailed"
+        )));
+    }
+    match invoice.status {
+        Some(
+            invoice::InvoiceStatus::Submitted
+            | invoice::InvoiceStatus::Authorised
+            | invoice::InvoiceStatus::Paid,
+        ) => Ok(()),
+        ref status => {
+            let status = status.as_ref().map_or("no status".to_string(), |s| {
+                format!("status {}", s.as_xero_value())
+            });
+            Err(XeroError::Validation(format!(
+                "Invoice {label} has {status}; only SUBMITTED, AUTHORISED or PAID invoices can be emailed"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::TokenSet;
+    use crate::test_support::{self, MockServer};
+
+    #[tokio::test]
+    async fn mark_sent_posts_back_the_fetched_invoice() {
+        let invoice_id = Uuid::new_v4();
+        let fetched = serde_json::json!({ "Invoices": [{
+            "Type": "ACCREC",
+            "InvoiceID": invoice_id,
+            "Contact": { "Name": "ACME Ltd" },
+            "Date": "/Date(1700000000000+0000)/",
+            "Status": "AUTHORISED",
+            "SentToContact": false,
+            "LineItems": [{ "Description": "Widget", "Quantity": 2.0, "UnitAmount": 10.0 }]
+        }]});
+        let mut updated = fetched.clone();
+        updated["Invoices"][0]["SentToContact"] = true.into();
+        let bodies = vec![fetched.to_string(), updated.to_string()];
+
+        let server = MockServer::ok(bodies).await;
+        let api = test_support::accounting_api(&server).await;
+
+        let invoice = api.mark_invoice_sent(invoice_id, true).await.unwrap();
+        assert_eq!(invoice.sent_to_contact, Some(true));
+
+        let requests = server.requests().await;
+        assert!(requests[0].starts_with(&format!("GET /Invoices/{invoice_id} ")));
+        assert!(requests[1].starts_with(&format!("POST /Invoices/{invoice_id} ")));
+        let (_, body) = requests[1].split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["SentToContact"], true);
+        assert_eq!(body["LineItems"][0]["Description"], "Widget");
+    }
+
+    #[tokio::test]
+    async fn blank_invoice_number_is_rejected_before_sending() {
+        let client = test_support::client().await;
+        let api = client
+            .tenant_with_token(Uuid::from_u128(1), TokenSet::default())
+            .accounting();
+
+        for number in ["", "  ", "INV-1,INV-2"] {
+            let err = api.get_invoice_by_number(number).await.unwrap_err();
+            assert!(
+                matches!(err, XeroError::Validation(_)),
+                "{number:?}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn attachment_flag_splits_invoices() {
+        let invoice = |has_attachments: Option<bool>| invoice::Invoice {
+            invoice_id: Some(Uuid::new_v4()),
+            has_attachments,
+            ..Default::default()
+        };
+        let invoices = vec![invoice(Some(true)), invoice(Some(false)), invoice(None)];
+        assert_eq!(
+            ids_with_attachment_flag(&invoices, true),
+            vec![invoices[0].invoice_id.unwrap()]
+        );
+        assert_eq!(
+            ids_with_attachment_flag(&invoices, false),
+            vec![invoices[1].invoice_id.unwrap()]
+        );
+    }
+
+    #[test]
+    fn draft_invoices_are_not_emailable() {
+        let draft = invoice::Invoice {
+            status: Some(invoice::InvoiceStatus::Draft),
+            invoice_number: Some("INV-1".to_string()),
+            ..Default::default()
+        };
+        let err = check_emailable(&draft).unwrap_err();
+        assert!(
+            matches!(&err, XeroError::Validation(msg) if msg.contains("INV-1 has status DRAFT;")),
+            "{err:?}"
+        );
+
+        let unsaved = invoice::Invoice {
+            invoice_number: Some("INV-2".to_string()),
+            ..Default::default()
+        };
+        let err = check_emailable(&unsaved).unwrap_err();
+        assert!(
+            matches!(&err, XeroError::Validation(msg) if msg.contains("INV-2 has no status;")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn bills_are_not_emailable() {
+        let bill = invoice::Invoice {
+            invoice_type: invoice::InvoiceType::Accpay,
+            status: Some(invoice::InvoiceStatus::Authorised),
+            ..Default::default()
+        };
+        assert!(check_emailable(&bill).is_err());
+    }
+
+    #[test]
+    fn authorised_sales_invoices_are_emailable() {
+        let invoice = invoice::Invoice {
+            status: Some(invoice::InvoiceStatus::Authorised),
+            ..Default::default()
+        };
+        assert!(check_emailable(&invoice).is_ok());
+    }
+
+    #[tokio::test]
+    async fn ensure_online_url_reuses_existing_url() {
+        let client = test_support::client().await;
+        let api = client.tenant(Uuid::nil()).accounting();
+
+        let mut invoice = invoice::Invoice {
+            online_url: Some("https://in.xero.com/abc".to_string()),
+            ..Default::default()
+        };
+        let url = api
+            .invoices()
+            .ensure_online_url(&mut invoice)
+            .await
+            .unwrap();
+        assert_eq!(url, "https://in.xero.com/abc");
+        assert!(serde_json::to_value(&invoice)
+            .unwrap()
+            .get("OnlineUrl")
+            .is_none());
+
+        let mut unsaved = invoice::Invoice::default();
+        assert!(matches!(
+            api.invoices().ensure_online_url(&mut unsaved).await,
+            Err(XeroError::Validation(_))
+        ));
     }
 }
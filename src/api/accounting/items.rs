@@ -1,7 +1,9 @@
+use super::order::{ItemOrderField, OrderBy};
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::item;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use uuid::Uuid;
@@ -25,25 +27,53 @@ impl<'a> ItemsResource<'a> {
     /// Retrieves a single item by ID.
     pub async fn get(&self, item_id: Uuid) -> Result<Vec<item::Item>, XeroError> {
         let path = format!("/Items/{item_id}");
+        let query = self.api.unitdp_query();
         let resp: item::ItemsResponse = self
             .api
             .client
-            .send_request(Method::GET, &path, None, None::<()>)
+            .send_request(Method::GET, &path, query.as_slice(), None::<()>)
             .await?;
         Ok(resp.items)
     }
 
+    /// Retrieves a single item by its `Code` (SKU).
+    ///
+    /// Xero accepts either the `ItemID` or the item code in the `/Items/{id}`
+    /// path. Returns `Ok(None)` when no item with that code exists.
+    pub async fn get_by_code(&self, code: &str) -> Result<Option<item::Item>, XeroError> {
+        let path = format!("/Items/{}", urlencoding::encode(code));
+        let query = self.api.unitdp_query();
+        let result: Result<item::ItemsResponse, XeroError> = self
+            .api
+            .client
+            .send_request(Method::GET, &path, query.as_slice(), None::<()>)
+            .await;
+        match result {
+            Ok(resp) => Ok(resp.items.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves only the items tracked as inventory.
+    ///
+    /// The Items endpoint is not paged, so this fetches every item once and
+    /// filters on [`item::Item::is_inventory`].
+    pub async fn tracked(&self) -> Result<Vec<item::Item>, XeroError> {
+        let items = self.list().send().await?;
+        Ok(items.into_iter().filter(item::Item::is_inventory).collect())
+    }
+
     /// Creates one or more new items.
     pub async fn create(&self, items: Vec<item::Item>) -> Result<Vec<item::Item>, XeroError> {
-        let body = if items.len() == 1 {
-            serde_json::to_value(items.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(item::ItemsRequest { items })?
-        };
+        let body = single_or_wrapped(items, |items| item::ItemsRequest { items })?;
+        let query = self.api.unitdp_query();
         let resp: item::ItemsResponse = self
             .api
             .client
-            .send_request(Method::PUT, "/Items", None, Some(body))
+            .send_request(Method::PUT, "/Items", query.as_slice(), Some(body))
             .await?;
         Ok(resp.items)
     }
@@ -55,10 +85,11 @@ impl<'a> ItemsResource<'a> {
         item_data: item::Item,
     ) -> Result<Vec<item::Item>, XeroError> {
         let path = format!("/Items/{item_id}");
+        let query = self.api.unitdp_query();
         let resp: item::ItemsResponse = self
             .api
             .client
-            .send_request(Method::POST, &path, None, Some(item_data))
+            .send_request(Method::POST, &path, query.as_slice(), Some(item_data))
             .await?;
         Ok(resp.items)
     }
@@ -80,6 +111,7 @@ pub struct ItemsListRequest<'a> {
     where_filter: Option<String>,
     order_by: Option<String>,
     page: Option<u32>,
+    unitdp: Option<u8>,
 }
 
 impl<'a> ItemsListRequest<'a> {
@@ -89,6 +121,7 @@ impl<'a> ItemsListRequest<'a> {
             where_filter: None,
             order_by: None,
             page: None,
+            unitdp: api.unitdp,
         }
     }
 
@@ -104,18 +137,30 @@ impl<'a> ItemsListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<ItemOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
+    /// Set unit decimal places for item unit prices.
+    pub fn unitdp(mut self, unitdp: u8) -> Self {
+        self.unitdp = Some(unitdp);
+        self
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<item::Item>, XeroError> {
         let mut query = QueryParams::default();
         query.push_opt_string("where", self.where_filter);
         query.push_opt_string("order", self.order_by);
         query.push_opt("page", self.page);
+        query.push_opt("unitdp", self.unitdp);
 
         let resp: item::ItemsResponse = self
             .api
@@ -149,6 +194,16 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves a single item by its `Code` (SKU).
+    pub async fn get_item_by_code(&self, code: &str) -> Result<Option<item::Item>, XeroError> {
+        self.items().get_by_code(code).await
+    }
+
+    /// Retrieves only the items tracked as inventory.
+    pub async fn get_tracked_items(&self) -> Result<Vec<item::Item>, XeroError> {
+        self.items().tracked().await
+    }
+
     /// Creates one or more new items.
     pub async fn create_items(&self, items: Vec<item::Item>) -> Result<Vec<item::Item>, XeroError> {
         self.items().create(items).await
@@ -168,3 +223,26 @@ impl AccountingApi {
         self.items().delete(item_id).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{self, MockServer};
+
+    #[tokio::test]
+    async fn default_unitdp_is_sent_and_overridable() {
+        let server = MockServer::ok([r#"{"Items":[]}"#; 3]).await;
+        let api = test_support::accounting_api(&server).await.with_unitdp(4);
+
+        assert_eq!(api.items().get_by_code("WIDGET").await.unwrap(), None);
+        api.items().list().send().await.unwrap();
+        api.items().list().unitdp(2).send().await.unwrap();
+        assert_eq!(
+            server.paths().await,
+            vec![
+                "/Items/WIDGET?unitdp=4",
+                "/Items?unitdp=4",
+                "/Items?unitdp=2"
+            ]
+        );
+    }
+}
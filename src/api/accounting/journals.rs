@@ -1,9 +1,18 @@
+use super::pagination::Paginated;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::journal;
 use chrono::{DateTime, Utc};
-use reqwest::Method;
+use log::{debug, warn};
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Maximum number of retries for a single journals page on a 429/503 response.
+const PAGE_MAX_RETRIES: u32 = 4;
+/// Initial delay before retrying a throttled journals page; doubled on each attempt.
+const PAGE_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
 
 /// Resource accessor for Journals.
 #[derive(Debug, Clone, Copy)]
@@ -20,6 +29,152 @@ impl<'a> JournalsResource<'a> {
     pub fn list(&self) -> JournalsListRequest<'a> {
         JournalsListRequest::new(self.api)
     }
+
+    /// Returns a pager that walks the journal feed from `offset` onwards.
+    pub fn pager(&self, offset: u32) -> JournalsPager<'a> {
+        JournalsPager::new(self.api, offset)
+    }
+
+    /// Retrieves a journal by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get(&self, journal_id: Uuid) -> Result<Option<journal::Journal>, XeroError> {
+        self.get_one(&format!("/Journals/{journal_id}")).await
+    }
+
+    /// Retrieves a journal by its `JournalNumber`, returning `Ok(None)` if it
+    /// does not exist. Unlike paging the feed, this is a single request.
+    pub async fn get_by_number(
+        &self,
+        journal_number: u32,
+    ) -> Result<Option<journal::Journal>, XeroError> {
+        self.get_one(&format!("/Journals/{journal_number}")).await
+    }
+
+    async fn get_one(&self, path: &str) -> Result<Option<journal::Journal>, XeroError> {
+        let resp: Result<journal::JournalsResponse, _> = self
+            .api
+            .client
+            .send_request(Method::GET, path, None, None::<()>)
+            .await;
+        match resp {
+            Ok(resp) => Ok(resp.journals.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == StatusCode::NOT_FOUND => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Async cursor over the full journal feed.
+///
+/// Xero returns up to 100 journals with a `JournalNumber` strictly greater than
+/// the supplied `offset`. The pager advances the offset to the highest journal
+/// number of each batch, so no journal is returned twice, and stops at the first
+/// empty page or the first page that does not move the offset forward.
+/// Throttled pages (429/503) are retried with exponential backoff on top of the
+/// client's rate limiter.
+#[derive(Debug, Clone)]
+pub struct JournalsPager<'a> {
+    api: &'a AccountingApi,
+    offset: u32,
+    payments_only: Option<bool>,
+    exhausted: bool,
+}
+
+impl<'a> JournalsPager<'a> {
+    fn new(api: &'a AccountingApi, offset: u32) -> Self {
+        Self {
+            api,
+            offset,
+            payments_only: None,
+            exhausted: false,
+        }
+    }
+
+    /// Restrict to payment journals only.
+    pub fn payments_only(mut self, payments_only: bool) -> Self {
+        self.payments_only = Some(payments_only);
+        self
+    }
+
+    /// The offset the next page will be requested from: the highest journal
+    /// number returned so far.
+    ///
+    /// Persist this to resume an export later without re-reading old journals.
+    #[must_use]
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Fetches the next page of journals, or `None` once the feed is exhausted.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<journal::Journal>>, XeroError> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let journals = self.fetch_with_backoff().await?;
+        if journals.is_empty() {
+            self.exhausted = true;
+            return Ok(None);
+        }
+
+        let offset = next_offset(self.offset, &journals);
+        if offset == self.offset {
+            // Refetching the same offset would return this page again forever.
+            self.exhausted = true;
+        }
+        self.offset = offset;
+        debug!(
+            "Fetched {} journals; next offset is {}",
+            journals.len(),
+            self.offset
+        );
+        Ok(Some(journals))
+    }
+
+    async fn fetch_with_backoff(&self) -> Result<Vec<journal::Journal>, XeroError> {
+        let mut delay = PAGE_INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let mut request = self.api.journals().list().offset(self.offset);
+            if let Some(payments_only) = self.payments_only {
+                request = request.payments_only(payments_only);
+            }
+            match request.send().await {
+                Err(XeroError::Api { status, .. })
+                    if attempt < PAGE_MAX_RETRIES
+                        && (status == StatusCode::TOO_MANY_REQUESTS
+                            || status == StatusCode::SERVICE_UNAVAILABLE) =>
+                {
+                    attempt += 1;
+                    warn!(
+                        "Journals page at offset {} throttled ({status}); retry {attempt}/{PAGE_MAX_RETRIES} in {delay:?}",
+                        self.offset
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Paginated for JournalsPager<'_> {
+    type Item = journal::Journal;
+
+    fn next_page(
+        &mut self,
+    ) -> impl std::future::Future<Output = Result<Option<Vec<journal::Journal>>, XeroError>> + Send
+    {
+        JournalsPager::next_page(self)
+    }
+}
+
+/// Returns the offset to request after `journals`: the highest journal number seen.
+fn next_offset(current: u32, journals: &[journal::Journal]) -> u32 {
+    journals
+        .iter()
+        .filter_map(|j| u32::try_from(j.journal_number).ok())
+        .fold(current, u32::max)
 }
 
 /// Builder for Journals list requests.
@@ -84,6 +239,29 @@ impl<'a> JournalsListRequest<'a> {
             Ok(resp.journals)
         }
     }
+
+    /// Executes the list request, parsing the response as it downloads.
+    ///
+    /// Returns the same journals as [`send`](Self::send) without first
+    /// buffering the whole body, which keeps peak memory down on large
+    /// ledger exports.
+    pub async fn send_streaming(self) -> Result<Vec<journal::Journal>, XeroError> {
+        let mut query = QueryParams::default();
+        query.push_opt("offset", self.offset);
+        query.push_opt("paymentsOnly", self.payments_only);
+
+        let resp: journal::JournalsResponse = self
+            .api
+            .client
+            .send_request_streaming(
+                Method::GET,
+                "/Journals",
+                query.as_slice(),
+                self.if_modified_since.as_ref(),
+            )
+            .await?;
+        Ok(resp.journals)
+    }
 }
 
 impl AccountingApi {
@@ -102,4 +280,133 @@ impl AccountingApi {
         }
         request.send().await
     }
+
+    /// Retrieves journals like [`get_journals`](Self::get_journals), parsing
+    /// the response as it downloads rather than buffering it first.
+    pub async fn get_journals_streaming(
+        &self,
+        offset: Option<u32>,
+        payments_only: Option<bool>,
+    ) -> Result<Vec<journal::Journal>, XeroError> {
+        let mut request = self.journals().list();
+        if let Some(offset) = offset {
+            request = request.offset(offset);
+        }
+        if let Some(payments_only) = payments_only {
+            request = request.payments_only(payments_only);
+        }
+        request.send_streaming().await
+    }
+
+    /// Retrieves a journal by ID. See [`JournalsResource::get`].
+    pub async fn get_journal(
+        &self,
+        journal_id: Uuid,
+    ) -> Result<Option<journal::Journal>, XeroError> {
+        self.journals().get(journal_id).await
+    }
+
+    /// Retrieves a journal by number. See [`JournalsResource::get_by_number`].
+    pub async fn get_journal_by_number(
+        &self,
+        journal_number: u32,
+    ) -> Result<Option<journal::Journal>, XeroError> {
+        self.journals().get_by_number(journal_number).await
+    }
+
+    /// Returns a pager over all journals with a number greater than `offset`.
+    pub fn journals_since(&self, offset: u32) -> JournalsPager<'_> {
+        self.journals().pager(offset)
+    }
+
+    /// Retrieves every journal with a number greater than `offset`, following
+    /// the journal feed until it is exhausted.
+    pub async fn get_all_journals_since(
+        &self,
+        offset: u32,
+    ) -> Result<Vec<journal::Journal>, XeroError> {
+        self.journals_since(offset).collect_all().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, response, MockServer};
+
+    fn journal(number: i32) -> journal::Journal {
+        journal::Journal {
+            journal_id: Uuid::new_v4(),
+            journal_date: Utc::now(),
+            journal_number: number,
+            created_date_utc: None,
+            reference: None,
+            source_id: None,
+            source_type: None,
+            journal_lines: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn next_offset_is_highest_journal_number() {
+        let page = vec![journal(101), journal(150), journal(120)];
+        assert_eq!(next_offset(100, &page), 150);
+    }
+
+    #[test]
+    fn next_offset_never_moves_backwards() {
+        assert_eq!(next_offset(200, &[journal(5)]), 200);
+        assert_eq!(next_offset(200, &[]), 200);
+    }
+
+    #[tokio::test]
+    async fn pager_stops_when_a_page_does_not_advance_the_offset() {
+        let stale = r#"{"Journals":[{"JournalID":"8138a266-fb42-49b2-a104-014b7045753d","JournalDate":"/Date(1573603200000+0000)/","JournalNumber":5,"JournalLines":[]}]}"#;
+        let server = MockServer::ok([stale]).await;
+        let api = test_support::accounting_api(&server).await;
+
+        let mut pager = api.journals().pager(10);
+        assert_eq!(pager.next_page().await.unwrap().unwrap().len(), 1);
+        assert_eq!(pager.offset(), 10);
+        assert!(pager.next_page().await.unwrap().is_none());
+        assert_eq!(server.paths().await, vec!["/Journals?offset=10"]);
+    }
+
+    #[tokio::test]
+    async fn streaming_parse_reads_chunked_body() {
+        let body = r#"{"Journals":[{"JournalID":"8138a266-fb42-49b2-a104-014b7045753d","JournalDate":"/Date(1573603200000+0000)/","JournalNumber":101,"JournalLines":[]}]}"#;
+        let (head, tail) = body.split_at(40);
+        let server = MockServer::start(vec![format!(
+            "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n\
+             {:x}\r\n{head}\r\n{:x}\r\n{tail}\r\n0\r\n\r\n",
+            head.len(),
+            tail.len()
+        )])
+        .await;
+        let api = test_support::accounting_api(&server).await;
+
+        let journals = api.get_journals_streaming(Some(100), None).await.unwrap();
+        assert_eq!(journals.len(), 1);
+        assert_eq!(journals[0].journal_number, 101);
+        assert_eq!(server.paths().await, vec!["/Journals?offset=100"]);
+    }
+
+    #[tokio::test]
+    async fn journal_by_number_is_one_request_and_missing_is_none() {
+        let found = r#"{"Journals":[{"JournalID":"8138a266-fb42-49b2-a104-014b7045753d","JournalDate":"/Date(1573603200000+0000)/","JournalNumber":4213,"JournalLines":[]}]}"#;
+        let server = MockServer::start(vec![
+            response("200 OK", found),
+            response("404 Not Found", "{}"),
+        ])
+        .await;
+        let api = test_support::accounting_api(&server).await;
+
+        let journal = api.get_journal_by_number(4213).await.unwrap().unwrap();
+        assert_eq!(journal.journal_number, 4213);
+        assert!(api.get_journal_by_number(9999).await.unwrap().is_none());
+        assert_eq!(
+            server.paths().await,
+            vec!["/Journals/4213", "/Journals/9999"]
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use super::pagination::Paginated;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
@@ -116,6 +117,16 @@ impl<'a> LinkedTransactionsListRequest<'a> {
         self
     }
 
+    /// Returns a pager that walks every page of this request, starting from
+    /// its `page` (or the first page).
+    pub fn pager(self) -> LinkedTransactionsPager<'a> {
+        LinkedTransactionsPager {
+            page: self.page.unwrap_or(1),
+            request: self,
+            exhausted: false,
+        }
+    }
+
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<linked_transaction::LinkedTransaction>, XeroError> {
         let mut query = QueryParams::default();
@@ -139,6 +150,57 @@ impl<'a> LinkedTransactionsListRequest<'a> {
     }
 }
 
+/// Async cursor over every page of a linked transactions list request.
+///
+/// Xero returns up to 100 linked transactions per page and takes no page
+/// size, so the pager advances `page` until a page comes back empty. Each
+/// request goes through the client's rate limiter. Created with
+/// [`LinkedTransactionsListRequest::pager`].
+#[derive(Debug, Clone)]
+pub struct LinkedTransactionsPager<'a> {
+    request: LinkedTransactionsListRequest<'a>,
+    page: u32,
+    exhausted: bool,
+}
+
+impl LinkedTransactionsPager<'_> {
+    /// The page the next call to [`LinkedTransactionsPager::next_page`] will request.
+    #[must_use]
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Fetches the next page of linked transactions, or `None` once all have
+    /// been returned.
+    pub async fn next_page(
+        &mut self,
+    ) -> Result<Option<Vec<linked_transaction::LinkedTransaction>>, XeroError> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let linked_transactions = self.request.clone().page(self.page).send().await?;
+        if linked_transactions.is_empty() {
+            self.exhausted = true;
+            return Ok(None);
+        }
+        self.page += 1;
+        Ok(Some(linked_transactions))
+    }
+}
+
+impl Paginated for LinkedTransactionsPager<'_> {
+    type Item = linked_transaction::LinkedTransaction;
+
+    fn next_page(
+        &mut self,
+    ) -> impl std::future::Future<
+        Output = Result<Option<Vec<linked_transaction::LinkedTransaction>>, XeroError>,
+    > + Send {
+        LinkedTransactionsPager::next_page(self)
+    }
+}
+
 impl AccountingApi {
     /// Retrieves one or many linked transactions.
     #[allow(clippy::too_many_arguments)]
@@ -174,6 +236,21 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves every linked transaction whose source is
+    /// `source_transaction_id` (such as the bill a billable expense is on),
+    /// across all pages.
+    pub async fn get_linked_transactions_for_source(
+        &self,
+        source_transaction_id: Uuid,
+    ) -> Result<Vec<linked_transaction::LinkedTransaction>, XeroError> {
+        self.linked_transactions()
+            .list()
+            .source_transaction_id(source_transaction_id)
+            .pager()
+            .collect_all()
+            .await
+    }
+
     /// Creates or updates a linked transaction.
     pub async fn create_or_update_linked_transaction(
         &self,
@@ -1,4 +1,5 @@
-use super::query::QueryParams;
+use super::attachments::{attachment_path, AttachmentEndpoint};
+use super::query::{check_page_size, QueryParams};
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::{attachment, manual_journal};
@@ -59,6 +60,52 @@ impl<'a> ManualJournalsResource<'a> {
         Ok(resp.manual_journals)
     }
 
+    /// Deletes a `DRAFT` manual journal by setting its status to `DELETED`.
+    pub async fn delete(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        self.set_status(
+            manual_journal_id,
+            manual_journal::ManualJournalStatus::Deleted,
+        )
+        .await
+    }
+
+    /// Voids a `POSTED` manual journal.
+    pub async fn void(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        self.set_status(
+            manual_journal_id,
+            manual_journal::ManualJournalStatus::Voided,
+        )
+        .await
+    }
+
+    async fn set_status(
+        &self,
+        manual_journal_id: Uuid,
+        status: manual_journal::ManualJournalStatus,
+    ) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        let path = format!("/ManualJournals/{manual_journal_id}");
+        let resp: manual_journal::ManualJournalsResponse =
+            self.api.set_status(&path, status).await?;
+        Ok(resp.manual_journals)
+    }
+
+    /// Retrieves the attachments on a manual journal.
+    pub async fn attachments(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<attachment::Attachment>, XeroError> {
+        self.api
+            .attachments()
+            .list(AttachmentEndpoint::ManualJournals, manual_journal_id)
+            .await
+    }
+
     /// Attaches a file to a Manual Journal.
     pub async fn attach_by_file_name(
         &self,
@@ -66,8 +113,11 @@ impl<'a> ManualJournalsResource<'a> {
         file_name: String,
         body: Vec<u8>,
     ) -> Result<Vec<attachment::Attachment>, XeroError> {
-        let encoded_file_name = file_name.replace('[', "%5B").replace(']', "%5D");
-        let path = format!("/ManualJournals/{manual_journal_id}/Attachments/{encoded_file_name}");
+        let path = attachment_path(
+            AttachmentEndpoint::ManualJournals,
+            manual_journal_id,
+            &file_name,
+        );
         let content_type = "application/octet-stream";
         let resp: attachment::AttachmentsResponse = self
             .api
@@ -117,7 +167,7 @@ impl<'a> ManualJournalsListRequest<'a> {
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
@@ -125,6 +175,7 @@ impl<'a> ManualJournalsListRequest<'a> {
 
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        check_page_size("/ManualJournals", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("where", self.where_filter);
         query.push_opt_string("order", self.order_by);
@@ -180,6 +231,30 @@ impl AccountingApi {
         self.manual_journals().create_or_update(journal).await
     }
 
+    /// Deletes a `DRAFT` manual journal.
+    pub async fn delete_manual_journal(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        self.manual_journals().delete(manual_journal_id).await
+    }
+
+    /// Voids a `POSTED` manual journal.
+    pub async fn void_manual_journal(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<manual_journal::ManualJournal>, XeroError> {
+        self.manual_journals().void(manual_journal_id).await
+    }
+
+    /// Retrieves the attachments on a manual journal.
+    pub async fn get_manual_journal_attachments(
+        &self,
+        manual_journal_id: Uuid,
+    ) -> Result<Vec<attachment::Attachment>, XeroError> {
+        self.manual_journals().attachments(manual_journal_id).await
+    }
+
     /// Attaches a file to a Manual Journal.
     pub async fn create_manual_journal_attachment(
         &self,
@@ -2,7 +2,11 @@
 
 use crate::auth::TokenSet;
 use crate::client::XeroClient;
+use crate::error::XeroError;
 use crate::http::ApiClient;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -19,6 +23,7 @@ pub mod credit_notes;
 pub mod currencies;
 pub mod employees;
 pub mod expense_claims;
+pub mod filter;
 pub mod history;
 pub mod invoice_reminders;
 pub mod invoices;
@@ -26,8 +31,10 @@ pub mod items;
 pub mod journals;
 pub mod linked_transactions;
 pub mod manual_journals;
+pub mod order;
 pub mod organisation;
 pub mod overpayments;
+pub mod pagination;
 pub mod payment_services;
 pub mod payments;
 pub mod prepayments;
@@ -41,13 +48,39 @@ pub mod tracking_categories;
 pub mod users;
 
 mod query;
+#[cfg(test)]
+mod request_body_tests;
+pub(crate) mod tenant_cache;
 
 const BASE_URL: &str = "https://api.xero.com/api.xro/2.0";
 
+/// Largest `pageSize` the paged Accounting endpoints accept.
+///
+/// Applies to Invoices, Contacts, BankTransactions, CreditNotes,
+/// ManualJournals, Payments, PurchaseOrders and Quotes. Xero does not reject a
+/// larger value; it quietly returns at most this many records, so list
+/// builders refuse a larger page size with [`XeroError::Validation`] instead.
+/// Without a `pageSize`, these endpoints return 100 records per page.
+///
+/// [`XeroError::Validation`]: crate::error::XeroError::Validation
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Body of a status-only update, e.g. `{"Status": "VOIDED"}`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct StatusUpdate<S> {
+    status: S,
+}
+
 /// A handle to the Accounting API endpoints.
 #[derive(Debug, Clone)]
 pub struct AccountingApi {
     client: ApiClient,
+    report_cache: Arc<reports::cache::ReportCache>,
+    account_codes: Arc<tenant_cache::AccountCodeCache>,
+    base_currencies: Arc<tenant_cache::BaseCurrencyCache>,
+    tax_rate_cache: Arc<tenant_cache::TaxRateCache>,
+    unitdp: Option<u8>,
 }
 
 impl AccountingApi {
@@ -59,15 +92,62 @@ impl AccountingApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
+            report_cache: client.report_cache.clone(),
+            account_codes: client.account_codes.clone(),
+            base_currencies: client.base_currencies.clone(),
+            tax_rate_cache: client.tax_rate_cache.clone(),
+            unitdp: None,
         }
     }
 
+    /// Sends `unitdp` with invoice, credit note, bank transaction and item
+    /// requests made through this handle.
+    ///
+    /// Xero rounds unit amounts to two decimal places unless asked for more;
+    /// pass `4` to read and write unit prices at full precision. List builders
+    /// that take their own `unitdp` override this default.
+    #[must_use]
+    pub fn with_unitdp(mut self, unitdp: u8) -> Self {
+        self.unitdp = Some(unitdp);
+        self
+    }
+
+    /// Starts a query string holding the default `unitdp`, if one is set.
+    pub(crate) fn unitdp_query(&self) -> query::QueryParams {
+        let mut query = query::QueryParams::default();
+        query.push_opt("unitdp", self.unitdp);
+        query
+    }
+
+    /// Moves the record at `path` (e.g. `/Invoices/{id}`) to `status`.
+    ///
+    /// Xero deletes and voids most transactions by POSTing just the new
+    /// `Status`; the `delete`/`void` methods on each resource go through here.
+    pub(crate) async fn set_status<R, S>(&self, path: &str, status: S) -> Result<R, XeroError>
+    where
+        R: DeserializeOwned,
+        S: Serialize,
+    {
+        self.client
+            .send_request(Method::POST, path, None, Some(StatusUpdate { status }))
+            .await
+    }
+
     pub(crate) fn with_token_override(mut self, token: Arc<TokenSet>) -> Self {
         self.client = self.client.with_token_override(token);
         self
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.client = self.client.with_base_url(base_url);
+        self
+    }
+
     /// Access Accounts endpoints.
     #[must_use]
     pub fn accounts(&self) -> accounts::AccountsResource<'_> {
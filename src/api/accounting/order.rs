@@ -0,0 +1,225 @@
+//! Helpers for building values for the Accounting API `order` query parameter.
+//!
+//! Xero ignores an `order` it cannot parse, or one naming a field it does not
+//! have, and returns records in its default order instead of an error. The
+//! per-resource field enums below only offer fields the resource can be
+//! sorted by; `&str` fields are still accepted for anything they miss.
+
+use std::fmt;
+
+/// A field a list endpoint can be sorted by.
+pub trait OrderField {
+    /// The field name as Xero spells it, e.g. `UpdatedDateUTC`.
+    fn field_name(&self) -> &str;
+}
+
+impl OrderField for &str {
+    fn field_name(&self) -> &str {
+        self
+    }
+}
+
+impl OrderField for String {
+    fn field_name(&self) -> &str {
+        self
+    }
+}
+
+/// Builds an `order` value from one or more fields, each ascending unless
+/// marked descending.
+///
+/// Convert it to a `String` for any list builder's `order_by`, or pass it to
+/// the typed `sort` setter where one exists.
+///
+/// ```
+/// use xero_rs_async::api::accounting::order::{InvoiceOrderField, OrderBy};
+///
+/// let order = OrderBy::field(InvoiceOrderField::Date)
+///     .desc()
+///     .then(InvoiceOrderField::InvoiceNumber);
+/// assert_eq!(order.to_string(), "Date DESC,InvoiceNumber ASC");
+/// assert_eq!(OrderBy::field("Name").to_string(), "Name ASC");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct OrderBy<F> {
+    clauses: Vec<(F, bool)>,
+}
+
+impl<F: OrderField> OrderBy<F> {
+    /// Sorts by `field`, ascending.
+    pub fn field(field: F) -> Self {
+        Self {
+            clauses: vec![(field, false)],
+        }
+    }
+
+    /// Breaks ties by `field`, ascending.
+    pub fn then(mut self, field: F) -> Self {
+        self.clauses.push((field, false));
+        self
+    }
+
+    /// Sorts the most recently added field ascending (the default).
+    pub fn asc(self) -> Self {
+        self.direction(false)
+    }
+
+    /// Sorts the most recently added field descending.
+    pub fn desc(self) -> Self {
+        self.direction(true)
+    }
+
+    fn direction(mut self, descending: bool) -> Self {
+        if let Some((_, last)) = self.clauses.last_mut() {
+            *last = descending;
+        }
+        self
+    }
+}
+
+impl<F: OrderField> fmt::Display for OrderBy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (field, descending)) in self.clauses.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            let direction = if *descending { "DESC" } else { "ASC" };
+            write!(f, "{} {direction}", field.field_name())?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: OrderField> From<OrderBy<F>> for String {
+    fn from(order: OrderBy<F>) -> Self {
+        order.to_string()
+    }
+}
+
+/// Declares a resource's sortable fields and their Xero names.
+macro_rules! order_fields {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $field:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl OrderField for $name {
+            fn field_name(&self) -> &str {
+                match self {
+                    $(Self::$variant => $field),+
+                }
+            }
+        }
+    };
+}
+
+order_fields!(
+    /// Fields Invoices can be sorted by.
+    InvoiceOrderField {
+        InvoiceNumber => "InvoiceNumber",
+        Reference => "Reference",
+        Type => "Type",
+        Date => "Date",
+        DueDate => "DueDate",
+        Status => "Status",
+        Total => "Total",
+        AmountDue => "AmountDue",
+        AmountPaid => "AmountPaid",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields Contacts can be sorted by.
+    ContactOrderField {
+        Name => "Name",
+        FirstName => "FirstName",
+        LastName => "LastName",
+        EmailAddress => "EmailAddress",
+        AccountNumber => "AccountNumber",
+        ContactNumber => "ContactNumber",
+        ContactStatus => "ContactStatus",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields BankTransactions can be sorted by.
+    BankTransactionOrderField {
+        Type => "Type",
+        Reference => "Reference",
+        Date => "Date",
+        Status => "Status",
+        Total => "Total",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields Payments can be sorted by.
+    PaymentOrderField {
+        Date => "Date",
+        Amount => "Amount",
+        Reference => "Reference",
+        Status => "Status",
+        PaymentType => "PaymentType",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields CreditNotes can be sorted by.
+    CreditNoteOrderField {
+        CreditNoteNumber => "CreditNoteNumber",
+        Reference => "Reference",
+        Type => "Type",
+        Date => "Date",
+        Status => "Status",
+        Total => "Total",
+        RemainingCredit => "RemainingCredit",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields Items can be sorted by.
+    ItemOrderField {
+        Code => "Code",
+        Name => "Name",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+order_fields!(
+    /// Fields Accounts can be sorted by.
+    AccountOrderField {
+        Code => "Code",
+        Name => "Name",
+        Type => "Type",
+        Class => "Class",
+        Status => "Status",
+        UpdatedDateUtc => "UpdatedDateUTC",
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direction_applies_to_the_latest_field() {
+        let order = OrderBy::field(ContactOrderField::UpdatedDateUtc)
+            .desc()
+            .then(ContactOrderField::Name)
+            .then(ContactOrderField::AccountNumber)
+            .desc();
+        assert_eq!(
+            String::from(order),
+            "UpdatedDateUTC DESC,Name ASC,AccountNumber DESC"
+        );
+        assert_eq!(OrderBy::field("Date").desc().asc().to_string(), "Date ASC");
+    }
+}
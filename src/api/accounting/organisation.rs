@@ -24,6 +24,31 @@ impl<'a> OrganisationResource<'a> {
         Ok(resp.organisations)
     }
 
+    /// Retrieves the tenant's organisation.
+    ///
+    /// Xero always returns exactly one organisation per tenant; an empty
+    /// response is reported as a `NOT_FOUND` API error.
+    pub async fn single(&self) -> Result<organisation::Organisation, XeroError> {
+        self.get().await?.into_iter().next().ok_or(XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: "Organisation not found in response".to_string(),
+        })
+    }
+
+    /// Returns the organisation's base currency code (e.g. `"NZD"`).
+    ///
+    /// The base currency never changes, so it is fetched with the
+    /// organisation on the first call for the tenant and cached on the client.
+    pub async fn base_currency(&self) -> Result<String, XeroError> {
+        let tenant_id = self.api.client.tenant_id();
+        if let Some(code) = self.api.base_currencies.get(tenant_id, &()) {
+            return Ok(code);
+        }
+        let code = self.single().await?.base_currency;
+        self.api.base_currencies.insert(tenant_id, (), code.clone());
+        Ok(code)
+    }
+
     /// Retrieves organisation actions.
     pub async fn actions(&self) -> Result<Vec<organisation::OrganisationAction>, XeroError> {
         let resp: organisation::ActionsResponse = self
@@ -34,6 +59,25 @@ impl<'a> OrganisationResource<'a> {
         Ok(resp.actions)
     }
 
+    /// Returns whether the organisation allows `action`.
+    ///
+    /// Actions missing from Xero's response, and
+    /// [`OrganisationActionName::Unknown`](organisation::OrganisationActionName::Unknown),
+    /// are reported as not allowed.
+    pub async fn can_perform(
+        &self,
+        action: organisation::OrganisationActionName,
+    ) -> Result<bool, XeroError> {
+        if action == organisation::OrganisationActionName::Unknown {
+            return Ok(false);
+        }
+        Ok(self
+            .actions()
+            .await?
+            .iter()
+            .any(|a| a.name == action && a.is_allowed()))
+    }
+
     /// Retrieves CIS settings for the organisation (UK only).
     pub async fn cis_settings(&self) -> Result<organisation::CISSettings, XeroError> {
         let path = "/Organisation/CISSettings";
@@ -50,6 +94,16 @@ impl AccountingApi {
         self.organisation().get().await
     }
 
+    /// Retrieves the tenant's organisation, erroring if none is returned.
+    pub async fn get_organisation_single(&self) -> Result<organisation::Organisation, XeroError> {
+        self.organisation().single().await
+    }
+
+    /// Returns the organisation's base currency code, cached per tenant.
+    pub async fn get_base_currency(&self) -> Result<String, XeroError> {
+        self.organisation().base_currency().await
+    }
+
     /// Retrieves organisation actions.
     pub async fn get_organisation_actions(
         &self,
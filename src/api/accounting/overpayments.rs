@@ -1,6 +1,8 @@
+use super::filter::WhereBuilder;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::bank_transaction::{BankTransaction, BankTransactionType};
 use crate::models::accounting::common::Allocation;
 use crate::models::accounting::{credit_note, overpayment};
 use chrono::{DateTime, Utc};
@@ -37,6 +39,85 @@ impl<'a> OverpaymentsResource<'a> {
         Ok(resp.overpayments)
     }
 
+    /// Retrieves an overpayment by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get_one(
+        &self,
+        overpayment_id: Uuid,
+    ) -> Result<Option<overpayment::Overpayment>, XeroError> {
+        match self.get(overpayment_id).await {
+            Ok(overpayments) => Ok(overpayments.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves the bank transaction the overpayment was created from.
+    ///
+    /// Overpayments do not carry the link themselves; the originating transaction
+    /// is the one whose `OverpaymentID` points back at the overpayment.
+    pub async fn source_transaction(
+        &self,
+        overpayment_id: Uuid,
+    ) -> Result<Option<BankTransaction>, XeroError> {
+        let filter = WhereBuilder::new()
+            .eq_guid("OverpaymentID", overpayment_id)
+            .build();
+        let transactions = self
+            .api
+            .bank_transactions()
+            .list()
+            .where_filter(filter)
+            .send()
+            .await?;
+        Ok(transactions
+            .into_iter()
+            .find(|transaction| transaction.overpayment_id == Some(overpayment_id)))
+    }
+
+    /// Creates an overpayment and returns it.
+    ///
+    /// Xero has no `PUT /Overpayments`; overpayments are created as bank transactions
+    /// of type `RECEIVE-OVERPAYMENT` (money in from a customer) or
+    /// `SPEND-OVERPAYMENT` (money out to a supplier), and Xero then creates the
+    /// overpayment that the transaction's `OverpaymentID` points at. `transaction` must
+    /// carry one of those two types; anything else is rejected with
+    /// [`XeroError::Validation`] before the request is sent.
+    pub async fn create(
+        &self,
+        transaction: BankTransaction,
+    ) -> Result<overpayment::Overpayment, XeroError> {
+        match transaction.transaction_type {
+            Some(BankTransactionType::ReceiveOverpayment | BankTransactionType::SpendOverpayment) => {}
+            other => {
+                return Err(XeroError::Validation(format!(
+                    "Overpayments are created from RECEIVE-OVERPAYMENT or SPEND-OVERPAYMENT bank transactions, got {other:?}"
+                )))
+            }
+        }
+        let created = self
+            .api
+            .bank_transactions()
+            .create(vec![transaction], None)
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|transaction| transaction.overpayment_id);
+        let overpayment_id = created.ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: "Bank transaction was created without an OverpaymentID".to_string(),
+        })?;
+        self.get(overpayment_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Overpayment {overpayment_id} not found after creation"),
+            })
+    }
+
     /// Allocates an overpayment.
     pub async fn allocate(
         &self,
@@ -52,17 +133,25 @@ impl<'a> OverpaymentsResource<'a> {
         Ok(resp.allocations)
     }
 
-    /// Deletes an overpayment allocation.
+    /// Deletes an overpayment allocation, returning the deleted allocation.
+    ///
+    /// Xero returns only the allocation (marked `is_deleted`); fetch the
+    /// overpayment again with `get` to see its updated remaining credit.
     pub async fn delete_allocation(
         &self,
         overpayment_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         let path = format!("/Overpayments/{overpayment_id}/Allocations/{allocation_id}");
-        self.api
+        let resp: credit_note::DeletedAllocationResponse = self
+            .api
             .client
-            .send_request_empty_response(Method::DELETE, &path, None::<()>)
-            .await
+            .send_request(Method::DELETE, &path, None, None::<()>)
+            .await?;
+        resp.into_allocation().ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: format!("Allocation {allocation_id} not returned after deletion"),
+        })
     }
 }
 
@@ -146,6 +235,31 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves an overpayment by ID, or `None` if it does not exist.
+    pub async fn get_overpayment(
+        &self,
+        overpayment_id: Uuid,
+    ) -> Result<Option<overpayment::Overpayment>, XeroError> {
+        self.overpayments().get_one(overpayment_id).await
+    }
+
+    /// Retrieves the bank transaction an overpayment was created from.
+    pub async fn get_overpayment_source_transaction(
+        &self,
+        overpayment_id: Uuid,
+    ) -> Result<Option<BankTransaction>, XeroError> {
+        self.overpayments().source_transaction(overpayment_id).await
+    }
+
+    /// Creates an overpayment from a `RECEIVE-OVERPAYMENT` or `SPEND-OVERPAYMENT`
+    /// bank transaction. See [`OverpaymentsResource::create`].
+    pub async fn create_overpayment(
+        &self,
+        transaction: BankTransaction,
+    ) -> Result<overpayment::Overpayment, XeroError> {
+        self.overpayments().create(transaction).await
+    }
+
     /// Allocates an overpayment.
     pub async fn allocate_overpayment(
         &self,
@@ -157,12 +271,12 @@ impl AccountingApi {
             .await
     }
 
-    /// Deletes an overpayment allocation.
+    /// Deletes an overpayment allocation, returning the deleted allocation.
     pub async fn delete_overpayment_allocation(
         &self,
         overpayment_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         self.overpayments()
             .delete_allocation(overpayment_id, allocation_id)
             .await
@@ -0,0 +1,106 @@
+//! A common interface over the Accounting API's two paging styles.
+//!
+//! Most list endpoints page with a 1-based `page` and a `pageSize`, and the
+//! last page is the first one shorter than the page size. Journals page by
+//! `offset` instead: Xero returns up to 100 journals whose `JournalNumber` is
+//! greater than the offset, so the next offset is the last journal number seen,
+//! and the feed ends at the first empty batch. Linked transactions take no
+//! page size, so they too run until an empty page.
+//!
+//! [`Paginated`] hides that difference, so code that drains or checkpoints a
+//! feed can be written once for [`JournalsPager`], [`InvoicesPager`],
+//! [`LinkedTransactionsPager`] and [`ContactsExport`].
+//!
+//! [`JournalsPager`]: super::journals::JournalsPager
+//! [`InvoicesPager`]: super::invoices::InvoicesPager
+//! [`LinkedTransactionsPager`]: super::linked_transactions::LinkedTransactionsPager
+//! [`ContactsExport`]: super::contacts::ContactsExport
+
+use crate::error::XeroError;
+use std::future::Future;
+
+/// An async cursor that fetches a feed one page at a time.
+pub trait Paginated {
+    /// The record type returned in each page.
+    type Item: Send;
+
+    /// Fetches the next page, or `None` once the feed is exhausted.
+    fn next_page(
+        &mut self,
+    ) -> impl Future<Output = Result<Option<Vec<Self::Item>>, XeroError>> + Send;
+
+    /// Drains the cursor, returning every remaining record.
+    fn collect_all(mut self) -> impl Future<Output = Result<Vec<Self::Item>, XeroError>> + Send
+    where
+        Self: Sized + Send,
+    {
+        async move {
+            let mut all = Vec::new();
+            while let Some(page) = self.next_page().await? {
+                all.extend(page);
+            }
+            Ok(all)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::accounting::AccountingApi;
+    use crate::test_support::{self, MockServer};
+    use uuid::Uuid;
+
+    async fn count_all<P: Paginated + Send>(pager: P) -> usize {
+        pager.collect_all().await.unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn offset_and_page_feeds_drain_through_one_interface() {
+        let invoices = |count: usize| {
+            let invoice = serde_json::json!({
+                "Type": "ACCREC",
+                "Contact": { "Name": "ACME Ltd" },
+                "Date": "/Date(1700000000000+0000)/",
+                "LineItems": []
+            });
+            serde_json::json!({ "Invoices": vec![invoice; count] }).to_string()
+        };
+        let bodies = vec![
+            r#"{"Journals":[{"JournalID":"00000000-0000-0000-0000-000000000001","JournalDate":"/Date(1700000000000+0000)/","JournalNumber":7,"CreatedDateUTC":"/Date(1700000000000+0000)/","JournalLines":[]}]}"#
+                .to_string(),
+            r#"{"Journals":[]}"#.to_string(),
+            invoices(2),
+            invoices(1),
+            r#"{"LinkedTransactions":[{"SourceTransactionID":"00000000-0000-0000-0000-000000000000","SourceLineItemID":"00000000-0000-0000-0000-000000000002"}]}"#
+                .to_string(),
+            r#"{"LinkedTransactions":[]}"#.to_string(),
+        ];
+        let server = MockServer::ok(bodies).await;
+        let api: AccountingApi = test_support::accounting_api(&server).await;
+
+        assert_eq!(count_all(api.journals().pager(0)).await, 1);
+        assert_eq!(
+            count_all(api.invoices().list().page_size(2).pager()).await,
+            3
+        );
+        assert_eq!(
+            api.get_linked_transactions_for_source(Uuid::nil())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            server.paths().await,
+            vec![
+                "/Journals?offset=0",
+                "/Journals?offset=7",
+                "/Invoices?page=1&pageSize=2",
+                "/Invoices?page=2&pageSize=2",
+                "/LinkedTransactions?SourceTransactionID=00000000-0000-0000-0000-000000000000&page=1",
+                "/LinkedTransactions?SourceTransactionID=00000000-0000-0000-0000-000000000000&page=2",
+            ]
+        );
+    }
+}
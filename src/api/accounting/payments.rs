@@ -1,12 +1,24 @@
-use super::query::QueryParams;
+use super::order::{OrderBy, PaymentOrderField};
+use super::query::{check_page_size, QueryParams};
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::fan_out::{fan_out, PER_TENANT_FAN_OUT};
+use crate::models::accounting::common::WithWarnings;
 use crate::models::accounting::payment;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
-use serde::Serialize;
 use uuid::Uuid;
 
+/// Outcome of reversing a batch of payments with [`PaymentsResource::delete_many`].
+#[derive(Debug, Default)]
+pub struct PaymentReversals {
+    /// Payments Xero confirmed as reversed (`DELETED`).
+    pub reversed: Vec<payment::Payment>,
+    /// Payments that could not be deleted, with the error for each.
+    pub failed: Vec<(Uuid, XeroError)>,
+}
+
 /// Resource accessor for Payments.
 #[derive(Debug, Clone, Copy)]
 pub struct PaymentsResource<'a> {
@@ -40,41 +52,94 @@ impl<'a> PaymentsResource<'a> {
         payments: Vec<payment::Payment>,
         summarize_errors: Option<bool>,
     ) -> Result<Vec<payment::Payment>, XeroError> {
+        Ok(self
+            .create_with_warnings(payments, summarize_errors)
+            .await?
+            .items)
+    }
+
+    /// Creates one or more new payments, also returning Xero's response-level warnings.
+    pub async fn create_with_warnings(
+        &self,
+        payments: Vec<payment::Payment>,
+        summarize_errors: Option<bool>,
+    ) -> Result<WithWarnings<payment::Payment>, XeroError> {
         let mut query = QueryParams::default();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if payments.len() == 1 {
-            serde_json::to_value(payments.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(payment::PaymentsRequest {
-                payments: Some(payments),
-            })?
-        };
+        let body = single_or_wrapped(payments, |payments| payment::PaymentsRequest {
+            payments: Some(payments),
+        })?;
         let resp: payment::PaymentsResponse = self
             .api
             .client
             .send_request(Method::PUT, "/Payments", query.as_slice(), Some(body))
             .await?;
-        Ok(resp.payments.unwrap_or_default())
+        Ok(WithWarnings {
+            items: resp.payments.unwrap_or_default(),
+            warnings: resp.warnings,
+        })
     }
 
-    /// Deletes (reverses) a payment.
-    pub async fn delete(&self, payment_id: Uuid) -> Result<Vec<payment::Payment>, XeroError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "PascalCase")]
-        struct DeleteRequest {
-            status: payment::PaymentStatus,
-        }
+    /// Reverses a payment by setting its status to `DELETED`.
+    ///
+    /// Xero has no separate void for payments: a `DELETED` payment stays on
+    /// record and its amount is added back to the amount due on the invoice,
+    /// credit note, prepayment or overpayment it paid. Xero does not create a
+    /// separate reversal record to link to, so the reversed payment itself is
+    /// returned, and this fails unless Xero reports it as `DELETED`.
+    pub async fn reverse(&self, payment_id: Uuid) -> Result<payment::Payment, XeroError> {
         let path = format!("/Payments/{payment_id}");
-        let body = DeleteRequest {
-            status: payment::PaymentStatus::Deleted,
-        };
         let resp: payment::PaymentsResponse = self
             .api
-            .client
-            .send_request(Method::POST, &path, None, Some(body))
+            .set_status(&path, payment::PaymentStatus::Deleted)
             .await?;
-        Ok(resp.payments.unwrap_or_default())
+        reversed_payment(resp.payments.unwrap_or_default(), payment_id)
     }
+
+    /// Deletes (reverses) a payment.
+    #[deprecated(note = "Use PaymentsResource::reverse, which checks the payment was reversed.")]
+    pub async fn delete(&self, payment_id: Uuid) -> Result<Vec<payment::Payment>, XeroError> {
+        Ok(vec![self.reverse(payment_id).await?])
+    }
+
+    /// Reverses many payments concurrently.
+    ///
+    /// Each payment is reversed with its own request, at most five at a time
+    /// to stay within Xero's concurrent request limit. Failures are collected
+    /// rather than aborting the batch, and both lists keep the order of
+    /// `payment_ids`.
+    pub async fn delete_many(&self, payment_ids: Vec<Uuid>) -> PaymentReversals {
+        let outcomes = fan_out(payment_ids, PER_TENANT_FAN_OUT, |payment_id| {
+            let api = self.api.clone();
+            async move { api.payments().reverse(payment_id).await }
+        })
+        .await;
+
+        let mut reversals = PaymentReversals::default();
+        for (payment_id, result) in outcomes {
+            match result {
+                Ok(payment) => reversals.reversed.push(payment),
+                Err(e) => reversals.failed.push((payment_id, e)),
+            }
+        }
+        reversals
+    }
+
+    /// Lists payments with the given status.
+    pub async fn list_by_status(
+        &self,
+        status: payment::PaymentStatus,
+    ) -> Result<Vec<payment::Payment>, XeroError> {
+        self.list()
+            .where_filter(status_filter(&status))
+            .send()
+            .await
+    }
+}
+
+/// Builds the `where` clause matching payments in `status`.
+fn status_filter(status: &payment::PaymentStatus) -> String {
+    format!("Status==\"{status}\"")
 }
 
 /// Builder for Payments list requests.
@@ -110,13 +175,18 @@ impl<'a> PaymentsListRequest<'a> {
         self
     }
 
+    /// Orders by fields this endpoint can sort on. See [`OrderBy`].
+    pub fn sort(self, order: OrderBy<PaymentOrderField>) -> Self {
+        self.order_by(order)
+    }
+
     /// Sets the page number.
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
@@ -124,6 +194,7 @@ impl<'a> PaymentsListRequest<'a> {
 
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<payment::Payment>, XeroError> {
+        check_page_size("/Payments", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("where", self.where_filter);
         query.push_opt_string("order", self.order_by);
@@ -180,11 +251,97 @@ impl AccountingApi {
         self.payments().create(payments, summarize_errors).await
     }
 
+    /// Reverses a payment. See [`PaymentsResource::reverse`].
+    pub async fn reverse_payment(&self, payment_id: Uuid) -> Result<payment::Payment, XeroError> {
+        self.payments().reverse(payment_id).await
+    }
+
     /// Deletes (reverses) a payment.
+    #[deprecated(
+        note = "Use AccountingApi::reverse_payment, which checks the payment was reversed."
+    )]
     pub async fn delete_payment(
         &self,
         payment_id: Uuid,
     ) -> Result<Vec<payment::Payment>, XeroError> {
-        self.payments().delete(payment_id).await
+        Ok(vec![self.reverse_payment(payment_id).await?])
+    }
+
+    /// Reverses many payments, collecting per-payment failures.
+    pub async fn delete_payments(&self, payment_ids: Vec<Uuid>) -> PaymentReversals {
+        self.payments().delete_many(payment_ids).await
+    }
+
+    /// Retrieves all payments with the given status.
+    pub async fn get_payments_by_status(
+        &self,
+        status: payment::PaymentStatus,
+    ) -> Result<Vec<payment::Payment>, XeroError> {
+        self.payments().list_by_status(status).await
+    }
+}
+
+/// Picks the reversed payment out of a status update response, checking Xero
+/// reports it as `DELETED`.
+fn reversed_payment(
+    payments: Vec<payment::Payment>,
+    payment_id: Uuid,
+) -> Result<payment::Payment, XeroError> {
+    let payment = payments
+        .into_iter()
+        .find(|p| p.payment_id == Some(payment_id))
+        .ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: format!("Xero did not return payment {payment_id} after reversing it"),
+        })?;
+    match payment.status {
+        Some(payment::PaymentStatus::Deleted) => Ok(payment),
+        ref status => {
+            let status = status.as_ref().map_or("no status".to_string(), |s| {
+                format!("status {}", s.as_xero_value())
+            });
+            Err(XeroError::Validation(format!(
+                "payment {payment_id} was not reversed; Xero reports {status}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_filter_quotes_value() {
+        assert_eq!(
+            status_filter(&payment::PaymentStatus::Deleted),
+            "Status==\"DELETED\""
+        );
+        assert_eq!(
+            status_filter(&payment::PaymentStatus::Authorised),
+            "Status==\"AUTHORISED\""
+        );
+    }
+
+    #[test]
+    fn reversal_must_come_back_deleted() {
+        let payment_id = Uuid::from_u128(7);
+        let payment = |status: &str| -> payment::Payment {
+            serde_json::from_value(serde_json::json!({
+                "PaymentID": payment_id,
+                "Date": "/Date(1700000000000+0000)/",
+                "Amount": 10,
+                "Status": status,
+            }))
+            .unwrap()
+        };
+
+        let reversed = reversed_payment(vec![payment("DELETED")], payment_id).unwrap();
+        assert_eq!(reversed.status, Some(payment::PaymentStatus::Deleted));
+        assert!(matches!(
+            reversed_payment(vec![payment("AUTHORISED")], payment_id),
+            Err(XeroError::Validation(message)) if message.ends_with("reports status AUTHORISED")
+        ));
+        assert!(reversed_payment(Vec::new(), payment_id).is_err());
     }
 }
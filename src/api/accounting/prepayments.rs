@@ -1,6 +1,8 @@
+use super::filter::WhereBuilder;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::bank_transaction::{BankTransaction, BankTransactionType};
 use crate::models::accounting::common::Allocation;
 use crate::models::accounting::{credit_note, prepayment};
 use chrono::{DateTime, Utc};
@@ -34,6 +36,85 @@ impl<'a> PrepaymentsResource<'a> {
         Ok(resp.prepayments)
     }
 
+    /// Retrieves a prepayment by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get_one(
+        &self,
+        prepayment_id: Uuid,
+    ) -> Result<Option<prepayment::Prepayment>, XeroError> {
+        match self.get(prepayment_id).await {
+            Ok(prepayments) => Ok(prepayments.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves the bank transaction the prepayment was created from.
+    ///
+    /// Prepayments do not carry the link themselves; the originating transaction
+    /// is the one whose `PrepaymentID` points back at the prepayment.
+    pub async fn source_transaction(
+        &self,
+        prepayment_id: Uuid,
+    ) -> Result<Option<BankTransaction>, XeroError> {
+        let filter = WhereBuilder::new()
+            .eq_guid("PrepaymentID", prepayment_id)
+            .build();
+        let transactions = self
+            .api
+            .bank_transactions()
+            .list()
+            .where_filter(filter)
+            .send()
+            .await?;
+        Ok(transactions
+            .into_iter()
+            .find(|transaction| transaction.prepayment_id == Some(prepayment_id)))
+    }
+
+    /// Creates a prepayment and returns it.
+    ///
+    /// Xero has no `PUT /Prepayments`; prepayments are created as bank transactions
+    /// of type `RECEIVE-PREPAYMENT` (money in from a customer) or
+    /// `SPEND-PREPAYMENT` (money out to a supplier), and Xero then creates the
+    /// prepayment that the transaction's `PrepaymentID` points at. `transaction` must
+    /// carry one of those two types; anything else is rejected with
+    /// [`XeroError::Validation`] before the request is sent.
+    pub async fn create(
+        &self,
+        transaction: BankTransaction,
+    ) -> Result<prepayment::Prepayment, XeroError> {
+        match transaction.transaction_type {
+            Some(BankTransactionType::ReceivePrepayment | BankTransactionType::SpendPrepayment) => {}
+            other => {
+                return Err(XeroError::Validation(format!(
+                    "Prepayments are created from RECEIVE-PREPAYMENT or SPEND-PREPAYMENT bank transactions, got {other:?}"
+                )))
+            }
+        }
+        let created = self
+            .api
+            .bank_transactions()
+            .create(vec![transaction], None)
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|transaction| transaction.prepayment_id);
+        let prepayment_id = created.ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: "Bank transaction was created without a PrepaymentID".to_string(),
+        })?;
+        self.get(prepayment_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| XeroError::Api {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("Prepayment {prepayment_id} not found after creation"),
+            })
+    }
+
     /// Allocates a prepayment to an invoice.
     pub async fn allocate(
         &self,
@@ -49,17 +130,25 @@ impl<'a> PrepaymentsResource<'a> {
         Ok(resp.allocations)
     }
 
-    /// Deletes a prepayment allocation.
+    /// Deletes a prepayment allocation, returning the deleted allocation.
+    ///
+    /// Xero returns only the allocation (marked `is_deleted`); fetch the
+    /// prepayment again with `get` to see its updated remaining credit.
     pub async fn delete_allocation(
         &self,
         prepayment_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         let path = format!("/Prepayments/{prepayment_id}/Allocations/{allocation_id}");
-        self.api
+        let resp: credit_note::DeletedAllocationResponse = self
+            .api
             .client
-            .send_request_empty_response(Method::DELETE, &path, None::<()>)
-            .await
+            .send_request(Method::DELETE, &path, None, None::<()>)
+            .await?;
+        resp.into_allocation().ok_or_else(|| XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: format!("Allocation {allocation_id} not returned after deletion"),
+        })
     }
 }
 
@@ -143,6 +232,31 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves a prepayment by ID, or `None` if it does not exist.
+    pub async fn get_prepayment(
+        &self,
+        prepayment_id: Uuid,
+    ) -> Result<Option<prepayment::Prepayment>, XeroError> {
+        self.prepayments().get_one(prepayment_id).await
+    }
+
+    /// Retrieves the bank transaction a prepayment was created from.
+    pub async fn get_prepayment_source_transaction(
+        &self,
+        prepayment_id: Uuid,
+    ) -> Result<Option<BankTransaction>, XeroError> {
+        self.prepayments().source_transaction(prepayment_id).await
+    }
+
+    /// Creates a prepayment from a `RECEIVE-PREPAYMENT` or `SPEND-PREPAYMENT`
+    /// bank transaction. See [`PrepaymentsResource::create`].
+    pub async fn create_prepayment(
+        &self,
+        transaction: BankTransaction,
+    ) -> Result<prepayment::Prepayment, XeroError> {
+        self.prepayments().create(transaction).await
+    }
+
     /// Allocates a prepayment to an invoice.
     pub async fn allocate_prepayment(
         &self,
@@ -152,12 +266,12 @@ impl AccountingApi {
         self.prepayments().allocate(prepayment_id, allocation).await
     }
 
-    /// Deletes a prepayment allocation.
+    /// Deletes a prepayment allocation, returning the deleted allocation.
     pub async fn delete_prepayment_allocation(
         &self,
         prepayment_id: Uuid,
         allocation_id: Uuid,
-    ) -> Result<(), XeroError> {
+    ) -> Result<Allocation, XeroError> {
         self.prepayments()
             .delete_allocation(prepayment_id, allocation_id)
             .await
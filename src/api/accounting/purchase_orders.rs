@@ -1,7 +1,10 @@
-use super::query::QueryParams;
+use super::attachments::AttachmentEndpoint;
+use super::query::{check_page_size, QueryParams};
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::attachment::Attachment;
 use crate::models::accounting::purchase_order;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -43,11 +46,9 @@ impl<'a> PurchaseOrdersResource<'a> {
     ) -> Result<Vec<purchase_order::PurchaseOrder>, XeroError> {
         let mut query = QueryParams::default();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if purchase_orders.len() == 1 {
-            serde_json::to_value(purchase_orders.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(purchase_order::PurchaseOrdersRequest { purchase_orders })?
-        };
+        let body = single_or_wrapped(purchase_orders, |purchase_orders| {
+            purchase_order::PurchaseOrdersRequest { purchase_orders }
+        })?;
         let resp: purchase_order::PurchaseOrdersResponse = self
             .api
             .client
@@ -60,6 +61,62 @@ impl<'a> PurchaseOrdersResource<'a> {
             .await?;
         Ok(resp.purchase_orders)
     }
+
+    /// Moves a purchase order to a new status, e.g. `SUBMITTED` or `AUTHORISED`.
+    pub async fn update_status(
+        &self,
+        purchase_order_id: Uuid,
+        status: purchase_order::PurchaseOrderStatus,
+    ) -> Result<Vec<purchase_order::PurchaseOrder>, XeroError> {
+        let path = format!("/PurchaseOrders/{purchase_order_id}");
+        let resp: purchase_order::PurchaseOrdersResponse =
+            self.api.set_status(&path, status).await?;
+        Ok(resp.purchase_orders)
+    }
+
+    /// Deletes a purchase order by setting its status to `DELETED`.
+    pub async fn delete(
+        &self,
+        purchase_order_id: Uuid,
+    ) -> Result<Vec<purchase_order::PurchaseOrder>, XeroError> {
+        self.update_status(
+            purchase_order_id,
+            purchase_order::PurchaseOrderStatus::Deleted,
+        )
+        .await
+    }
+
+    /// Retrieves the attachments on a purchase order.
+    pub async fn attachments(&self, purchase_order_id: Uuid) -> Result<Vec<Attachment>, XeroError> {
+        self.api
+            .attachments()
+            .list(AttachmentEndpoint::PurchaseOrders, purchase_order_id)
+            .await
+    }
+
+    /// Attaches a file to a purchase order.
+    pub async fn upload_attachment<B>(
+        &self,
+        purchase_order_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.api
+            .attachments()
+            .upload_put(
+                AttachmentEndpoint::PurchaseOrders,
+                purchase_order_id,
+                filename,
+                content_type,
+                body,
+                None,
+            )
+            .await
+    }
 }
 
 /// Builder for Purchase Orders list requests.
@@ -117,7 +174,7 @@ impl<'a> PurchaseOrdersListRequest<'a> {
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
@@ -125,6 +182,7 @@ impl<'a> PurchaseOrdersListRequest<'a> {
 
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<purchase_order::PurchaseOrder>, XeroError> {
+        check_page_size("/PurchaseOrders", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("status", self.status);
         query.push_opt_string("DateFrom", self.date_from);
@@ -191,4 +249,36 @@ impl AccountingApi {
             .create_or_update(purchase_orders, summarize_errors)
             .await
     }
+
+    /// Deletes a purchase order by setting its status to `DELETED`.
+    pub async fn delete_purchase_order(
+        &self,
+        purchase_order_id: Uuid,
+    ) -> Result<Vec<purchase_order::PurchaseOrder>, XeroError> {
+        self.purchase_orders().delete(purchase_order_id).await
+    }
+
+    /// Retrieves the attachments on a purchase order.
+    pub async fn get_purchase_order_attachments(
+        &self,
+        purchase_order_id: Uuid,
+    ) -> Result<Vec<Attachment>, XeroError> {
+        self.purchase_orders().attachments(purchase_order_id).await
+    }
+
+    /// Attaches a file to a purchase order.
+    pub async fn upload_purchase_order_attachment<B>(
+        &self,
+        purchase_order_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.purchase_orders()
+            .upload_attachment(purchase_order_id, filename, content_type, body)
+            .await
+    }
 }
@@ -1,5 +1,28 @@
+use super::MAX_PAGE_SIZE;
+use crate::error::XeroError;
 use std::fmt::Display;
 
+/// Rejects a `pageSize` Xero would silently cap at [`MAX_PAGE_SIZE`].
+pub(crate) fn check_page_size(endpoint: &str, page_size: Option<u32>) -> Result<(), XeroError> {
+    match page_size {
+        Some(size) if size > MAX_PAGE_SIZE => Err(XeroError::Validation(format!(
+            "pageSize {size} exceeds the maximum of {MAX_PAGE_SIZE} for {endpoint}; \
+             Xero would return only {MAX_PAGE_SIZE} records per page"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects a pager page size that is zero or above [`MAX_PAGE_SIZE`].
+pub(crate) fn check_pager_page_size(endpoint: &str, page_size: u32) -> Result<(), XeroError> {
+    if page_size == 0 {
+        return Err(XeroError::Validation(format!(
+            "pageSize for {endpoint} must be at least 1"
+        )));
+    }
+    check_page_size(endpoint, Some(page_size))
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct QueryParams {
     params: Vec<(String, String)>,
@@ -62,3 +85,24 @@ where
         .collect::<Vec<_>>()
         .join(",")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_above_the_cap_is_rejected() {
+        assert!(check_page_size("/Invoices", None).is_ok());
+        assert!(check_page_size("/Invoices", Some(MAX_PAGE_SIZE)).is_ok());
+        let err = check_page_size("/Invoices", Some(MAX_PAGE_SIZE + 1)).unwrap_err();
+        assert!(matches!(err, XeroError::Validation(msg) if msg.contains("/Invoices")));
+    }
+
+    #[test]
+    fn pager_page_size_must_be_between_one_and_the_cap() {
+        assert!(check_pager_page_size("/Contacts", 1).is_ok());
+        assert!(check_pager_page_size("/Contacts", MAX_PAGE_SIZE).is_ok());
+        assert!(check_pager_page_size("/Contacts", 0).is_err());
+        assert!(check_pager_page_size("/Contacts", MAX_PAGE_SIZE + 1).is_err());
+    }
+}
@@ -1,7 +1,8 @@
-use super::query::QueryParams;
+use super::query::{check_page_size, QueryParams};
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::quote;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -40,11 +41,7 @@ impl<'a> QuotesResource<'a> {
     ) -> Result<Vec<quote::Quote>, XeroError> {
         let mut query = QueryParams::default();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if quotes.len() == 1 {
-            serde_json::to_value(quotes.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(quote::QuotesRequest { quotes })?
-        };
+        let body = single_or_wrapped(quotes, |quotes| quote::QuotesRequest { quotes })?;
         let resp: quote::QuotesResponse = self
             .api
             .client
@@ -141,7 +138,7 @@ impl<'a> QuotesListRequest<'a> {
         self
     }
 
-    /// Sets the page size.
+    /// Sets the page size, at most [`MAX_PAGE_SIZE`](crate::api::accounting::MAX_PAGE_SIZE).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
         self
@@ -149,6 +146,7 @@ impl<'a> QuotesListRequest<'a> {
 
     /// Executes the list request.
     pub async fn send(self) -> Result<Vec<quote::Quote>, XeroError> {
+        check_page_size("/Quotes", self.page_size)?;
         let mut query = QueryParams::default();
         query.push_opt_string("QuoteNumber", self.quote_number);
         query.push_opt("ContactID", self.contact_id);
@@ -1,7 +1,10 @@
+use super::attachments::AttachmentEndpoint;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
+use crate::models::accounting::attachment::Attachment;
 use crate::models::accounting::receipt;
+use crate::util::single_or_wrapped;
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use uuid::Uuid;
@@ -41,11 +44,7 @@ impl<'a> ReceiptsResource<'a> {
     ) -> Result<Vec<receipt::Receipt>, XeroError> {
         let mut query = QueryParams::default();
         query.push_opt("summarizeErrors", summarize_errors);
-        let body = if receipts.len() == 1 {
-            serde_json::to_value(receipts.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(receipt::ReceiptsRequest { receipts })?
-        };
+        let body = single_or_wrapped(receipts, |receipts| receipt::ReceiptsRequest { receipts })?;
         let resp: receipt::ReceiptsResponse = self
             .api
             .client
@@ -53,6 +52,38 @@ impl<'a> ReceiptsResource<'a> {
             .await?;
         Ok(resp.receipts)
     }
+
+    /// Retrieves the attachments on a receipt.
+    pub async fn attachments(&self, receipt_id: Uuid) -> Result<Vec<Attachment>, XeroError> {
+        self.api
+            .attachments()
+            .list(AttachmentEndpoint::Receipts, receipt_id)
+            .await
+    }
+
+    /// Attaches a file, such as a photo of the paper receipt, to a receipt.
+    pub async fn upload_attachment<B>(
+        &self,
+        receipt_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.api
+            .attachments()
+            .upload_put(
+                AttachmentEndpoint::Receipts,
+                receipt_id,
+                filename,
+                content_type,
+                body,
+                None,
+            )
+            .await
+    }
 }
 
 /// Builder for Receipts list requests.
@@ -132,4 +163,28 @@ impl AccountingApi {
             .create_or_update(receipts, summarize_errors)
             .await
     }
+
+    /// Retrieves the attachments on a receipt.
+    pub async fn get_receipt_attachments(
+        &self,
+        receipt_id: Uuid,
+    ) -> Result<Vec<Attachment>, XeroError> {
+        self.receipts().attachments(receipt_id).await
+    }
+
+    /// Attaches a file to a receipt.
+    pub async fn upload_receipt_attachment<B>(
+        &self,
+        receipt_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<Vec<Attachment>, XeroError>
+    where
+        B: Into<reqwest::Body>,
+    {
+        self.receipts()
+            .upload_attachment(receipt_id, filename, content_type, body)
+            .await
+    }
 }
@@ -2,6 +2,7 @@ use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::repeating_invoice;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -40,13 +41,11 @@ impl<'a> RepeatingInvoicesResource<'a> {
         &self,
         invoices: Vec<repeating_invoice::RepeatingInvoice>,
     ) -> Result<Vec<repeating_invoice::RepeatingInvoice>, XeroError> {
-        let body = if invoices.len() == 1 {
-            serde_json::to_value(invoices.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(repeating_invoice::RepeatingInvoicesRequest {
+        let body = single_or_wrapped(invoices, |invoices| {
+            repeating_invoice::RepeatingInvoicesRequest {
                 repeating_invoices: invoices,
-            })?
-        };
+            }
+        })?;
         let resp: repeating_invoice::RepeatingInvoicesResponse = self
             .api
             .client
@@ -0,0 +1,196 @@
+use super::super::pagination::Paginated;
+use super::ReportsResource;
+use crate::error::XeroError;
+use crate::fan_out::{fan_out, PER_TENANT_FAN_OUT};
+use crate::models::accounting::common::Money;
+use crate::models::accounting::{invoice, report};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// One contact's Aged Receivables report, as returned by
+/// [`ReportsResource::aged_receivables_all_contacts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactAgedReceivables {
+    pub contact_id: Uuid,
+    pub contact_name: String,
+    pub report: report::AgedReceivablesByContactReport,
+}
+
+/// The outcome of [`ReportsResource::aged_receivables_all_contacts`]: one
+/// report per contact that could be fetched, and the contacts that could not.
+#[derive(Debug, Default)]
+pub struct AllContactsAgedReceivables {
+    /// The reports fetched, in the order the contacts were first seen.
+    pub reports: Vec<ContactAgedReceivables>,
+    /// Contacts whose report request failed, with the error it failed with.
+    pub failed: Vec<(Uuid, XeroError)>,
+}
+
+impl ReportsResource<'_> {
+    /// Retrieves the Aged Receivables report of every contact that owes money.
+    ///
+    /// Xero only produces this report for one contact at a time. The contacts
+    /// are found from authorised sales invoices with an amount due, then their
+    /// reports are requested at most five at a time to stay within Xero's
+    /// concurrent request limit. A contact whose report fails is listed in
+    /// [`AllContactsAgedReceivables::failed`] without losing the others; only
+    /// a failure to list the invoices fails the call.
+    ///
+    /// The contacts are chosen by what they owe today, whatever `date` is:
+    /// Xero's invoice filter only sees the current `AmountDue`. A contact that
+    /// owed money on an earlier `date` but has since paid in full is left out.
+    pub async fn aged_receivables_all_contacts(
+        &self,
+        date: Option<NaiveDate>,
+    ) -> Result<AllContactsAgedReceivables, XeroError> {
+        let invoices = self
+            .api
+            .invoices()
+            .list()
+            .where_filter("Type==\"ACCREC\" AND AmountDue>0")
+            .statuses(["AUTHORISED"])
+            .summary_only(true)
+            .pager()
+            .collect_all()
+            .await?;
+
+        let outcomes = fan_out(
+            contacts_owing(&invoices),
+            PER_TENANT_FAN_OUT,
+            |(contact_id, _)| {
+                let api = self.api.clone();
+                async move {
+                    api.reports()
+                        .aged_receivables_by_contact_report(Some(contact_id), date, None, None)
+                        .await
+                }
+            },
+        )
+        .await;
+
+        let mut all = AllContactsAgedReceivables::default();
+        for ((contact_id, contact_name), result) in outcomes {
+            match result {
+                Ok(report) => all.reports.push(ContactAgedReceivables {
+                    contact_id,
+                    contact_name,
+                    report,
+                }),
+                Err(err) => all.failed.push((contact_id, err)),
+            }
+        }
+        Ok(all)
+    }
+}
+
+/// The distinct contacts with an amount due on `invoices`, in first-seen order.
+fn contacts_owing(invoices: &[invoice::Invoice]) -> Vec<(Uuid, String)> {
+    let mut seen = HashSet::new();
+    invoices
+        .iter()
+        .filter(|invoice| invoice.amount_due.is_some_and(|due| due > Money::default()))
+        .filter_map(|invoice| {
+            let contact_id = invoice.contact.contact_id?;
+            seen.insert(contact_id)
+                .then(|| (contact_id, invoice.contact.name.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::accounting::contact::Contact;
+    use crate::test_support::{self, MockServer};
+
+    fn invoice(contact_id: Uuid, name: &str, amount_due: Option<Money>) -> invoice::Invoice {
+        invoice::Invoice {
+            contact: Contact {
+                contact_id: Some(contact_id),
+                name: name.to_string(),
+                ..Default::default()
+            },
+            amount_due,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn contacts_owing_are_distinct_and_in_first_seen_order() {
+        let acme = Uuid::new_v4();
+        let globex = Uuid::new_v4();
+        let paid_up = Uuid::new_v4();
+        let invoices = vec![
+            invoice(globex, "Globex", Some(Money::from(120))),
+            invoice(paid_up, "Paid Up Ltd", Some(Money::default())),
+            invoice(acme, "ACME", Some(Money::from(40))),
+            invoice(globex, "Globex", Some(Money::from(5))),
+            invoice(paid_up, "Paid Up Ltd", None),
+        ];
+
+        assert_eq!(
+            contacts_owing(&invoices),
+            vec![(globex, "Globex".to_string()), (acme, "ACME".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_contacts_are_reported_alongside_the_rest() {
+        let acme = Uuid::from_u128(10);
+        let globex = Uuid::from_u128(20);
+        let owing = |contact_id: Uuid, name: &str| {
+            serde_json::json!({
+                "Type": "ACCREC",
+                "Contact": { "ContactID": contact_id, "Name": name },
+                "Date": "/Date(1700000000000+0000)/",
+                "AmountDue": 50.0,
+                "LineItems": []
+            })
+        };
+        let invoices =
+            serde_json::json!({ "Invoices": [owing(acme, "ACME"), owing(globex, "Globex")] })
+                .to_string();
+        let report = serde_json::json!({ "Reports": [{
+            "ReportName": "Aged Receivables By Contact",
+            "ReportType": "AgedReceivablesByContact",
+            "ReportDate": "31 January 2024",
+            "Rows": []
+        }]})
+        .to_string();
+
+        // The two report requests race, so answer by contact, not by order.
+        let server = MockServer::respond_with(3, move |request| {
+            let response = if request.starts_with("GET /Invoices") {
+                test_support::response("200 OK", &invoices)
+            } else if request.contains(&format!("contactID={acme}")) {
+                test_support::response("200 OK", &report)
+            } else {
+                test_support::response("404 Not Found", "{}")
+            };
+            async move { response }
+        })
+        .await;
+        let api = test_support::accounting_api(&server).await;
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 31);
+        let all = api
+            .reports()
+            .aged_receivables_all_contacts(date)
+            .await
+            .unwrap();
+
+        assert_eq!(all.reports.len(), 1);
+        assert_eq!(all.reports[0].contact_id, acme);
+        assert_eq!(all.reports[0].contact_name, "ACME");
+        assert_eq!(all.failed.len(), 1);
+        assert_eq!(all.failed[0].0, globex);
+
+        // Contacts are picked by today's balance even for a past `date`.
+        let paths = server.paths().await;
+        assert!(paths[0].contains("AmountDue%3E0"), "{}", paths[0]);
+        assert!(paths[1..]
+            .iter()
+            .all(|path| path.contains("date=2024-01-31")));
+    }
+}
@@ -4,6 +4,7 @@ use crate::error::XeroError;
 use crate::models::accounting::report;
 use chrono::NaiveDate;
 use reqwest::Method;
+use std::time::Duration;
 use uuid::Uuid;
 
 impl<'a> ReportsResource<'a> {
@@ -123,6 +124,34 @@ impl super::super::AccountingApi {
         self.reports().get(report_name, params).await
     }
 
+    /// Retrieves a report with typed parameters, see [`super::ReportParams`].
+    pub async fn get_report_with_params(
+        &self,
+        report_name: &str,
+        params: super::ReportParams,
+    ) -> Result<report::Report, XeroError> {
+        self.reports().get_with_params(report_name, params).await
+    }
+
+    /// Retrieves a report, reusing a cached copy fetched less than `ttl` ago.
+    pub async fn get_report_cached(
+        &self,
+        report_name: &str,
+        params: Vec<(&str, &str)>,
+        ttl: Duration,
+    ) -> Result<report::Report, XeroError> {
+        let params = params
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.reports().get_cached(report_name, params, ttl).await
+    }
+
+    /// Drops all cached reports for this tenant.
+    pub fn clear_report_cache(&self) {
+        self.reports().clear_cache();
+    }
+
     /// Retrieves the Bank Summary report.
     pub async fn get_bank_summary(
         &self,
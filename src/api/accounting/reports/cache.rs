@@ -0,0 +1,180 @@
+//! In-memory TTL cache for report responses.
+//!
+//! Reports are expensive to generate and count against the rate limit, but
+//! change slowly. The cache is shared by every [`AccountingApi`] created from
+//! the same [`crate::client::XeroClient`] and keyed by tenant, report name and
+//! query parameters, so dashboards that poll the same report reuse one response
+//! until it expires.
+//!
+//! [`AccountingApi`]: super::super::AccountingApi
+
+use crate::models::accounting::report;
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReportCacheKey {
+    tenant_id: Uuid,
+    report_name: String,
+    params: Vec<(String, String)>,
+}
+
+impl ReportCacheKey {
+    fn new(tenant_id: Uuid, report_name: &str, params: &[(String, String)]) -> Self {
+        // Parameter order does not change the report, so normalise it.
+        let mut params = params.to_vec();
+        params.sort();
+        Self {
+            tenant_id,
+            report_name: report_name.to_string(),
+            params,
+        }
+    }
+}
+
+/// A fetched report, when it was fetched and how long its caller keeps it.
+#[derive(Debug)]
+struct CachedReport {
+    stored_at: Instant,
+    ttl: Duration,
+    report: report::Report,
+}
+
+impl CachedReport {
+    fn is_stale(&self, ttl: Duration) -> bool {
+        self.stored_at.elapsed() >= ttl
+    }
+}
+
+/// Shared store of fetched reports and when they were fetched.
+#[derive(Debug, Default)]
+pub(crate) struct ReportCache {
+    entries: DashMap<ReportCacheKey, CachedReport>,
+}
+
+impl ReportCache {
+    /// Returns the cached report if it was stored less than `ttl` ago, and
+    /// evicts it otherwise.
+    pub(crate) fn get(
+        &self,
+        tenant_id: Uuid,
+        report_name: &str,
+        params: &[(String, String)],
+        ttl: Duration,
+    ) -> Option<report::Report> {
+        let key = ReportCacheKey::new(tenant_id, report_name, params);
+        if let Some(entry) = self.entries.get(&key) {
+            if !entry.is_stale(ttl) {
+                return Some(entry.report.clone());
+            }
+        }
+        self.entries.remove_if(&key, |_, entry| entry.is_stale(ttl));
+        None
+    }
+
+    /// Stores a freshly fetched report to be kept for `ttl`, replacing any
+    /// previous entry and evicting every other report past its own TTL.
+    pub(crate) fn insert(
+        &self,
+        tenant_id: Uuid,
+        report_name: &str,
+        params: &[(String, String)],
+        ttl: Duration,
+        report: report::Report,
+    ) {
+        self.entries.retain(|_, entry| !entry.is_stale(entry.ttl));
+        let key = ReportCacheKey::new(tenant_id, report_name, params);
+        let entry = CachedReport {
+            stored_at: Instant::now(),
+            ttl,
+            report,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    /// Drops every cached report for `tenant_id`.
+    pub(crate) fn clear_tenant(&self, tenant_id: Uuid) {
+        self.entries.retain(|key, _| key.tenant_id != tenant_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(name: &str) -> report::Report {
+        serde_json::from_value(serde_json::json!({
+            "ReportName": name,
+            "ReportDate": "1 March 2024"
+        }))
+        .unwrap()
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn hit_ignores_param_order_and_respects_tenant() {
+        let cache = ReportCache::default();
+        let tenant = Uuid::new_v4();
+        let ttl = Duration::from_secs(60);
+        cache.insert(
+            tenant,
+            "BalanceSheet",
+            &params(&[("date", "2024-03-01"), ("periods", "3")]),
+            ttl,
+            report("Balance Sheet"),
+        );
+
+        let reordered = params(&[("periods", "3"), ("date", "2024-03-01")]);
+        assert!(cache.get(tenant, "BalanceSheet", &reordered, ttl).is_some());
+        assert!(cache
+            .get(Uuid::new_v4(), "BalanceSheet", &reordered, ttl)
+            .is_none());
+        assert!(cache
+            .get(tenant, "BalanceSheet", &params(&[("periods", "3")]), ttl)
+            .is_none());
+    }
+
+    #[test]
+    fn expired_entries_and_cleared_tenants_miss() {
+        let cache = ReportCache::default();
+        let tenant = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let ttl = Duration::from_secs(60);
+        cache.insert(tenant, "TrialBalance", &[], ttl, report("Trial Balance"));
+        cache.insert(other, "TrialBalance", &[], ttl, report("Trial Balance"));
+
+        assert!(cache
+            .get(tenant, "TrialBalance", &[], Duration::ZERO)
+            .is_none());
+        assert_eq!(cache.entries.len(), 1, "the stale entry is evicted");
+
+        cache.insert(tenant, "TrialBalance", &[], ttl, report("Trial Balance"));
+        cache.clear_tenant(tenant);
+        assert!(cache.get(tenant, "TrialBalance", &[], ttl).is_none());
+        assert!(cache.get(other, "TrialBalance", &[], ttl).is_some());
+    }
+
+    #[test]
+    fn insert_evicts_reports_past_their_own_ttl() {
+        let cache = ReportCache::default();
+        let tenant = Uuid::new_v4();
+        let ttl = Duration::from_secs(60);
+        cache.insert(
+            tenant,
+            "BankSummary",
+            &[],
+            Duration::ZERO,
+            report("Bank Summary"),
+        );
+        cache.insert(tenant, "TrialBalance", &[], ttl, report("Trial Balance"));
+        assert_eq!(cache.entries.len(), 1);
+        assert!(cache.get(tenant, "TrialBalance", &[], ttl).is_some());
+    }
+}
@@ -2,11 +2,18 @@ use super::AccountingApi;
 use crate::error::XeroError;
 use crate::models::accounting::report;
 use reqwest::Method;
+use std::time::Duration;
 
+mod aged;
 mod base;
+pub(crate) mod cache;
+mod params;
 mod typed_api;
 mod typed_reports;
 
+pub use aged::{AllContactsAgedReceivables, ContactAgedReceivables};
+pub use params::ReportParams;
+
 /// Resource accessor for Reports.
 #[derive(Debug, Clone, Copy)]
 pub struct ReportsResource<'a> {
@@ -38,4 +45,31 @@ impl<'a> ReportsResource<'a> {
                 message: "Report not found in response".to_string(),
             })
     }
+
+    /// Retrieves a report, reusing a cached copy fetched less than `ttl` ago.
+    ///
+    /// The cache is shared by all Accounting handles created from the same
+    /// client and keyed by tenant, report name and parameters.
+    pub async fn get_cached(
+        &self,
+        report_name: &str,
+        params: Vec<(String, String)>,
+        ttl: Duration,
+    ) -> Result<report::Report, XeroError> {
+        let tenant_id = self.api.client.tenant_id();
+        let cache = &self.api.report_cache;
+        if let Some(report) = cache.get(tenant_id, report_name, &params, ttl) {
+            return Ok(report);
+        }
+        let report = self.get(report_name, params.clone()).await?;
+        cache.insert(tenant_id, report_name, &params, ttl, report.clone());
+        Ok(report)
+    }
+
+    /// Drops all cached reports for this tenant.
+    pub fn clear_cache(&self) {
+        self.api
+            .report_cache
+            .clear_tenant(self.api.client.tenant_id());
+    }
 }
@@ -0,0 +1,190 @@
+use super::super::query::QueryParams;
+use super::ReportsResource;
+use crate::error::XeroError;
+use crate::models::accounting::report;
+use chrono::NaiveDate;
+
+/// Reports that accept `standardLayout`.
+const STANDARD_LAYOUT_REPORTS: &[&str] = &["BalanceSheet", "ProfitAndLoss"];
+/// Reports that accept `paymentsOnly`.
+const PAYMENTS_ONLY_REPORTS: &[&str] = &["BalanceSheet", "ProfitAndLoss", "TrialBalance"];
+
+/// Query parameters for [`ReportsResource::get_with_params`].
+///
+/// Xero silently ignores parameters a report does not understand, so the
+/// `standardLayout` and `paymentsOnly` toggles are checked against the reports
+/// that honour them before the request is sent.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[must_use]
+pub struct ReportParams {
+    date: Option<NaiveDate>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    periods: Option<u32>,
+    timeframe: Option<String>,
+    standard_layout: Option<bool>,
+    payments_only: Option<bool>,
+    extra: Vec<(String, String)>,
+}
+
+impl ReportParams {
+    /// Creates an empty parameter set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The "as at" date of point-in-time reports such as the Balance Sheet.
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// The start of the reporting period.
+    pub fn from_date(mut self, from_date: NaiveDate) -> Self {
+        self.from_date = Some(from_date);
+        self
+    }
+
+    /// The end of the reporting period.
+    pub fn to_date(mut self, to_date: NaiveDate) -> Self {
+        self.to_date = Some(to_date);
+        self
+    }
+
+    /// The number of comparison periods.
+    pub fn periods(mut self, periods: u32) -> Self {
+        self.periods = Some(periods);
+        self
+    }
+
+    /// The length of each comparison period: `MONTH`, `QUARTER` or `YEAR`.
+    pub fn timeframe(mut self, timeframe: impl Into<String>) -> Self {
+        self.timeframe = Some(timeframe.into());
+        self
+    }
+
+    /// Uses Xero's standard layout instead of the organisation's customised
+    /// one. Only the Balance Sheet and Profit and Loss support this.
+    pub fn standard_layout(mut self, standard_layout: bool) -> Self {
+        self.standard_layout = Some(standard_layout);
+        self
+    }
+
+    /// Reports on a cash basis, including only cash transactions. Only the
+    /// Balance Sheet, Profit and Loss and Trial Balance support this.
+    pub fn payments_only(mut self, payments_only: bool) -> Self {
+        self.payments_only = Some(payments_only);
+        self
+    }
+
+    /// Adds any other query parameter, such as `trackingCategoryID`.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra.push((key.into(), value.into()));
+        self
+    }
+
+    /// Builds the query for `report_name`, or [`XeroError::Validation`] if a
+    /// toggle is set that the report does not support.
+    pub(crate) fn to_query(&self, report_name: &str) -> Result<Vec<(String, String)>, XeroError> {
+        check_supported(
+            "standardLayout",
+            self.standard_layout,
+            STANDARD_LAYOUT_REPORTS,
+            report_name,
+        )?;
+        check_supported(
+            "paymentsOnly",
+            self.payments_only,
+            PAYMENTS_ONLY_REPORTS,
+            report_name,
+        )?;
+
+        let mut query = QueryParams::default();
+        query.push_opt("date", self.date.map(|d| d.format("%Y-%m-%d")));
+        query.push_opt("fromDate", self.from_date.map(|d| d.format("%Y-%m-%d")));
+        query.push_opt("toDate", self.to_date.map(|d| d.format("%Y-%m-%d")));
+        query.push_opt("periods", self.periods);
+        query.push_opt_string("timeframe", self.timeframe.clone());
+        query.push_opt("standardLayout", self.standard_layout);
+        query.push_opt("paymentsOnly", self.payments_only);
+        let mut params = query.as_slice().unwrap_or_default().to_vec();
+        params.extend(self.extra.iter().cloned());
+        Ok(params)
+    }
+}
+
+fn check_supported(
+    name: &str,
+    value: Option<bool>,
+    supported: &[&str],
+    report_name: &str,
+) -> Result<(), XeroError> {
+    if value.is_some() && !supported.contains(&report_name) {
+        return Err(XeroError::Validation(format!(
+            "{report_name} does not support {name}; it applies to {}",
+            supported.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+impl ReportsResource<'_> {
+    /// Retrieves a report by name with typed [`ReportParams`].
+    pub async fn get_with_params(
+        &self,
+        report_name: &str,
+        params: ReportParams,
+    ) -> Result<report::Report, XeroError> {
+        let query = params.to_query(report_name)?;
+        self.get(report_name, query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggles_serialize_as_query_flags() {
+        let params = ReportParams::new()
+            .from_date(NaiveDate::from_ymd_opt(2024, 4, 1).unwrap())
+            .to_date(NaiveDate::from_ymd_opt(2025, 3, 31).unwrap())
+            .standard_layout(true)
+            .payments_only(false)
+            .param("trackingCategoryID", "abc");
+        let query = params.to_query("ProfitAndLoss").unwrap();
+        let query: Vec<(&str, &str)> = query
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            query,
+            vec![
+                ("fromDate", "2024-04-01"),
+                ("toDate", "2025-03-31"),
+                ("standardLayout", "true"),
+                ("paymentsOnly", "false"),
+                ("trackingCategoryID", "abc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_toggles_are_rejected() {
+        let layout = ReportParams::new().standard_layout(true);
+        assert!(layout.to_query("BalanceSheet").is_ok());
+        assert!(matches!(
+            layout.to_query("TrialBalance"),
+            Err(XeroError::Validation(_))
+        ));
+
+        let cash = ReportParams::new().payments_only(true);
+        assert!(cash.to_query("TrialBalance").is_ok());
+        assert!(matches!(
+            cash.to_query("BankSummary"),
+            Err(XeroError::Validation(_))
+        ));
+
+        assert!(ReportParams::new().to_query("BankSummary").is_ok());
+    }
+}
@@ -13,6 +13,15 @@ impl super::super::AccountingApi {
         self.reports().bank_summary_report(from_date, to_date).await
     }
 
+    /// Retrieves the Bank Summary report as one row per bank account.
+    pub async fn get_bank_summary_typed(
+        &self,
+        from_date: Option<NaiveDate>,
+        to_date: Option<NaiveDate>,
+    ) -> Result<report::BankSummary, XeroError> {
+        self.reports().bank_summary_typed(from_date, to_date).await
+    }
+
     /// Retrieves the Balance Sheet report (typed).
     #[allow(clippy::too_many_arguments)]
     pub async fn get_balance_sheet_report(
@@ -51,6 +60,14 @@ impl super::super::AccountingApi {
             .await
     }
 
+    /// Retrieves the Aged Receivables report of every contact with an amount due.
+    pub async fn get_aged_receivables_all_contacts(
+        &self,
+        date: Option<NaiveDate>,
+    ) -> Result<super::AllContactsAgedReceivables, XeroError> {
+        self.reports().aged_receivables_all_contacts(date).await
+    }
+
     /// Retrieves Aged Payables by Contact (typed).
     pub async fn get_aged_payables_by_contact_report(
         &self,
@@ -189,4 +206,12 @@ impl super::super::AccountingApi {
     ) -> Result<Vec<report::TenNinetyNineReport>, XeroError> {
         self.reports().ten_ninety_nine_report(report_year).await
     }
+
+    /// Retrieves the 1099 reports for a tax year with typed vendor rows.
+    pub async fn get_1099_report(
+        &self,
+        report_year: u16,
+    ) -> Result<Vec<report::TenNinetyNine>, XeroError> {
+        self.reports().ten_ninety_nine(report_year).await
+    }
 }
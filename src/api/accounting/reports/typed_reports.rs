@@ -17,7 +17,20 @@ impl<'a> ReportsResource<'a> {
         report::BankSummaryReport::try_from_report(report).map_err(report_type_error)
     }
 
+    /// Retrieves the Bank Summary report as one row per bank account.
+    pub async fn bank_summary_typed(
+        &self,
+        from_date: Option<NaiveDate>,
+        to_date: Option<NaiveDate>,
+    ) -> Result<report::BankSummary, XeroError> {
+        Ok(self
+            .bank_summary_report(from_date, to_date)
+            .await?
+            .to_typed())
+    }
+
     /// Retrieves the Balance Sheet report (typed).
+    #[allow(clippy::too_many_arguments)]
     pub async fn balance_sheet_report(
         &self,
         date: Option<NaiveDate>,
@@ -295,6 +308,19 @@ impl<'a> ReportsResource<'a> {
             .map(|r| r.map_err(report_type_error))
             .collect()
     }
+
+    /// Retrieves the 1099 reports for a tax year with vendor rows parsed into
+    /// [`report::Vendor1099`].
+    pub async fn ten_ninety_nine(
+        &self,
+        report_year: u16,
+    ) -> Result<Vec<report::TenNinetyNine>, XeroError> {
+        self.ten_ninety_nine_report(Some(i32::from(report_year)))
+            .await?
+            .iter()
+            .map(|report| report.to_typed().map_err(XeroError::from))
+            .collect()
+    }
 }
 
 fn report_type_error(message: String) -> XeroError {
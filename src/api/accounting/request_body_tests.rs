@@ -0,0 +1,265 @@
+//! Asserts the exact JSON shape sent by the Accounting create/update methods.
+//!
+//! Xero expects a bare object when creating a single record and a plural
+//! wrapper (`{"Invoices": [...]}`) when creating several; both paths go
+//! through [`single_or_wrapped`], so these tests pin the key names, wrapping
+//! and PascalCase casing for representative payloads.
+
+use crate::models::accounting::common::{LineItem, Money};
+use crate::models::accounting::{
+    account, bank_transaction, contact, credit_note, employee, invoice, item, payment,
+    purchase_order,
+};
+use crate::test_support::{self, MockServer};
+use crate::util::single_or_wrapped;
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+fn contact_ref(contact_id: Uuid) -> contact::Contact {
+    contact::Contact {
+        contact_id: Some(contact_id),
+        name: "ACME Ltd".to_string(),
+        ..Default::default()
+    }
+}
+
+fn sample_invoice(contact_id: Uuid) -> invoice::Invoice {
+    invoice::Invoice {
+        contact: contact_ref(contact_id),
+        date: Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap(),
+        status: Some(invoice::InvoiceStatus::Authorised),
+        line_items: vec![LineItem {
+            description: Some("Widget".to_string()),
+            quantity: Some(2.0),
+            unit_amount: Some(Money::from(10)),
+            account_code: Some("200".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn single_invoice_is_sent_as_bare_object() {
+    let contact_id = Uuid::nil();
+    let body = single_or_wrapped(vec![sample_invoice(contact_id)], |invoices| {
+        invoice::InvoicesRequest { invoices }
+    })
+    .unwrap();
+
+    assert_eq!(
+        body,
+        json!({
+            "Type": "ACCREC",
+            "Contact": { "ContactID": contact_id, "Name": "ACME Ltd" },
+            "LineItems": [{
+                "Description": "Widget",
+                "Quantity": 2.0,
+                "UnitAmount": 10.0,
+                "AccountCode": "200"
+            }],
+            "Date": "/Date(1700000000000)/",
+            "Status": "AUTHORISED"
+        })
+    );
+}
+
+/// The request line and JSON body of a raw request captured by [`MockServer`].
+fn sent_body(request: &str) -> (&str, serde_json::Value) {
+    let (head, body) = request.split_once("\r\n\r\n").unwrap();
+    let line = head.lines().next().unwrap();
+    (line, serde_json::from_str(body).unwrap())
+}
+
+#[tokio::test]
+async fn create_methods_send_the_shaped_body() {
+    let server = MockServer::ok([r#"{"Invoices":[]}"#, r#"{"Contacts":[]}"#]).await;
+    let api = test_support::accounting_api(&server).await;
+
+    api.invoices()
+        .create(vec![sample_invoice(Uuid::nil())], None)
+        .await
+        .unwrap();
+    api.contacts()
+        .create(vec![contact_ref(Uuid::nil()), contact_ref(Uuid::nil())])
+        .await
+        .unwrap();
+
+    let requests = server.requests().await;
+    let (line, body) = sent_body(&requests[0]);
+    assert!(line.starts_with("PUT /Invoices"), "{line}");
+    assert_eq!(body["Type"], "ACCREC");
+    assert_eq!(body["LineItems"][0]["AccountCode"], "200");
+    assert!(
+        body.get("Invoices").is_none(),
+        "a single invoice is not wrapped"
+    );
+
+    let (line, body) = sent_body(&requests[1]);
+    assert!(line.starts_with("PUT /Contacts"), "{line}");
+    assert_eq!(body["Contacts"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn multiple_invoices_are_wrapped() {
+    let invoices = vec![sample_invoice(Uuid::nil()), sample_invoice(Uuid::nil())];
+    let body =
+        single_or_wrapped(invoices, |invoices| invoice::InvoicesRequest { invoices }).unwrap();
+
+    let wrapped = body["Invoices"].as_array().expect("Invoices array");
+    assert_eq!(wrapped.len(), 2);
+    assert_eq!(wrapped[0]["Type"], "ACCREC");
+    assert_eq!(body.as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn contacts_wrap_under_contacts_key() {
+    let single = single_or_wrapped(vec![contact_ref(Uuid::nil())], |contacts| {
+        contact::ContactsRequest { contacts }
+    })
+    .unwrap();
+    assert_eq!(
+        single,
+        json!({ "ContactID": Uuid::nil(), "Name": "ACME Ltd" })
+    );
+
+    let many = single_or_wrapped(
+        vec![contact_ref(Uuid::nil()), contact_ref(Uuid::nil())],
+        |contacts| contact::ContactsRequest { contacts },
+    )
+    .unwrap();
+    assert_eq!(many["Contacts"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn item_uses_id_and_cogs_renames() {
+    let new_item = item::Item {
+        item_id: None,
+        code: "SKU-1".to_string(),
+        name: Some("Widget".to_string()),
+        is_sold: None,
+        is_purchased: None,
+        description: None,
+        purchase_description: None,
+        purchase_details: Some(item::ItemDetails {
+            unit_price: Some(4.5),
+            account_code: None,
+            cogs_account_code: Some("310".to_string()),
+            tax_type: None,
+        }),
+        sales_details: None,
+        is_tracked_as_inventory: None,
+        inventory_asset_account_code: None,
+        total_cost_pool: None,
+        quantity_on_hand: None,
+        updated_date_utc: None,
+    };
+    let body = single_or_wrapped(vec![new_item], |items| item::ItemsRequest { items }).unwrap();
+
+    assert_eq!(
+        body,
+        json!({
+            "Code": "SKU-1",
+            "Name": "Widget",
+            "PurchaseDetails": { "UnitPrice": 4.5, "COGSAccountCode": "310" }
+        })
+    );
+}
+
+#[test]
+fn account_serializes_type_key() {
+    let new_account = account::Account {
+        code: Some("201".to_string()),
+        name: Some("Sales - Online".to_string()),
+        account_type: Some(account::AccountType::Revenue),
+        ..Default::default()
+    };
+    let body = single_or_wrapped(vec![new_account], |accounts| account::AccountsRequest {
+        accounts,
+    })
+    .unwrap();
+
+    assert_eq!(
+        body,
+        json!({ "Code": "201", "Name": "Sales - Online", "Type": "REVENUE" })
+    );
+}
+
+#[test]
+fn employee_and_purchase_order_shapes() {
+    let new_employee = employee::Employee {
+        employee_id: None,
+        status: None,
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+        external_link: None,
+        updated_date_utc: None,
+    };
+    let body = single_or_wrapped(vec![new_employee.clone(), new_employee], |employees| {
+        employee::EmployeesRequest { employees }
+    })
+    .unwrap();
+    assert_eq!(
+        body,
+        json!({ "Employees": [
+            { "FirstName": "Ada", "LastName": "Lovelace" },
+            { "FirstName": "Ada", "LastName": "Lovelace" }
+        ]})
+    );
+
+    let order: purchase_order::PurchaseOrder = serde_json::from_value(json!({
+        "Contact": { "Name": "Supplier" },
+        "LineItems": [],
+        "Date": null
+    }))
+    .unwrap();
+    let body = single_or_wrapped(vec![order], |purchase_orders| {
+        purchase_order::PurchaseOrdersRequest { purchase_orders }
+    })
+    .unwrap();
+    assert_eq!(
+        body,
+        json!({ "Contact": { "Name": "Supplier" }, "LineItems": [] })
+    );
+}
+
+#[test]
+fn plural_wrapper_keys() {
+    let credit_notes = single_or_wrapped(
+        vec![credit_note::CreditNote::default(); 2],
+        |credit_notes| credit_note::CreditNotesRequest { credit_notes },
+    )
+    .unwrap();
+    assert!(credit_notes.get("CreditNotes").is_some());
+
+    let bank_transactions = single_or_wrapped(
+        vec![bank_transaction::BankTransaction::default(); 2],
+        |bank_transactions| bank_transaction::BankTransactionsRequest { bank_transactions },
+    )
+    .unwrap();
+    assert!(bank_transactions.get("BankTransactions").is_some());
+
+    let payments = single_or_wrapped(vec![payment::Payment::default(); 2], |payments| {
+        payment::PaymentsRequest {
+            payments: Some(payments),
+        }
+    })
+    .unwrap();
+    assert!(payments.get("Payments").is_some());
+}
+
+#[test]
+fn status_updates_send_only_the_status() {
+    let body = serde_json::to_value(super::StatusUpdate {
+        status: purchase_order::PurchaseOrderStatus::Deleted,
+    })
+    .unwrap();
+    assert_eq!(body, json!({ "Status": "DELETED" }));
+
+    let body = serde_json::to_value(super::StatusUpdate {
+        status: invoice::InvoiceStatus::Voided,
+    })
+    .unwrap();
+    assert_eq!(body, json!({ "Status": "VOIDED" }));
+}
@@ -1,3 +1,4 @@
+use super::filter::WhereBuilder;
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
@@ -31,8 +32,47 @@ impl<'a> TaxRatesResource<'a> {
             .client
             .send_request(Method::PUT, "/TaxRates", None, Some(body))
             .await?;
+        self.clear_cache();
         Ok(resp.tax_rates)
     }
+
+    /// Resolves a `TaxType` code (e.g. `"OUTPUT"`) to its tax rate.
+    ///
+    /// Each code is fetched with a `where` filter on its first lookup for the
+    /// tenant and cached on the client for an hour; tax rate writes made
+    /// through this crate clear it. Returns `Ok(None)` if no tax rate has the
+    /// code; misses are not cached, so a rate added later is found.
+    pub async fn by_type(&self, tax_type: &str) -> Result<Option<tax_rate::TaxRate>, XeroError> {
+        let tenant_id = self.api.client.tenant_id();
+        if let Some(tax_rate) = self
+            .api
+            .tax_rate_cache
+            .get(tenant_id, &tax_type.to_string())
+        {
+            return Ok(Some(tax_rate));
+        }
+        let filter = WhereBuilder::new().eq("TaxType", tax_type).build();
+        let tax_rate = self
+            .list()
+            .where_filter(filter)
+            .send()
+            .await?
+            .into_iter()
+            .find(|rate| rate.tax_type.as_deref() == Some(tax_type));
+        if let Some(tax_rate) = &tax_rate {
+            self.api
+                .tax_rate_cache
+                .insert(tenant_id, tax_type.to_string(), tax_rate.clone());
+        }
+        Ok(tax_rate)
+    }
+
+    /// Drops this tenant's cached tax rates used by [`Self::by_type`].
+    pub fn clear_cache(&self) {
+        self.api
+            .tax_rate_cache
+            .clear_tenant(self.api.client.tenant_id());
+    }
 }
 
 /// Builder for Tax Rates list requests.
@@ -96,6 +136,14 @@ impl AccountingApi {
         request.send().await
     }
 
+    /// Resolves a `TaxType` code to its tax rate. See [`TaxRatesResource::by_type`].
+    pub async fn get_tax_rate_by_type(
+        &self,
+        tax_type: &str,
+    ) -> Result<Option<tax_rate::TaxRate>, XeroError> {
+        self.tax_rates().by_type(tax_type).await
+    }
+
     /// Creates or updates a tax rate.
     pub async fn create_or_update_tax_rate(
         &self,
@@ -104,3 +152,24 @@ impl AccountingApi {
         self.tax_rates().create_or_update(vec![tax_rate]).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{self, MockServer};
+
+    #[tokio::test]
+    async fn by_type_refetches_misses_and_caches_hits() {
+        let found =
+            r#"{"TaxRates":[{"Name":"GST on Income","TaxType":"OUTPUT2","TaxComponents":[]}]}"#;
+        let server = MockServer::ok([r#"{"TaxRates":[]}"#, found]).await;
+        let api = test_support::accounting_api(&server).await;
+
+        assert!(api.get_tax_rate_by_type("OUTPUT2").await.unwrap().is_none());
+        let rate = api.get_tax_rate_by_type("OUTPUT2").await.unwrap().unwrap();
+        assert_eq!(rate.name, "GST on Income");
+        // Served from the cache; the server has no responses left.
+        let cached = api.get_tax_rate_by_type("OUTPUT2").await.unwrap().unwrap();
+        assert_eq!(cached.name, rate.name);
+        assert_eq!(server.paths().await.len(), 2);
+    }
+}
@@ -0,0 +1,97 @@
+//! Per-tenant lookup cache with a time-to-live.
+//!
+//! Backs the lookups that resolve one field of rarely-changing reference data
+//! (account codes, tax rates, the base currency) without a request each time.
+//! The caches hang off [`crate::client::XeroClient`], so every handle created
+//! from one client shares them. Entries older than the TTL are treated as
+//! missing and dropped when next touched, which bounds how long a change made
+//! outside this client can go unseen.
+
+use crate::models::accounting::tax_rate::TaxRate;
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Each tenant's account codes mapped to their `AccountID`s, loaded together
+/// from the chart of accounts.
+pub(crate) type AccountCodeCache = TenantCache<(), Arc<HashMap<String, Uuid>>>;
+pub(crate) const ACCOUNT_CODES_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Tax rates by `TaxType` code.
+pub(crate) type TaxRateCache = TenantCache<String, TaxRate>;
+pub(crate) const TAX_RATES_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Each tenant's base currency code, which is fixed once the organisation
+/// exists.
+pub(crate) type BaseCurrencyCache = TenantCache<(), String>;
+pub(crate) const BASE_CURRENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Values keyed by tenant and `K`, each kept for at most `ttl`.
+#[derive(Debug)]
+pub(crate) struct TenantCache<K: Eq + Hash, V> {
+    ttl: Duration,
+    entries: DashMap<(Uuid, K), (Instant, V)>,
+}
+
+impl<K, V> TenantCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Returns the value stored for `key`, unless it is older than the TTL.
+    pub(crate) fn get(&self, tenant_id: Uuid, key: &K) -> Option<V> {
+        let key = (tenant_id, key.clone());
+        if let Some(entry) = self.entries.get(&key) {
+            let (stored_at, value) = entry.value();
+            if stored_at.elapsed() < self.ttl {
+                return Some(value.clone());
+            }
+        }
+        self.entries
+            .remove_if(&key, |_, (stored_at, _)| stored_at.elapsed() >= self.ttl);
+        None
+    }
+
+    /// Stores `value` for `key`, replacing any previous entry.
+    pub(crate) fn insert(&self, tenant_id: Uuid, key: K, value: V) {
+        self.entries
+            .insert((tenant_id, key), (Instant::now(), value));
+    }
+
+    /// Drops every entry for `tenant_id`.
+    pub(crate) fn clear_tenant(&self, tenant_id: Uuid) {
+        self.entries.retain(|(tenant, _), _| *tenant != tenant_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_per_tenant_and_expire() {
+        let cache = TenantCache::new(Duration::from_secs(60));
+        let tenant_id = Uuid::new_v4();
+        cache.insert(tenant_id, "OUTPUT".to_string(), 15);
+        assert_eq!(cache.get(tenant_id, &"OUTPUT".to_string()), Some(15));
+        assert_eq!(cache.get(Uuid::new_v4(), &"OUTPUT".to_string()), None);
+
+        cache.clear_tenant(tenant_id);
+        assert_eq!(cache.get(tenant_id, &"OUTPUT".to_string()), None);
+
+        let expired = TenantCache::new(Duration::ZERO);
+        expired.insert(tenant_id, (), "NZD");
+        assert_eq!(expired.get(tenant_id, &()), None);
+        assert!(expired.entries.is_empty(), "stale entry is evicted");
+    }
+}
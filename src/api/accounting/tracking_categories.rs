@@ -1,11 +1,21 @@
 use super::query::QueryParams;
 use super::AccountingApi;
 use crate::error::XeroError;
-use crate::models::accounting::tracking_category;
+use crate::models::accounting::tracking_category::{self, TrackingCategoryStatus};
 use reqwest::Method;
 use serde::Serialize;
 use uuid::Uuid;
 
+/// Request body shared by the category and option update endpoints.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct TrackingUpdateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<TrackingCategoryStatus>,
+}
+
 /// Resource accessor for Tracking Categories.
 #[derive(Debug, Clone, Copy)]
 pub struct TrackingCategoriesResource<'a> {
@@ -36,6 +46,18 @@ impl<'a> TrackingCategoriesResource<'a> {
         Ok(resp.tracking_categories)
     }
 
+    /// Finds an active tracking category by name, ignoring case and surrounding whitespace.
+    ///
+    /// The returned category includes its options, so option IDs can be
+    /// resolved from their names as well.
+    pub async fn find_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<tracking_category::TrackingCategory>, XeroError> {
+        let categories = self.list().send().await?;
+        Ok(find_named(categories, name))
+    }
+
     /// Creates a new tracking category.
     pub async fn create(
         &self,
@@ -49,19 +71,42 @@ impl<'a> TrackingCategoriesResource<'a> {
         Ok(resp.tracking_categories)
     }
 
-    /// Updates a tracking category name.
+    /// Updates a tracking category name and, optionally, its status.
     pub async fn update(
         &self,
         category_id: Uuid,
         name: String,
+        status: Option<TrackingCategoryStatus>,
+    ) -> Result<Vec<tracking_category::TrackingCategory>, XeroError> {
+        let body = TrackingUpdateRequest {
+            name: Some(name),
+            status,
+        };
+        self.post_category_update(category_id, body).await
+    }
+
+    /// Archives a tracking category.
+    ///
+    /// Archived categories are hidden from new transactions but remain on
+    /// historical ones, unlike [`Self::delete`] which is only permitted for
+    /// unused categories.
+    pub async fn archive(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<tracking_category::TrackingCategory>, XeroError> {
+        let body = TrackingUpdateRequest {
+            name: None,
+            status: Some(TrackingCategoryStatus::Archived),
+        };
+        self.post_category_update(category_id, body).await
+    }
+
+    async fn post_category_update(
+        &self,
+        category_id: Uuid,
+        body: TrackingUpdateRequest,
     ) -> Result<Vec<tracking_category::TrackingCategory>, XeroError> {
-        #[derive(Serialize)]
-        struct UpdateRequest {
-            #[serde(rename = "Name")]
-            name: String,
-        }
         let path = format!("/TrackingCategories/{category_id}");
-        let body = UpdateRequest { name };
         let resp: tracking_category::TrackingCategoriesResponse = self
             .api
             .client
@@ -94,20 +139,65 @@ impl<'a> TrackingCategoriesResource<'a> {
         Ok(resp.options)
     }
 
-    /// Updates a tracking option.
+    /// Creates several options for a tracking category in a single request.
+    ///
+    /// Xero accepts an `Options` array on the same endpoint as
+    /// [`Self::create_option`], so setting up a category with many options
+    /// costs one call rather than one per option. An empty `options` returns
+    /// without calling Xero.
+    pub async fn create_options(
+        &self,
+        category_id: Uuid,
+        options: Vec<tracking_category::TrackingOption>,
+    ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
+        if options.is_empty() {
+            return Ok(Vec::new());
+        }
+        let path = format!("/TrackingCategories/{category_id}/Options");
+        let body = tracking_category::TrackingOptionsRequest { options };
+        let resp: tracking_category::TrackingOptionsResponse = self
+            .api
+            .client
+            .send_request(Method::PUT, &path, None, Some(body))
+            .await?;
+        Ok(resp.options)
+    }
+
+    /// Updates a tracking option name and, optionally, its status.
     pub async fn update_option(
         &self,
         category_id: Uuid,
         option_id: Uuid,
         name: String,
+        status: Option<TrackingCategoryStatus>,
+    ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
+        let body = TrackingUpdateRequest {
+            name: Some(name),
+            status,
+        };
+        self.post_option_update(category_id, option_id, body).await
+    }
+
+    /// Archives a tracking option without removing it from historical transactions.
+    pub async fn archive_option(
+        &self,
+        category_id: Uuid,
+        option_id: Uuid,
+    ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
+        let body = TrackingUpdateRequest {
+            name: None,
+            status: Some(TrackingCategoryStatus::Archived),
+        };
+        self.post_option_update(category_id, option_id, body).await
+    }
+
+    async fn post_option_update(
+        &self,
+        category_id: Uuid,
+        option_id: Uuid,
+        body: TrackingUpdateRequest,
     ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
-        #[derive(Serialize)]
-        struct UpdateRequest {
-            #[serde(rename = "Name")]
-            name: String,
-        }
         let path = format!("/TrackingCategories/{category_id}/Options/{option_id}");
-        let body = UpdateRequest { name };
         let resp: tracking_category::TrackingOptionsResponse = self
             .api
             .client
@@ -184,6 +274,17 @@ impl<'a> TrackingCategoriesListRequest<'a> {
     }
 }
 
+/// Picks the category whose name matches `name` case-insensitively.
+fn find_named(
+    categories: Vec<tracking_category::TrackingCategory>,
+    name: &str,
+) -> Option<tracking_category::TrackingCategory> {
+    let wanted = name.trim().to_lowercase();
+    categories
+        .into_iter()
+        .find(|category| category.name.trim().to_lowercase() == wanted)
+}
+
 impl AccountingApi {
     /// Retrieves tracking categories and their options.
     pub async fn get_tracking_categories(
@@ -210,6 +311,14 @@ impl AccountingApi {
         }
     }
 
+    /// Retrieves an active tracking category (with its options) by case-insensitive name.
+    pub async fn get_tracking_category_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<tracking_category::TrackingCategory>, XeroError> {
+        self.tracking_categories().find_by_name(name).await
+    }
+
     /// Creates a new tracking category.
     pub async fn create_tracking_category(
         &self,
@@ -223,8 +332,19 @@ impl AccountingApi {
         &self,
         category_id: Uuid,
         name: String,
+        status: Option<TrackingCategoryStatus>,
+    ) -> Result<Vec<tracking_category::TrackingCategory>, XeroError> {
+        self.tracking_categories()
+            .update(category_id, name, status)
+            .await
+    }
+
+    /// Archives a tracking category.
+    pub async fn archive_tracking_category(
+        &self,
+        category_id: Uuid,
     ) -> Result<Vec<tracking_category::TrackingCategory>, XeroError> {
-        self.tracking_categories().update(category_id, name).await
+        self.tracking_categories().archive(category_id).await
     }
 
     /// Deletes a tracking category.
@@ -243,15 +363,38 @@ impl AccountingApi {
             .await
     }
 
+    /// Creates several options for a tracking category in one request.
+    pub async fn create_tracking_options(
+        &self,
+        category_id: Uuid,
+        options: Vec<tracking_category::TrackingOption>,
+    ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
+        self.tracking_categories()
+            .create_options(category_id, options)
+            .await
+    }
+
     /// Updates a tracking option.
     pub async fn update_tracking_option(
         &self,
         category_id: Uuid,
         option_id: Uuid,
         name: String,
+        status: Option<TrackingCategoryStatus>,
     ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
         self.tracking_categories()
-            .update_option(category_id, option_id, name)
+            .update_option(category_id, option_id, name, status)
+            .await
+    }
+
+    /// Archives a tracking option.
+    pub async fn archive_tracking_option(
+        &self,
+        category_id: Uuid,
+        option_id: Uuid,
+    ) -> Result<Vec<tracking_category::TrackingOption>, XeroError> {
+        self.tracking_categories()
+            .archive_option(category_id, option_id)
             .await
     }
 
@@ -266,3 +409,46 @@ impl AccountingApi {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str) -> tracking_category::TrackingCategory {
+        tracking_category::TrackingCategory {
+            tracking_category_id: Some(Uuid::new_v4()),
+            name: name.to_string(),
+            status: None,
+            options: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn find_named_ignores_case_and_whitespace() {
+        let categories = vec![category("Region"), category("Département")];
+
+        let found = find_named(categories.clone(), "  REGION ");
+        assert_eq!(found.map(|c| c.name), Some("Region".to_string()));
+
+        let found = find_named(categories.clone(), "DÉPARTEMENT");
+        assert_eq!(found.map(|c| c.name), Some("Département".to_string()));
+
+        assert!(find_named(categories, "Cost Centre").is_none());
+    }
+
+    #[test]
+    fn batch_options_serialize_as_options_array() {
+        let option = |name: &str| tracking_category::TrackingOption {
+            tracking_option_id: None,
+            name: name.to_string(),
+            status: None,
+        };
+        let body = tracking_category::TrackingOptionsRequest {
+            options: vec![option("North"), option("South")],
+        };
+        assert_eq!(
+            serde_json::to_value(body).unwrap(),
+            serde_json::json!({ "Options": [{ "Name": "North" }, { "Name": "South" }] })
+        );
+    }
+}
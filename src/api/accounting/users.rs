@@ -32,6 +32,17 @@ impl<'a> UsersResource<'a> {
             .await?;
         Ok(resp.users)
     }
+
+    /// Retrieves a single user by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get_one(&self, user_id: Uuid) -> Result<Option<user::User>, XeroError> {
+        match self.get(user_id).await {
+            Ok(users) => Ok(users.into_iter().next()),
+            Err(XeroError::Api { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// Builder for Users list requests.
@@ -100,4 +111,9 @@ impl AccountingApi {
             request.send().await
         }
     }
+
+    /// Retrieves a single user by ID, returning `Ok(None)` if it does not exist.
+    pub async fn get_user(&self, user_id: Uuid) -> Result<Option<user::User>, XeroError> {
+        self.users().get_one(user_id).await
+    }
 }
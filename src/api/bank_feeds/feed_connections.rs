@@ -4,6 +4,7 @@ use crate::models::bank_feeds::feed_connection;
 use reqwest::Method;
 use uuid::Uuid;
 
+/// Resource accessor for Bank Feeds feed connections.
 #[derive(Debug, Clone, Copy)]
 pub struct FeedConnectionsResource<'a> {
     api: &'a BankFeedsApi,
@@ -14,10 +15,12 @@ impl<'a> FeedConnectionsResource<'a> {
         Self { api }
     }
 
+    /// Builds a request to list feed connections.
     pub fn list(&self) -> FeedConnectionsListRequest<'a> {
         FeedConnectionsListRequest::new(self.api)
     }
 
+    /// Retrieves a feed connection by ID.
     pub async fn get(
         &self,
         feed_connection_id: Uuid,
@@ -29,6 +32,7 @@ impl<'a> FeedConnectionsResource<'a> {
             .await
     }
 
+    /// Requests new feed connections; each result reports its own status.
     pub async fn create(
         &self,
         items: Vec<feed_connection::FeedConnectionCreate>,
@@ -40,6 +44,7 @@ impl<'a> FeedConnectionsResource<'a> {
             .await
     }
 
+    /// Requests deletion of feed connections; each result reports its own status.
     pub async fn delete(
         &self,
         items: Vec<feed_connection::FeedConnectionDeleteRequestItem>,
@@ -25,6 +25,9 @@ impl BankFeedsApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
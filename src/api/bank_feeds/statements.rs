@@ -4,6 +4,7 @@ use crate::models::bank_feeds::statement;
 use reqwest::Method;
 use uuid::Uuid;
 
+/// Resource accessor for Bank Feeds statements.
 #[derive(Debug, Clone, Copy)]
 pub struct StatementsResource<'a> {
     api: &'a BankFeedsApi,
@@ -14,10 +15,12 @@ impl<'a> StatementsResource<'a> {
         Self { api }
     }
 
+    /// Builds a request to list statements.
     pub fn list(&self) -> StatementsListRequest<'a> {
         StatementsListRequest::new(self.api)
     }
 
+    /// Retrieves a statement by ID.
     pub async fn get(&self, statement_id: Uuid) -> Result<statement::Statement, XeroError> {
         let path = format!("/Statements/{statement_id}");
         self.api
@@ -26,6 +29,7 @@ impl<'a> StatementsResource<'a> {
             .await
     }
 
+    /// Submits statements for feed connections; each result reports its own status.
     pub async fn create(
         &self,
         statements: Vec<statement::Statement>,
@@ -1,6 +1,7 @@
 use super::PayrollAuApi;
 use crate::error::XeroError;
 use crate::models::payroll_au::employee;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -32,11 +33,9 @@ impl<'a> EmployeesResource<'a> {
         &self,
         employees: Vec<employee::Employee>,
     ) -> Result<Vec<employee::Employee>, XeroError> {
-        let body = if employees.len() == 1 {
-            serde_json::to_value(employees.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(employee::EmployeesRequest { employees })?
-        };
+        let body = single_or_wrapped(employees, |employees| employee::EmployeesRequest {
+            employees,
+        })?;
         let resp: employee::EmployeesResponse = self
             .api
             .client_v1
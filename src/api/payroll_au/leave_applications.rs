@@ -1,6 +1,7 @@
 use super::PayrollAuApi;
 use crate::error::XeroError;
 use crate::models::payroll_au::leave_application;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -35,13 +36,9 @@ impl<'a> LeaveApplicationsResource<'a> {
         &self,
         leave_applications: Vec<leave_application::LeaveApplication>,
     ) -> Result<Vec<leave_application::LeaveApplication>, XeroError> {
-        let body = if leave_applications.len() == 1 {
-            serde_json::to_value(leave_applications.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(leave_application::LeaveApplicationsRequest {
-                leave_applications,
-            })?
-        };
+        let body = single_or_wrapped(leave_applications, |leave_applications| {
+            leave_application::LeaveApplicationsRequest { leave_applications }
+        })?;
         let resp: leave_application::LeaveApplicationsResponse = self
             .api
             .client_v1
@@ -36,6 +36,9 @@ impl PayrollAuApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
             client_v2: ApiClient::new(
                 BASE_URL_V2,
@@ -43,6 +46,9 @@ impl PayrollAuApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
@@ -1,6 +1,7 @@
 use super::PayrollAuApi;
 use crate::error::XeroError;
 use crate::models::payroll_au::pay_run;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -32,11 +33,7 @@ impl<'a> PayRunsResource<'a> {
         &self,
         pay_runs: Vec<pay_run::PayRun>,
     ) -> Result<Vec<pay_run::PayRun>, XeroError> {
-        let body = if pay_runs.len() == 1 {
-            serde_json::to_value(pay_runs.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(pay_run::PayRunsRequest { pay_runs })?
-        };
+        let body = single_or_wrapped(pay_runs, |pay_runs| pay_run::PayRunsRequest { pay_runs })?;
         let resp: pay_run::PayRunsResponse = self
             .api
             .client_v1
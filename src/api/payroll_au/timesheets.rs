@@ -1,6 +1,7 @@
 use super::PayrollAuApi;
 use crate::error::XeroError;
 use crate::models::payroll_au::timesheet;
+use crate::util::single_or_wrapped;
 use reqwest::Method;
 use uuid::Uuid;
 
@@ -32,11 +33,9 @@ impl<'a> TimesheetsResource<'a> {
         &self,
         timesheets: Vec<timesheet::Timesheet>,
     ) -> Result<Vec<timesheet::Timesheet>, XeroError> {
-        let body = if timesheets.len() == 1 {
-            serde_json::to_value(timesheets.into_iter().next().unwrap())?
-        } else {
-            serde_json::to_value(timesheet::TimesheetsRequest { timesheets })?
-        };
+        let body = single_or_wrapped(timesheets, |timesheets| timesheet::TimesheetsRequest {
+            timesheets,
+        })?;
         let resp: timesheet::TimesheetsResponse = self
             .api
             .client_v1
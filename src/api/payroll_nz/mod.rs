@@ -48,6 +48,9 @@ impl PayrollNzApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
@@ -50,6 +50,9 @@ impl PayrollUkApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
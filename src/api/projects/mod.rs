@@ -6,6 +6,7 @@ use crate::http::ApiClient;
 use std::sync::Arc;
 use uuid::Uuid;
 
+#[allow(clippy::module_inception)]
 pub mod projects;
 pub mod tasks;
 pub mod time_entries;
@@ -27,6 +28,9 @@ impl ProjectsApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
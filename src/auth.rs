@@ -8,6 +8,9 @@
 //!   generates a verifier/challenge pair, sends the challenge with the
 //!   authorize redirect, and presents the verifier when exchanging the code.
 //!   Construct with [`TokenManager::new_pkce`].
+//!
+//! Tokens are cached in memory. Call [`TokenManager::with_cache_path`] to also
+//! persist them as JSON on disk so they survive a restart.
 
 use crate::error::XeroError;
 use base64::Engine;
@@ -16,7 +19,9 @@ use rand::RngExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::fs;
 use tokio::sync::Mutex;
 
 const AUTHORIZE_URL: &str = "https://login.xero.com/identity/connect/authorize";
@@ -42,6 +47,142 @@ impl TokenSet {
         (self.obtained_at + chrono::Duration::seconds(self.expires_in as i64))
             < (now + chrono::Duration::seconds(60))
     }
+
+    /// When the access token expires.
+    #[must_use]
+    pub fn expires_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.obtained_at + chrono::Duration::seconds(self.expires_in as i64)
+    }
+
+    /// The scopes granted to the access token, read from its `scope` claim.
+    ///
+    /// Xero access tokens are JWTs; the signature is not checked. Empty if
+    /// the token is not a JWT or carries no scopes.
+    #[must_use]
+    pub fn granted_scopes(&self) -> Vec<String> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Scopes {
+            List(Vec<String>),
+            Spaced(String),
+        }
+        #[derive(Deserialize)]
+        struct Claims {
+            scope: Option<Scopes>,
+        }
+
+        let claims = self
+            .access_token
+            .split('.')
+            .nth(1)
+            .and_then(|payload| {
+                base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(payload.trim_end_matches('='))
+                    .ok()
+            })
+            .and_then(|json| serde_json::from_slice::<Claims>(&json).ok());
+        match claims.and_then(|c| c.scope) {
+            Some(Scopes::List(scopes)) => scopes,
+            Some(Scopes::Spaced(scopes)) => scopes.split_whitespace().map(str::to_string).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+const IDENTITY_SCOPES: &[&str] = &["openid", "profile", "email", "offline_access"];
+
+const ACCOUNTING_READ_WRITE_SCOPES: &[&str] = &[
+    "accounting.transactions",
+    "accounting.settings",
+    "accounting.contacts",
+    "accounting.attachments",
+    "accounting.reports.read",
+    "accounting.journals.read",
+    "accounting.budgets.read",
+];
+
+const ACCOUNTING_READ_ONLY_SCOPES: &[&str] = &[
+    "accounting.transactions.read",
+    "accounting.settings.read",
+    "accounting.contacts.read",
+    "accounting.attachments.read",
+    "accounting.reports.read",
+    "accounting.journals.read",
+    "accounting.budgets.read",
+];
+
+// Payroll scopes are shared by the AU, NZ and UK payroll APIs.
+const PAYROLL_SCOPES: &[&str] = &[
+    "payroll.employees",
+    "payroll.payruns",
+    "payroll.payslip",
+    "payroll.timesheets",
+    "payroll.settings",
+];
+
+const OTHER_SCOPES: &[&str] = &[
+    "accounting.reports.tenninetynine.read",
+    "files",
+    "assets",
+    "projects",
+    "bankfeeds",
+    "paymentservices",
+];
+
+/// Predefined OAuth 2.0 scope sets for [`TokenManager::get_authorization_url`].
+///
+/// Every set includes the identity scopes (`openid`, `profile`, `email`) and
+/// `offline_access` so a refresh token is issued. `bankfeeds` and
+/// `paymentservices` are restricted scopes that Xero must enable for your app.
+#[derive(Debug, Clone, Copy)]
+pub struct Scopes;
+
+impl Scopes {
+    /// Identity scopes plus `offline_access`.
+    #[must_use]
+    pub fn identity() -> Vec<&'static str> {
+        IDENTITY_SCOPES.to_vec()
+    }
+
+    /// Read/write access to Accounting transactions, settings, contacts and
+    /// attachments, with read access to reports, journals and budgets.
+    #[must_use]
+    pub fn accounting_read_write() -> Vec<&'static str> {
+        Self::with(ACCOUNTING_READ_WRITE_SCOPES)
+    }
+
+    /// Read-only access to the Accounting API.
+    #[must_use]
+    pub fn accounting_read_only() -> Vec<&'static str> {
+        Self::with(ACCOUNTING_READ_ONLY_SCOPES)
+    }
+
+    /// Access to the payroll API for the organisation's region (AU, NZ or UK).
+    #[must_use]
+    pub fn payroll() -> Vec<&'static str> {
+        Self::with(PAYROLL_SCOPES)
+    }
+
+    /// Every scope this crate has an API for, including restricted ones.
+    #[must_use]
+    pub fn all() -> Vec<&'static str> {
+        let mut scopes = Self::accounting_read_write();
+        scopes.extend_from_slice(PAYROLL_SCOPES);
+        scopes.extend_from_slice(OTHER_SCOPES);
+        scopes
+    }
+
+    /// Identity scopes plus `extra`, skipping duplicates.
+    #[must_use]
+    pub fn with(extra: &[&'static str]) -> Vec<&'static str> {
+        let mut scopes = Self::identity();
+        for scope in extra {
+            if !scopes.contains(scope) {
+                scopes.push(scope);
+            }
+        }
+        scopes
+    }
 }
 
 /// Selects how the [`TokenManager`] authenticates against the token endpoint.
@@ -74,6 +215,7 @@ pub struct TokenManager {
     redirect_uri: String,
     auth_mode: AuthMode,
     cached_token: Arc<Mutex<Option<TokenSet>>>,
+    cache_path: Option<PathBuf>,
 }
 
 impl TokenManager {
@@ -91,6 +233,7 @@ impl TokenManager {
             redirect_uri,
             auth_mode: AuthMode::Code { client_secret },
             cached_token: Arc::new(Mutex::new(None)),
+            cache_path: None,
         }
     }
 
@@ -107,9 +250,20 @@ impl TokenManager {
             redirect_uri,
             auth_mode: AuthMode::Pkce,
             cached_token: Arc::new(Mutex::new(None)),
+            cache_path: None,
         }
     }
 
+    /// Persists tokens to `path` as JSON in addition to the in-memory cache.
+    ///
+    /// Every saved or refreshed token is written to the file, and the file is
+    /// read on first use when the in-memory cache is empty.
+    #[must_use]
+    pub fn with_cache_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(path.into());
+        self
+    }
+
     /// Returns `true` if this manager is configured for the PKCE flow.
     #[must_use]
     pub fn is_pkce(&self) -> bool {
@@ -146,18 +300,23 @@ impl TokenManager {
     /// Generates the authorization URL for the standard code flow.
     #[must_use]
     pub fn get_authorization_url(&self, scopes: &[&str], state: &str) -> String {
-        let scope_str = scopes.join(" ");
-        use url::Url;
-
-        let mut url = Url::parse(AUTHORIZE_URL).unwrap();
-        url.query_pairs_mut()
-            .append_pair("response_type", "code")
-            .append_pair("client_id", &self.client_id)
-            .append_pair("redirect_uri", &self.redirect_uri)
-            .append_pair("scope", &scope_str)
-            .append_pair("state", state);
+        self.authorization_url(scopes, state, &self.redirect_uri, None)
+    }
 
-        url.to_string()
+    /// Generates the authorization URL for the standard code flow with an
+    /// explicit redirect URI instead of the configured one.
+    ///
+    /// Use this when one Xero app serves several environments. The same URI
+    /// must be passed to [`Self::exchange_code_with_redirect`], as Xero rejects
+    /// a token exchange whose redirect differs from the authorize request.
+    #[must_use]
+    pub fn get_authorization_url_with_redirect(
+        &self,
+        scopes: &[&str],
+        state: &str,
+        redirect_uri: &str,
+    ) -> String {
+        self.authorization_url(scopes, state, redirect_uri, None)
     }
 
     /// Generates the authorization URL for the PKCE flow.
@@ -171,29 +330,55 @@ impl TokenManager {
         scopes: &[&str],
         state: &str,
         code_challenge: &str,
+    ) -> String {
+        self.authorization_url(scopes, state, &self.redirect_uri, Some(code_challenge))
+    }
+
+    /// Generates the authorization URL for the PKCE flow with an explicit
+    /// redirect URI, to be paired with [`Self::exchange_code_pkce_with_redirect`].
+    #[must_use]
+    pub fn get_authorization_url_pkce_with_redirect(
+        &self,
+        scopes: &[&str],
+        state: &str,
+        code_challenge: &str,
+        redirect_uri: &str,
+    ) -> String {
+        self.authorization_url(scopes, state, redirect_uri, Some(code_challenge))
+    }
+
+    fn authorization_url(
+        &self,
+        scopes: &[&str],
+        state: &str,
+        redirect_uri: &str,
+        code_challenge: Option<&str>,
     ) -> String {
         let scope_str = scopes.join(" ");
         use url::Url;
 
         let mut url = Url::parse(AUTHORIZE_URL).unwrap();
-        url.query_pairs_mut()
-            .append_pair("response_type", "code")
-            .append_pair("client_id", &self.client_id)
-            .append_pair("redirect_uri", &self.redirect_uri)
-            .append_pair("scope", &scope_str)
-            .append_pair("state", state)
-            .append_pair("code_challenge", code_challenge)
-            .append_pair("code_challenge_method", "S256");
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &self.client_id)
+                .append_pair("redirect_uri", redirect_uri)
+                .append_pair("scope", &scope_str)
+                .append_pair("state", state);
+            if let Some(challenge) = code_challenge {
+                query
+                    .append_pair("code_challenge", challenge)
+                    .append_pair("code_challenge_method", "S256");
+            }
+        }
 
         url.to_string()
     }
 
     /// Builds a POST request to the token endpoint with the right auth shape
     /// for this manager's mode. For PKCE, `client_id` is appended to `params`.
-    fn token_request<'a>(
-        &'a self,
-        mut params: Vec<(&'a str, &'a str)>,
-    ) -> reqwest::RequestBuilder {
+    fn token_request<'a>(&'a self, mut params: Vec<(&'a str, &'a str)>) -> reqwest::RequestBuilder {
         let req = self.http_client.post(TOKEN_URL);
         match &self.auth_mode {
             AuthMode::Code { client_secret } => req
@@ -210,13 +395,14 @@ impl TokenManager {
         &self,
         code: &str,
         code_verifier: Option<&str>,
+        redirect_uri: &str,
         persist_cache: bool,
     ) -> Result<TokenSet, XeroError> {
         debug!("Exchanging authorization code for token set.");
         let mut params = vec![
             ("grant_type", "authorization_code"),
             ("code", code),
-            ("redirect_uri", self.redirect_uri.as_str()),
+            ("redirect_uri", redirect_uri),
         ];
         if let Some(v) = code_verifier {
             params.push(("code_verifier", v));
@@ -248,7 +434,24 @@ impl TokenManager {
                 "exchange_code called on a PKCE TokenManager — use exchange_code_pkce".to_string(),
             ));
         }
-        self.exchange_code_inner(code, None, true).await
+        self.exchange_code_inner(code, None, &self.redirect_uri, true)
+            .await
+    }
+
+    /// Exchanges an authorization code (code flow) that was requested with
+    /// [`Self::get_authorization_url_with_redirect`], using the same redirect URI.
+    pub async fn exchange_code_with_redirect(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<TokenSet, XeroError> {
+        if matches!(self.auth_mode, AuthMode::Pkce) {
+            return Err(XeroError::Auth(
+                "exchange_code_with_redirect called on a PKCE TokenManager — use exchange_code_pkce_with_redirect".to_string(),
+            ));
+        }
+        self.exchange_code_inner(code, None, redirect_uri, true)
+            .await
     }
 
     /// Exchanges an authorization code without mutating in-memory token cache.
@@ -261,7 +464,8 @@ impl TokenManager {
                 "exchange_code_no_cache called on a PKCE TokenManager — use exchange_code_pkce_no_cache".to_string(),
             ));
         }
-        self.exchange_code_inner(code, None, false).await
+        self.exchange_code_inner(code, None, &self.redirect_uri, false)
+            .await
     }
 
     /// Exchanges an authorization code for a token set (PKCE flow).
@@ -279,7 +483,25 @@ impl TokenManager {
                     .to_string(),
             ));
         }
-        self.exchange_code_inner(code, Some(code_verifier), true)
+        self.exchange_code_inner(code, Some(code_verifier), &self.redirect_uri, true)
+            .await
+    }
+
+    /// Exchanges a PKCE authorization code that was requested with
+    /// [`Self::get_authorization_url_pkce_with_redirect`], using the same redirect URI.
+    pub async fn exchange_code_pkce_with_redirect(
+        &self,
+        code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
+    ) -> Result<TokenSet, XeroError> {
+        if !matches!(self.auth_mode, AuthMode::Pkce) {
+            return Err(XeroError::Auth(
+                "exchange_code_pkce_with_redirect called on a code-flow TokenManager — use exchange_code_with_redirect"
+                    .to_string(),
+            ));
+        }
+        self.exchange_code_inner(code, Some(code_verifier), redirect_uri, true)
             .await
     }
 
@@ -295,7 +517,7 @@ impl TokenManager {
                     .to_string(),
             ));
         }
-        self.exchange_code_inner(code, Some(code_verifier), false)
+        self.exchange_code_inner(code, Some(code_verifier), &self.redirect_uri, false)
             .await
     }
 
@@ -306,7 +528,8 @@ impl TokenManager {
     ///
     /// Retries up to 2 times (3 total attempts) with backoff on transient
     /// network errors.  HTTP 4xx responses are **not** retried because they
-    /// indicate a permanent token problem (e.g. `invalid_grant`).
+    /// indicate a permanent token problem. An `invalid_grant` rejection is
+    /// returned as [`XeroError::ReauthRequired`].
     async fn refresh_token_inner(
         &self,
         token_set: &TokenSet,
@@ -347,9 +570,7 @@ impl TokenManager {
                     let status = response.status();
                     let message = response.text().await?;
                     if status.is_client_error() {
-                        return Err(XeroError::Auth(format!(
-                            "Failed to refresh token: {status} - {message}"
-                        )));
+                        return Err(refresh_rejected(status, &message));
                     }
                     // 5xx or other server-side errors are transient – retry.
                     warn!(
@@ -368,13 +589,12 @@ impl TokenManager {
                         attempt + 1,
                         max_attempts
                     );
-                    last_err = Some(XeroError::Request(e));
+                    last_err = Some(XeroError::from(e));
                 }
             }
 
             // Sleep before the next retry (skip sleep after the last attempt).
-            if attempt < max_attempts - 1 {
-                let delay = backoff_ms[attempt];
+            if let Some(&delay) = backoff_ms.get(attempt) {
                 debug!("Retrying token refresh in {delay}ms");
                 tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
             }
@@ -448,24 +668,86 @@ impl TokenManager {
         Ok(token_set.access_token)
     }
 
-    /// Saves the token set to the in-memory cache.
+    /// Refreshes the token after Xero rejected `rejected_access_token` with a 401.
+    ///
+    /// If the cached token has already moved on (another request refreshed it
+    /// first), that token is returned instead of refreshing again, so
+    /// concurrent 401s do not burn through rotating refresh tokens.
+    pub(crate) async fn refresh_after_unauthorized(
+        &self,
+        rejected_access_token: &str,
+    ) -> Result<String, XeroError> {
+        let token_set = self.load_token().await.ok_or_else(|| {
+            XeroError::Auth("Not authenticated. Please authorize first.".to_string())
+        })?;
+        if token_set.access_token != rejected_access_token {
+            debug!("Access token was already refreshed by another request.");
+            return Ok(token_set.access_token);
+        }
+        warn!("Access token rejected with 401. Forcing a refresh...");
+        Ok(self.refresh_token(&token_set).await?.access_token)
+    }
+
+    /// Saves the token set to the in-memory cache, and to the cache file if configured.
     async fn save_token(&self, token_set: &TokenSet) {
         trace!("Saving token to in-memory cache");
         let mut cached_token = self.cached_token.lock().await;
         *cached_token = Some(token_set.clone());
         debug!("Token saved successfully to in-memory cache.");
+
+        if let Some(path) = &self.cache_path {
+            let written = match serde_json::to_string_pretty(token_set) {
+                Ok(json) => fs::write(path, json).await.map_err(XeroError::from),
+                Err(e) => Err(XeroError::from(e)),
+            };
+            match written {
+                Ok(()) => debug!("Token written to cache file {}.", path.display()),
+                Err(e) => warn!("Failed to write token cache {}: {e}", path.display()),
+            }
+        }
     }
 
-    /// Loads the token set from the in-memory cache.
+    /// Loads the token set from the in-memory cache, falling back to the cache file.
     async fn load_token(&self) -> Option<TokenSet> {
         trace!("Loading token from in-memory cache");
-        let cached_token = self.cached_token.lock().await;
+        let mut cached_token = self.cached_token.lock().await;
         if let Some(token) = cached_token.as_ref() {
             debug!("Token found in in-memory cache.");
-            Some(token.clone())
-        } else {
-            warn!("No token found in in-memory cache.");
-            None
+            return Some(token.clone());
+        }
+
+        if let Some(token) = self.read_cache_file().await {
+            *cached_token = Some(token.clone());
+            return Some(token);
+        }
+
+        warn!("No token found in in-memory cache.");
+        None
+    }
+
+    /// Reads the token set from the cache file, if one is configured and present.
+    async fn read_cache_file(&self) -> Option<TokenSet> {
+        let path = self.cache_path.as_ref()?;
+        if !fs::try_exists(path).await.unwrap_or(false) {
+            debug!("Token cache file {} does not exist.", path.display());
+            return None;
+        }
+        let contents = match fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                warn!("Failed to read token cache {}: {e}", path.display());
+                return None;
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(token) => {
+                debug!("Token loaded from cache file {}.", path.display());
+                Some(token)
+            }
+            Err(e) => {
+                warn!("Failed to parse token cache {}: {e}", path.display());
+                None
+            }
         }
     }
 
@@ -480,10 +762,53 @@ impl TokenManager {
     }
 }
 
+/// The error body returned by the OAuth token endpoint.
+#[derive(Deserialize)]
+struct OAuthErrorBody {
+    error: String,
+}
+
+/// Maps a 4xx token-refresh response to an error, singling out `invalid_grant`
+/// so callers can send the user back through authorization instead of retrying.
+fn refresh_rejected(status: reqwest::StatusCode, message: &str) -> XeroError {
+    let detail = format!("Failed to refresh token: {status} - {message}");
+    match serde_json::from_str::<OAuthErrorBody>(message) {
+        Ok(body) if body.error == "invalid_grant" => XeroError::ReauthRequired(detail),
+        _ => XeroError::Auth(detail),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn jwt_with(claims: serde_json::Value) -> String {
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        format!(
+            "{}.{}.signature",
+            encode(br#"{"alg":"RS256"}"#),
+            encode(claims.to_string().as_bytes())
+        )
+    }
+
+    #[test]
+    fn granted_scopes_are_read_from_the_access_token() {
+        let token = |access_token: String| TokenSet {
+            access_token,
+            ..Default::default()
+        };
+        let listed = token(jwt_with(serde_json::json!({
+            "scope": ["openid", "accounting.transactions"]
+        })));
+        assert_eq!(
+            listed.granted_scopes(),
+            vec!["openid", "accounting.transactions"]
+        );
+        let spaced = token(jwt_with(serde_json::json!({ "scope": "openid email" })));
+        assert_eq!(spaced.granted_scopes(), vec!["openid", "email"]);
+        assert!(token("opaque".to_string()).granted_scopes().is_empty());
+    }
+
     /// RFC 7636 Appendix B test vector.
     #[test]
     fn challenge_matches_rfc7636_vector() {
@@ -509,7 +834,10 @@ mod tests {
         }
 
         // Challenge must be the deterministic SHA256/base64url of the verifier.
-        assert_eq!(codes.challenge, TokenManager::challenge_for(&codes.verifier));
+        assert_eq!(
+            codes.challenge,
+            TokenManager::challenge_for(&codes.verifier)
+        );
     }
 
     #[test]
@@ -554,6 +882,37 @@ mod tests {
         assert!(!url.contains("code_challenge_method"));
     }
 
+    #[test]
+    fn authorize_url_uses_explicit_redirect() {
+        let tm = TokenManager::new(
+            Client::new(),
+            "CLIENT123".to_string(),
+            "SECRET".to_string(),
+            "http://localhost/cb".to_string(),
+        );
+        let url = tm.get_authorization_url_with_redirect(
+            &["openid"],
+            "state-xyz",
+            "https://staging.example.com/cb",
+        );
+        assert!(url.contains("redirect_uri=https%3A%2F%2Fstaging.example.com%2Fcb"));
+        assert!(!url.contains("localhost"));
+
+        let pkce = TokenManager::new_pkce(
+            Client::new(),
+            "CLIENT123".to_string(),
+            "http://localhost/cb".to_string(),
+        );
+        let url = pkce.get_authorization_url_pkce_with_redirect(
+            &["openid"],
+            "state-xyz",
+            "CHALLENGE_VAL",
+            "https://app.example.com/cb",
+        );
+        assert!(url.contains("redirect_uri=https%3A%2F%2Fapp.example.com%2Fcb"));
+        assert!(url.contains("code_challenge=CHALLENGE_VAL"));
+    }
+
     #[test]
     fn is_pkce_reflects_construction() {
         let pkce = TokenManager::new_pkce(
@@ -594,4 +953,86 @@ mod tests {
         let err = tm.exchange_code_pkce("code", "verifier").await.unwrap_err();
         assert!(matches!(err, XeroError::Auth(msg) if msg.contains("code-flow")));
     }
+
+    #[test]
+    fn scope_sets_include_identity_and_have_no_duplicates() {
+        for scopes in [
+            Scopes::accounting_read_write(),
+            Scopes::accounting_read_only(),
+            Scopes::payroll(),
+            Scopes::all(),
+        ] {
+            assert!(scopes.contains(&"offline_access"));
+            let mut deduped = scopes.clone();
+            deduped.sort_unstable();
+            deduped.dedup();
+            assert_eq!(deduped.len(), scopes.len(), "duplicate in {scopes:?}");
+        }
+
+        let all = Scopes::all();
+        for scope in [
+            "accounting.reports.tenninetynine.read",
+            "paymentservices",
+            "bankfeeds",
+        ] {
+            assert!(all.contains(&scope));
+        }
+        assert_eq!(Scopes::with(&["files", "openid", "files"]).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn token_round_trips_through_cache_file() {
+        let path = std::env::temp_dir().join(format!("xero_token_{}.json", uuid::Uuid::new_v4()));
+        let token = TokenSet {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_in: 1800,
+            token_type: "Bearer".to_string(),
+            ..Default::default()
+        };
+
+        let writer = TokenManager::new_pkce(
+            Client::new(),
+            "id".to_string(),
+            "http://localhost/cb".to_string(),
+        )
+        .with_cache_path(&path);
+        writer.set_token(&token).await;
+
+        let reader = TokenManager::new_pkce(
+            Client::new(),
+            "id".to_string(),
+            "http://localhost/cb".to_string(),
+        )
+        .with_cache_path(&path);
+        let loaded = reader
+            .get_cached_token()
+            .await
+            .expect("token loaded from file");
+        fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(loaded.access_token, "access");
+        assert_eq!(loaded.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(loaded.obtained_at, token.obtained_at);
+        // Once loaded, the token is served from memory even if the file is gone.
+        assert!(reader.get_cached_token().await.is_some());
+    }
+
+    #[test]
+    fn invalid_grant_requires_reauth() {
+        let err = refresh_rejected(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"error":"invalid_grant"}"#,
+        );
+        assert!(matches!(err, XeroError::ReauthRequired(_)));
+
+        let err = refresh_rejected(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"error":"invalid_client"}"#,
+        );
+        assert!(matches!(err, XeroError::Auth(_)));
+
+        let err = refresh_rejected(reqwest::StatusCode::UNAUTHORIZED, "not json");
+        assert!(matches!(err, XeroError::Auth(_)));
+    }
 }
@@ -6,7 +6,7 @@ use std::sync::Mutex;
 use tiny_http::{Response, Server};
 use url::Url;
 use uuid::Uuid;
-use xero_rs_async::auth::PkceCodes;
+use xero_rs_async::auth::{PkceCodes, Scopes};
 use xero_rs_async::client::XeroClient;
 
 /// Selects which OAuth flow to run.
@@ -67,10 +67,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // 3. Generate and display the authorization URL
-    let scopes = [
-        "openid",
-        "profile",
-        "email",
+    let scopes = Scopes::with(&[
         "accounting.transactions",
         "accounting.settings",
         "accounting.contacts",
@@ -81,8 +78,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "accounting.reports.tenninetynine.read",
         "accounting.journals.read",
         "accounting.budgets.read",
-        "offline_access",
-    ];
+    ]);
     let state = "12345";
 
     // PKCE-only: generate verifier/challenge before building the URL.
@@ -95,11 +91,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         (Flow::Code, _) => xero_client
             .token_manager
             .get_authorization_url(&scopes, state),
-        (Flow::Pkce, Some(codes)) => xero_client.token_manager.get_authorization_url_pkce(
-            &scopes,
-            state,
-            &codes.challenge,
-        ),
+        (Flow::Pkce, Some(codes)) => {
+            xero_client
+                .token_manager
+                .get_authorization_url_pkce(&scopes, state, &codes.challenge)
+        }
         (Flow::Pkce, None) => unreachable!(),
     };
 
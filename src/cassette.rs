@@ -0,0 +1,290 @@
+//! Record-and-replay of API traffic, so tests can run without Xero access.
+//!
+//! A [`Cassette`] attached with [`XeroClient::with_cassette`] sits where the
+//! client would send a request. In [`CassetteMode::Record`] it passes each call
+//! through to Xero and keeps a copy of the response; [`Cassette::save`] then
+//! writes them to a JSON file. In [`CassetteMode::Replay`] nothing leaves the
+//! process: each request is answered from the file instead.
+//!
+//! Interactions are matched on method and path plus query string, so the
+//! host, tenant and access token do not have to match the recording. When the
+//! same request was recorded more than once, the responses are replayed in
+//! the order they were captured.
+//!
+//! Token refreshes and connection lookups do not go through the cassette; a
+//! replaying client should be given a long-lived placeholder token.
+//!
+//! [`XeroClient::with_cassette`]: crate::client::XeroClient::with_cassette
+
+use crate::error::XeroError;
+use base64::Engine;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One recorded request and the response Xero gave to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// The URL path and query string, without scheme or host.
+    pub path: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// The response body; base64-encoded when `base64` is set.
+    pub body: String,
+    /// Set for bodies that are not valid UTF-8, such as file downloads.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub base64: bool,
+}
+
+impl Interaction {
+    fn body_bytes(&self) -> Result<Vec<u8>, XeroError> {
+        if self.base64 {
+            base64::engine::general_purpose::STANDARD
+                .decode(&self.body)
+                .map_err(|e| {
+                    XeroError::Validation(format!(
+                        "Recorded body for {} {} is not valid base64: {e}",
+                        self.method, self.path
+                    ))
+                })
+        } else {
+            Ok(self.body.clone().into_bytes())
+        }
+    }
+}
+
+/// Whether a [`Cassette`] captures live traffic or plays it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+#[derive(Debug, Default)]
+struct Tape {
+    interactions: Vec<Interaction>,
+    /// Which interactions have already been replayed.
+    played: Vec<bool>,
+}
+
+/// A file of recorded interactions, either being written or played back.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    tape: Mutex<Tape>,
+}
+
+impl Cassette {
+    /// Starts an empty recording that [`Cassette::save`] will write to `path`.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: CassetteMode::Record,
+            tape: Mutex::default(),
+        }
+    }
+
+    /// Loads a recording from `path` for playback.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, XeroError> {
+        let path = path.into();
+        let text = std::fs::read_to_string(&path)?;
+        let interactions: Vec<Interaction> = serde_json::from_str(&text)?;
+        let played = vec![false; interactions.len()];
+        Ok(Self {
+            path,
+            mode: CassetteMode::Replay,
+            tape: Mutex::new(Tape {
+                interactions,
+                played,
+            }),
+        })
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A copy of the interactions recorded or loaded so far.
+    #[must_use]
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().interactions.clone()
+    }
+
+    /// Writes the recorded interactions to the cassette's file as pretty JSON,
+    /// creating parent directories as needed. Does nothing when replaying.
+    pub fn save(&self) -> Result<(), XeroError> {
+        if self.mode == CassetteMode::Replay {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.lock().interactions)?;
+        std::fs::write(&self.path, json)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Tape> {
+        self.tape.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sends `request` through `http_client` while recording, or answers it
+    /// from the tape while replaying.
+    pub(crate) async fn execute(
+        &self,
+        http_client: &Client,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, XeroError> {
+        let method = request.method().to_string();
+        let path = path_and_query(request.url());
+        match self.mode {
+            CassetteMode::Replay => self.play(&method, &path),
+            CassetteMode::Record => {
+                let response = http_client.execute(request).await?;
+                let status = response.status().as_u16();
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let bytes = response.bytes().await?.to_vec();
+                let (body, base64) = match String::from_utf8(bytes) {
+                    Ok(text) => (text, false),
+                    Err(e) => (
+                        base64::engine::general_purpose::STANDARD.encode(e.as_bytes()),
+                        true,
+                    ),
+                };
+                let interaction = Interaction {
+                    method,
+                    path,
+                    status,
+                    content_type,
+                    body,
+                    base64,
+                };
+                let response = to_response(&interaction)?;
+                let mut tape = self.lock();
+                tape.interactions.push(interaction);
+                tape.played.push(true);
+                Ok(response)
+            }
+        }
+    }
+
+    fn play(&self, method: &str, path: &str) -> Result<reqwest::Response, XeroError> {
+        let mut tape = self.lock();
+        let Tape {
+            interactions,
+            played,
+        } = &mut *tape;
+        let index = interactions
+            .iter()
+            .zip(played.iter())
+            .position(|(i, &done)| !done && i.method == method && i.path == path)
+            .ok_or_else(|| {
+                XeroError::Validation(format!(
+                    "No recorded response for {method} {path} in {}",
+                    self.path.display()
+                ))
+            })?;
+        played[index] = true;
+        to_response(&interactions[index])
+    }
+}
+
+fn path_and_query(url: &reqwest::Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn to_response(interaction: &Interaction) -> Result<reqwest::Response, XeroError> {
+    let mut builder = http::Response::builder().status(interaction.status);
+    if let Some(content_type) = &interaction.content_type {
+        builder = builder.header(CONTENT_TYPE, content_type);
+    }
+    let response = builder
+        .body(interaction.body_bytes()?)
+        .map_err(|e| XeroError::Validation(format!("Invalid recorded response: {e}")))?;
+    Ok(reqwest::Response::from(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockServer;
+
+    #[tokio::test]
+    async fn recorded_responses_replay_in_order_without_network() {
+        let bodies = [r#"{"n":1}"#, r#"{"n":2}"#];
+        let server = MockServer::start(
+            bodies
+                .iter()
+                .map(|body| {
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                })
+                .collect(),
+        )
+        .await;
+
+        let path = std::env::temp_dir().join(format!("cassette-{}.json", uuid::Uuid::new_v4()));
+        let http_client = Client::new();
+        let url = format!("{}/api.xro/2.0/Accounts?page=1", server.url());
+        let recorder = Cassette::record(&path);
+        for expected in bodies {
+            let request = http_client.get(&url).build().unwrap();
+            let response = recorder.execute(&http_client, request).await.unwrap();
+            assert_eq!(response.text().await.unwrap(), expected);
+        }
+        server.requests().await;
+        recorder.save().unwrap();
+
+        // The server is gone; replay must not touch the network.
+        let player = Cassette::replay(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let other_host = "http://xero.invalid/api.xro/2.0/Accounts?page=1";
+        for expected in bodies {
+            let request = http_client.get(other_host).build().unwrap();
+            let response = player.execute(&http_client, request).await.unwrap();
+            assert_eq!(
+                response.headers()[CONTENT_TYPE],
+                "application/json",
+                "content type is replayed"
+            );
+            assert_eq!(response.text().await.unwrap(), expected);
+        }
+
+        let request = http_client.get(other_host).build().unwrap();
+        let err = player.execute(&http_client, request).await.unwrap_err();
+        assert!(matches!(err, XeroError::Validation(_)), "{err:?}");
+    }
+
+    #[test]
+    fn binary_bodies_round_trip_as_base64() {
+        let interaction = Interaction {
+            method: "GET".to_string(),
+            path: "/files.xro/1.0/Files/x/Content".to_string(),
+            status: 200,
+            content_type: None,
+            body: base64::engine::general_purpose::STANDARD.encode([0xff, 0x00, 0xfe]),
+            base64: true,
+        };
+        assert_eq!(interaction.body_bytes().unwrap(), vec![0xff, 0x00, 0xfe]);
+    }
+}
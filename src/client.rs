@@ -1,5 +1,12 @@
 //! The main asynchronous Xero API client.
 
+#[cfg(feature = "accounting")]
+use crate::api::accounting::reports::cache::ReportCache;
+#[cfg(feature = "accounting")]
+use crate::api::accounting::tenant_cache::{
+    AccountCodeCache, BaseCurrencyCache, TaxRateCache, TenantCache, ACCOUNT_CODES_TTL,
+    BASE_CURRENCY_TTL, TAX_RATES_TTL,
+};
 #[cfg(feature = "accounting")]
 use crate::api::accounting::AccountingApi;
 #[cfg(feature = "bank-feeds")]
@@ -15,17 +22,25 @@ use crate::api::practice_manager::PracticeManagerApi;
 #[cfg(feature = "projects")]
 use crate::api::projects::ProjectsApi;
 use crate::auth::{TokenManager, TokenSet};
+use crate::cassette::Cassette;
 #[cfg(feature = "assets")]
 use crate::endpoints::assets::AssetsApi;
 #[cfg(feature = "files")]
 use crate::endpoints::files::FilesApi;
 use crate::error::XeroError;
-use crate::rate_limiter::RateLimiter;
+#[cfg(feature = "accounting")]
+use crate::fan_out::fan_out;
+#[cfg(feature = "accounting")]
+use crate::models::accounting::organisation::Organisation;
+use crate::rate_limiter::RequestLimiter;
 
 use log::{debug, info};
-use reqwest::Client;
+use reqwest::{tls, Certificate, Client, Method, RequestBuilder, StatusCode};
 use serde::Deserialize;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 /// Represents a Xero tenant connection.
@@ -38,16 +53,358 @@ pub struct Connection {
     pub tenant_name: Option<String>,
 }
 
+/// Most tenants queried at once by multi-tenant helpers such as
+/// [`XeroClient::get_all_organisations`].
+///
+/// These helpers send one request per tenant, so no tenant's own concurrency
+/// limit is at stake. The bound instead keeps open connections, and the draw
+/// on the app-wide minute limit that every tenant shares, modest when an app
+/// is connected to many organisations.
+pub const TENANT_FAN_OUT: usize = 5;
+
+/// Outcome of [`XeroClient::get_all_organisations`].
+#[cfg(feature = "accounting")]
+#[derive(Debug, Default)]
+pub struct TenantOrganisations {
+    /// Each tenant's organisation, in the order `/connections` listed them.
+    pub organisations: Vec<(Uuid, Organisation)>,
+    /// Tenants whose organisation could not be fetched, with the error for each.
+    pub failed: Vec<(Uuid, XeroError)>,
+}
+
+/// Snapshot of the client's authentication state, see [`XeroClient::diagnose`].
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Whether the cached token was accepted by Xero.
+    pub authenticated: bool,
+    /// When the cached access token expires, if there is one.
+    pub token_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Tenants the token can reach; empty when not authenticated.
+    pub connected_tenants: Vec<Connection>,
+    /// Scopes read from the access token, see [`TokenSet::granted_scopes`].
+    pub granted_scopes: Vec<String>,
+}
+
+/// Connections fetched at a point in time, see [`XeroClient::get_connections_cached`].
+type CachedConnections = (Instant, Vec<Connection>);
+
+/// Finds the single connection whose tenant name matches `name`, ignoring case
+/// and surrounding whitespace.
+fn find_tenant_by_name(connections: &[Connection], name: &str) -> Result<Uuid, XeroError> {
+    let wanted = name.trim().to_lowercase();
+    let matches: Vec<&Connection> = connections
+        .iter()
+        .filter(|c| {
+            c.tenant_name
+                .as_deref()
+                .is_some_and(|n| n.trim().to_lowercase() == wanted)
+        })
+        .collect();
+    match matches.as_slice() {
+        [connection] => Ok(connection.tenant_id),
+        [] => Err(XeroError::Validation(format!(
+            "No connected tenant is named \"{name}\""
+        ))),
+        several => Err(XeroError::Validation(format!(
+            "{} connected tenants are named \"{name}\"; use a tenant ID instead",
+            several.len()
+        ))),
+    }
+}
+
+/// A callback applied to every outbound API request just before it is sent.
+///
+/// Use it to attach tracing context (e.g. `traceparent`) or custom headers.
+/// Install one with [`XeroClient::on_request`].
+#[derive(Clone)]
+pub struct RequestHook(Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>);
+
+impl RequestHook {
+    pub(crate) fn apply(&self, builder: RequestBuilder) -> RequestBuilder {
+        (self.0)(builder)
+    }
+}
+
+impl std::fmt::Debug for RequestHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RequestHook")
+    }
+}
+
+/// Timing for one API call, passed to the hook installed with
+/// [`XeroClient::on_response`].
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseMetrics<'a> {
+    pub method: &'a Method,
+    /// The URL path, without the query string.
+    pub path: &'a str,
+    pub status: StatusCode,
+    /// Time from sending the request to receiving the response headers. Rate
+    /// limiter waits are not included, and neither is reading the body.
+    pub elapsed: Duration,
+    pub tenant_id: Uuid,
+}
+
+/// A callback invoked with [`ResponseMetrics`] after every API response.
+///
+/// Install one with [`XeroClient::on_response`].
+#[derive(Clone)]
+pub struct ResponseHook(Arc<dyn Fn(&ResponseMetrics<'_>) + Send + Sync>);
+
+impl ResponseHook {
+    pub(crate) fn call(&self, metrics: &ResponseMetrics<'_>) {
+        (self.0)(metrics)
+    }
+}
+
+impl std::fmt::Debug for ResponseHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ResponseHook")
+    }
+}
+
 /// The main client for interacting with all Xero APIs.
 #[derive(Debug, Clone)]
 pub struct XeroClient {
     pub(crate) http_client: Client,
     pub token_manager: Arc<TokenManager>,
-    pub(crate) rate_limiter: Arc<RateLimiter>,
+    pub(crate) rate_limiter: Arc<dyn RequestLimiter>,
+    pub(crate) on_request: Option<RequestHook>,
+    pub(crate) on_response: Option<ResponseHook>,
+    pub(crate) cassette: Option<Arc<Cassette>>,
+    pub(crate) connections_cache: Arc<Mutex<Option<CachedConnections>>>,
+    #[cfg(feature = "accounting")]
+    pub(crate) report_cache: Arc<ReportCache>,
+    #[cfg(feature = "accounting")]
+    pub(crate) account_codes: Arc<AccountCodeCache>,
+    #[cfg(feature = "accounting")]
+    pub(crate) base_currencies: Arc<BaseCurrencyCache>,
+    #[cfg(feature = "accounting")]
+    pub(crate) tax_rate_cache: Arc<TaxRateCache>,
+}
+
+/// Builder for a [`XeroClient`], created with [`XeroClient::builder`].
+///
+/// Besides the OAuth settings, it exposes the connection-pool, HTTP/2 and TLS
+/// knobs of the underlying `reqwest::Client`, or accepts a fully custom client.
+#[derive(Debug)]
+pub struct XeroClientBuilder {
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_uri: String,
+    rate_limiter: Arc<dyn RequestLimiter>,
+    token_cache: Option<PathBuf>,
+    initial_token: Option<TokenSet>,
+    http_client: Option<Client>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    http2_prior_knowledge: bool,
+    http2_adaptive_window: bool,
+    root_certificates: Vec<Certificate>,
+    min_tls_version: Option<tls::Version>,
+}
+
+impl XeroClientBuilder {
+    fn new(client_id: String, redirect_uri: String, rate_limiter: Arc<dyn RequestLimiter>) -> Self {
+        Self {
+            client_id,
+            client_secret: None,
+            redirect_uri,
+            rate_limiter,
+            token_cache: None,
+            initial_token: None,
+            http_client: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            connect_timeout: None,
+            timeout: None,
+            http2_prior_knowledge: false,
+            http2_adaptive_window: false,
+            root_certificates: Vec::new(),
+            min_tls_version: None,
+        }
+    }
+
+    /// Uses the authorization code flow with this client secret instead of PKCE.
+    #[must_use]
+    pub fn client_secret(mut self, client_secret: impl Into<String>) -> Self {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Persists tokens to this JSON file (see [`TokenManager::with_cache_path`]).
+    #[must_use]
+    pub fn token_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.token_cache = Some(path.into());
+        self
+    }
+
+    /// Seeds the token cache with an existing token.
+    #[must_use]
+    pub fn initial_token(mut self, token: TokenSet) -> Self {
+        self.initial_token = Some(token);
+        self
+    }
+
+    /// Uses a preconfigured `reqwest::Client` for all requests.
+    ///
+    /// The pool, timeout, HTTP/2 and TLS settings on this builder are ignored
+    /// when a client is supplied.
+    #[must_use]
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Maximum idle connections kept per host.
+    #[must_use]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// How long idle pooled connections are kept; `None` keeps them indefinitely.
+    #[must_use]
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing a connection.
+    #[must_use]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Overall timeout for each request, from connect to end of body.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Speaks HTTP/2 without ALPN negotiation.
+    #[must_use]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Enables HTTP/2 adaptive flow-control windows.
+    #[must_use]
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Trusts an additional root certificate, e.g. the CA of a TLS-inspecting proxy.
+    ///
+    /// The system roots stay trusted; call this once per extra certificate.
+    #[must_use]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Refuses to connect over TLS versions older than `version`.
+    #[must_use]
+    pub fn min_tls_version(mut self, version: tls::Version) -> Self {
+        self.min_tls_version = Some(version);
+        self
+    }
+
+    fn build_http_client(&mut self) -> Result<Client, XeroError> {
+        if let Some(client) = self.http_client.take() {
+            return Ok(client);
+        }
+        let mut builder = Client::builder();
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(true);
+        }
+        for certificate in self.root_certificates.drain(..) {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(version) = self.min_tls_version {
+            builder = builder.min_tls_version(version);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Builds the client.
+    pub async fn build(mut self) -> Result<XeroClient, XeroError> {
+        debug!("Creating new XeroClient instance.");
+        let http_client = self.build_http_client()?;
+        let mut token_manager = match self.client_secret {
+            Some(client_secret) => TokenManager::new(
+                http_client.clone(),
+                self.client_id,
+                client_secret,
+                self.redirect_uri,
+            ),
+            None => TokenManager::new_pkce(http_client.clone(), self.client_id, self.redirect_uri),
+        };
+        if let Some(path) = self.token_cache {
+            token_manager = token_manager.with_cache_path(path);
+        }
+        if let Some(token) = &self.initial_token {
+            token_manager.set_token(token).await;
+        }
+
+        info!("XeroClient created successfully.");
+        Ok(XeroClient {
+            http_client,
+            token_manager: Arc::new(token_manager),
+            rate_limiter: self.rate_limiter,
+            on_request: None,
+            on_response: None,
+            cassette: None,
+            connections_cache: Arc::default(),
+            #[cfg(feature = "accounting")]
+            report_cache: Arc::default(),
+            #[cfg(feature = "accounting")]
+            account_codes: Arc::new(TenantCache::new(ACCOUNT_CODES_TTL)),
+            #[cfg(feature = "accounting")]
+            base_currencies: Arc::new(TenantCache::new(BASE_CURRENCY_TTL)),
+            #[cfg(feature = "accounting")]
+            tax_rate_cache: Arc::new(TenantCache::new(TAX_RATES_TTL)),
+        })
+    }
 }
 
 /// A tenant-bound client that vends API handles without requiring tenant IDs per call.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    not(any(
+        feature = "accounting",
+        feature = "assets",
+        feature = "files",
+        feature = "projects",
+        feature = "payroll-au",
+        feature = "payroll-uk",
+        feature = "payroll-nz",
+        feature = "bank-feeds",
+        feature = "practice-manager"
+    )),
+    allow(dead_code)
+)]
 pub struct TenantClient {
     client: XeroClient,
     tenant_id: Uuid,
@@ -181,6 +538,18 @@ impl TenantClient {
 }
 
 impl XeroClient {
+    /// Starts building a `XeroClient` with custom HTTP or token settings.
+    ///
+    /// Without [`XeroClientBuilder::client_secret`] the client uses the PKCE flow.
+    #[must_use]
+    pub fn builder(
+        client_id: impl Into<String>,
+        redirect_uri: impl Into<String>,
+        rate_limiter: Arc<dyn RequestLimiter>,
+    ) -> XeroClientBuilder {
+        XeroClientBuilder::new(client_id.into(), redirect_uri.into(), rate_limiter)
+    }
+
     /// Creates a new `XeroClient`.
     ///
     /// # Arguments
@@ -188,28 +557,43 @@ impl XeroClient {
     /// * `client_id` - Your Xero App's client ID.
     /// * `client_secret` - Your Xero App's client secret.
     /// * `redirect_uri` - The redirect URI configured in your Xero App.
-    /// * `rate_limiter` - An Arc-wrapped, shared `RateLimiter` instance.
+    /// * `rate_limiter` - The shared limiter, usually a `RateLimiter`.
     pub async fn new(
         client_id: String,
         client_secret: String,
         redirect_uri: String,
-        rate_limiter: Arc<RateLimiter>,
+        rate_limiter: Arc<dyn RequestLimiter>,
     ) -> Result<Self, XeroError> {
-        debug!("Creating new XeroClient instance.");
-        let http_client = Client::new();
-        let token_manager = Arc::new(TokenManager::new(
-            http_client.clone(),
-            client_id,
-            client_secret,
-            redirect_uri,
-        ));
+        Self::builder(client_id, redirect_uri, rate_limiter)
+            .client_secret(client_secret)
+            .build()
+            .await
+    }
 
-        info!("XeroClient created successfully.");
-        Ok(Self {
-            http_client,
-            token_manager,
-            rate_limiter,
-        })
+    /// Creates a new `XeroClient` whose tokens are also persisted to `token_cache`.
+    ///
+    /// Any token already in the file is picked up on the first request, and
+    /// refreshed tokens are written back so they survive a restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - Your Xero App's client ID.
+    /// * `client_secret` - Your Xero App's client secret.
+    /// * `redirect_uri` - The redirect URI configured in your Xero App.
+    /// * `token_cache` - Path of the JSON file used to persist the `TokenSet`.
+    /// * `rate_limiter` - The shared limiter, usually a `RateLimiter`.
+    pub async fn new_with_token_cache(
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        token_cache: PathBuf,
+        rate_limiter: Arc<dyn RequestLimiter>,
+    ) -> Result<Self, XeroError> {
+        Self::builder(client_id, redirect_uri, rate_limiter)
+            .client_secret(client_secret)
+            .token_cache(token_cache)
+            .build()
+            .await
     }
 
     /// Creates a new `XeroClient` configured for the OAuth 2.0 PKCE flow.
@@ -222,26 +606,15 @@ impl XeroClient {
     ///
     /// * `client_id` - Your Xero PKCE App's client ID.
     /// * `redirect_uri` - The redirect URI configured in your Xero App.
-    /// * `rate_limiter` - An Arc-wrapped, shared `RateLimiter` instance.
+    /// * `rate_limiter` - The shared limiter, usually a `RateLimiter`.
     pub async fn new_pkce(
         client_id: String,
         redirect_uri: String,
-        rate_limiter: Arc<RateLimiter>,
+        rate_limiter: Arc<dyn RequestLimiter>,
     ) -> Result<Self, XeroError> {
-        debug!("Creating new XeroClient instance (PKCE).");
-        let http_client = Client::new();
-        let token_manager = Arc::new(TokenManager::new_pkce(
-            http_client.clone(),
-            client_id,
-            redirect_uri,
-        ));
-
-        info!("XeroClient created successfully (PKCE).");
-        Ok(Self {
-            http_client,
-            token_manager,
-            rate_limiter,
-        })
+        Self::builder(client_id, redirect_uri, rate_limiter)
+            .build()
+            .await
     }
 
     /// Creates a new PKCE `XeroClient` with an isolated `TokenManager` pre-seeded with the given token.
@@ -251,24 +624,13 @@ impl XeroClient {
     pub async fn new_pkce_with_token(
         client_id: String,
         redirect_uri: String,
-        rate_limiter: Arc<RateLimiter>,
+        rate_limiter: Arc<dyn RequestLimiter>,
         initial_token: TokenSet,
     ) -> Result<Self, XeroError> {
-        debug!("Creating new XeroClient instance (PKCE) with pre-seeded token.");
-        let http_client = Client::new();
-        let token_manager = Arc::new(TokenManager::new_pkce(
-            http_client.clone(),
-            client_id,
-            redirect_uri,
-        ));
-        token_manager.set_token(&initial_token).await;
-
-        info!("XeroClient created successfully (PKCE) with pre-seeded token.");
-        Ok(Self {
-            http_client,
-            token_manager,
-            rate_limiter,
-        })
+        Self::builder(client_id, redirect_uri, rate_limiter)
+            .initial_token(initial_token)
+            .build()
+            .await
     }
 
     /// Creates a new `XeroClient` with an isolated `TokenManager` pre-seeded with the given token.
@@ -281,31 +643,64 @@ impl XeroClient {
     /// * `client_id` - Your Xero App's client ID.
     /// * `client_secret` - Your Xero App's client secret.
     /// * `redirect_uri` - The redirect URI configured in your Xero App.
-    /// * `rate_limiter` - A shared `RateLimiter` instance (can be the same one used by other clients).
+    /// * `rate_limiter` - The shared limiter (can be the same one used by other clients).
     /// * `initial_token` - A `TokenSet` to pre-seed the new `TokenManager` with.
     pub async fn new_with_token(
         client_id: String,
         client_secret: String,
         redirect_uri: String,
-        rate_limiter: Arc<RateLimiter>,
+        rate_limiter: Arc<dyn RequestLimiter>,
         initial_token: TokenSet,
     ) -> Result<Self, XeroError> {
-        debug!("Creating new XeroClient instance with pre-seeded token.");
-        let http_client = Client::new();
-        let token_manager = Arc::new(TokenManager::new(
-            http_client.clone(),
-            client_id,
-            client_secret,
-            redirect_uri,
-        ));
-        token_manager.set_token(&initial_token).await;
+        Self::builder(client_id, redirect_uri, rate_limiter)
+            .client_secret(client_secret)
+            .initial_token(initial_token)
+            .build()
+            .await
+    }
 
-        info!("XeroClient created successfully with pre-seeded token.");
-        Ok(Self {
-            http_client,
-            token_manager,
-            rate_limiter,
-        })
+    /// Installs a hook that is applied to every API request before it is sent.
+    ///
+    /// The hook runs for all API handles created from this client afterwards,
+    /// after the auth and tenant headers have been set. Token endpoint calls
+    /// made by the [`TokenManager`] are not affected.
+    #[must_use]
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    {
+        self.on_request = Some(RequestHook(Arc::new(hook)));
+        self
+    }
+
+    /// Installs a hook that is called after every API response with the
+    /// call's method, path, status, duration and tenant, e.g. to record
+    /// latency histograms.
+    ///
+    /// Like [`XeroClient::on_request`], it applies to API handles created from
+    /// this client afterwards. A retried request reports each attempt. Calls
+    /// that fail before a response arrives (e.g. connection errors) are not
+    /// reported.
+    #[must_use]
+    pub fn on_response<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ResponseMetrics<'_>) + Send + Sync + 'static,
+    {
+        self.on_response = Some(ResponseHook(Arc::new(hook)));
+        self
+    }
+
+    /// Records API responses to, or replays them from, `cassette`.
+    ///
+    /// Intended for tests: record once against a real organisation, then
+    /// replay offline. Like [`XeroClient::on_request`], it applies to API
+    /// handles created from this client afterwards. While recording, response
+    /// bodies are read in full before they are returned, so downloads are not
+    /// streamed. Call [`Cassette::save`] once the calls are done.
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
     }
 
     /// Retrieves the list of tenants (organisations) connected to the current token.
@@ -314,6 +709,32 @@ impl XeroClient {
         self.get_connections_with_access_token(&access_token).await
     }
 
+    /// Retrieves the list of connected tenants, reusing a cached copy younger than `ttl`.
+    ///
+    /// The cache is shared by all clones of this client. Concurrent callers wait
+    /// for a single in-flight refresh rather than each hitting `/connections`.
+    pub async fn get_connections_cached(
+        &self,
+        ttl: Duration,
+    ) -> Result<Vec<Connection>, XeroError> {
+        let mut cache = self.connections_cache.lock().await;
+        if let Some((fetched_at, connections)) = cache.as_ref() {
+            if fetched_at.elapsed() < ttl {
+                return Ok(connections.clone());
+            }
+        }
+        let connections = self.get_connections().await?;
+        *cache = Some((Instant::now(), connections.clone()));
+        Ok(connections)
+    }
+
+    /// Clears the cache used by [`XeroClient::get_connections_cached`].
+    ///
+    /// Call this after connecting or disconnecting a tenant.
+    pub async fn invalidate_connections_cache(&self) {
+        *self.connections_cache.lock().await = None;
+    }
+
     /// Retrieves the list of tenants (organisations) for an explicit access token.
     pub async fn get_connections_with_access_token(
         &self,
@@ -337,12 +758,141 @@ impl XeroClient {
         }
     }
 
-    /// Returns a clone of the shared `RateLimiter` `Arc` used by this client.
+    /// Checks at startup that the stored token is usable, refreshing it if it
+    /// is close to expiry.
+    ///
+    /// Loads the token from memory or the token cache file. Returns
+    /// [`XeroError::ReauthRequired`] if there is no token or Xero rejects the
+    /// refresh token, so the service can fail fast before its first real call.
+    /// Other refresh failures, such as network errors, are returned as is.
+    pub async fn ensure_authenticated(&self) -> Result<(), XeroError> {
+        if self.token_manager.get_cached_token().await.is_none() {
+            return Err(XeroError::ReauthRequired(
+                "No stored token; run the authorization flow first".to_string(),
+            ));
+        }
+        self.token_manager.get_access_token().await?;
+        Ok(())
+    }
+
+    /// Reports whether the client is authenticated, when its token expires,
+    /// which tenants it can reach and which scopes it was granted.
+    ///
+    /// Refreshes the token if it is close to expiry, then calls
+    /// `/connections`. A missing, rejected or unrefreshable token is reported
+    /// as `authenticated: false` rather than an error; other failures (such as
+    /// network errors) are returned.
+    pub async fn diagnose(&self) -> Result<Diagnostics, XeroError> {
+        let unauthenticated = |token: Option<TokenSet>| Diagnostics {
+            authenticated: false,
+            token_expires_at: token.as_ref().map(TokenSet::expires_at),
+            connected_tenants: Vec::new(),
+            granted_scopes: token.map(|t| t.granted_scopes()).unwrap_or_default(),
+        };
+
+        let Some(token) = self.token_manager.get_cached_token().await else {
+            return Ok(unauthenticated(None));
+        };
+        let access_token = match self.token_manager.get_access_token().await {
+            Ok(access_token) => access_token,
+            Err(XeroError::Auth(_) | XeroError::ReauthRequired(_)) => {
+                return Ok(unauthenticated(Some(token)));
+            }
+            Err(e) => return Err(e),
+        };
+        // The refresh above may have replaced the token.
+        let token = self.token_manager.get_cached_token().await.unwrap_or(token);
+
+        match self.get_connections_with_access_token(&access_token).await {
+            Ok(connected_tenants) => Ok(Diagnostics {
+                authenticated: true,
+                token_expires_at: Some(token.expires_at()),
+                connected_tenants,
+                granted_scopes: token.granted_scopes(),
+            }),
+            Err(XeroError::Api { status, .. })
+                if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN =>
+            {
+                Ok(unauthenticated(Some(token)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lightweight readiness check for a tenant.
+    ///
+    /// Refreshes the access token if it is close to expiry, then issues one cheap
+    /// request: `GET /Organisation` (through the rate limiter) when the
+    /// `accounting` feature is enabled, otherwise `GET /connections`, checking
+    /// that `tenant_id` is among the connected tenants and returning
+    /// [`XeroError::Validation`] if it is not.
+    pub async fn ping(&self, tenant_id: Uuid) -> Result<(), XeroError> {
+        debug!("Pinging Xero for tenant {tenant_id}");
+
+        #[cfg(feature = "accounting")]
+        {
+            self.token_manager.get_access_token().await?;
+            self.accounting_for_tenant(tenant_id)
+                .get_organisation()
+                .await?;
+        }
+
+        #[cfg(not(feature = "accounting"))]
+        {
+            let access_token = self.token_manager.get_access_token().await?;
+            let connections = self
+                .get_connections_with_access_token(&access_token)
+                .await?;
+            if !connections.iter().any(|c| c.tenant_id == tenant_id) {
+                return Err(XeroError::Validation(format!(
+                    "tenant {tenant_id} is not connected to this token"
+                )));
+            }
+        }
+
+        info!("Ping succeeded for tenant {tenant_id}");
+        Ok(())
+    }
+
+    /// Fetches the `Organisation` record of every connected organisation tenant.
+    ///
+    /// Up to [`TENANT_FAN_OUT`] tenants are requested at once, each still
+    /// going through the shared rate limiter. A failure to list connections
+    /// is returned as `Err`, while a failure for one tenant is collected in
+    /// [`TenantOrganisations::failed`] without affecting the others.
+    /// Non-organisation connections, such as Practice Manager, are skipped.
+    #[cfg(feature = "accounting")]
+    pub async fn get_all_organisations(&self) -> Result<TenantOrganisations, XeroError> {
+        let tenant_ids: Vec<Uuid> = self
+            .get_connections()
+            .await?
+            .into_iter()
+            .filter(|c| c.tenant_type.eq_ignore_ascii_case("ORGANISATION"))
+            .map(|c| c.tenant_id)
+            .collect();
+
+        let outcomes = fan_out(tenant_ids, TENANT_FAN_OUT, |tenant_id| {
+            let api = self.accounting_for_tenant(tenant_id);
+            async move { api.get_organisation_single().await }
+        })
+        .await;
+
+        let mut result = TenantOrganisations::default();
+        for (tenant_id, outcome) in outcomes {
+            match outcome {
+                Ok(organisation) => result.organisations.push((tenant_id, organisation)),
+                Err(e) => result.failed.push((tenant_id, e)),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns a clone of the shared rate limiter `Arc` used by this client.
     ///
     /// This is useful when constructing a new isolated client (via `new_with_token`)
     /// that should share the same rate-limiting budget as the global client.
     #[must_use]
-    pub fn rate_limiter(&self) -> Arc<RateLimiter> {
+    pub fn rate_limiter(&self) -> Arc<dyn RequestLimiter> {
         self.rate_limiter.clone()
     }
 
@@ -365,6 +915,23 @@ impl XeroClient {
         self.tenant(tenant_id).accounting()
     }
 
+    /// Looks up the ID of the connected tenant named `name`, ignoring case.
+    ///
+    /// Returns [`XeroError::Validation`] if no connected tenant has that name,
+    /// or if several do.
+    pub async fn tenant_id_by_name(&self, name: &str) -> Result<Uuid, XeroError> {
+        let connections = self.get_connections().await?;
+        find_tenant_by_name(&connections, name)
+    }
+
+    /// Returns an Accounting API handle for the connected tenant named `name`.
+    /// See [`XeroClient::tenant_id_by_name`].
+    #[cfg(feature = "accounting")]
+    pub async fn accounting_for_tenant_name(&self, name: &str) -> Result<AccountingApi, XeroError> {
+        let tenant_id = self.tenant_id_by_name(name).await?;
+        Ok(self.accounting_for_tenant(tenant_id))
+    }
+
     /// Returns a convenient API handle for the Assets API that is bound to a specific tenant.
     #[must_use]
     #[cfg(feature = "assets")]
@@ -508,3 +1075,106 @@ impl XeroClient {
         self.tenant_with_token(tenant_id, token).practice_manager()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limiter::RateLimiter;
+    use crate::test_support::{self, token};
+
+    #[tokio::test]
+    async fn builder_applies_pool_and_flow_settings() {
+        let rate_limiter = Arc::new(RateLimiter::new().await.unwrap());
+        let pkce = XeroClient::builder("id", "http://localhost/cb", rate_limiter.clone())
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout(Some(Duration::from_secs(30)))
+            .connect_timeout(Duration::from_secs(5))
+            .http2_adaptive_window(true)
+            .min_tls_version(tls::Version::TLS_1_2)
+            .build()
+            .await
+            .unwrap();
+        assert!(pkce.token_manager.is_pkce());
+
+        let code = XeroClient::builder("id", "http://localhost/cb", rate_limiter)
+            .client_secret("secret")
+            .http_client(Client::new())
+            .initial_token(TokenSet {
+                access_token: "token".to_string(),
+                obtained_at: chrono::Utc::now(),
+                ..Default::default()
+            })
+            .build()
+            .await
+            .unwrap();
+        assert!(!code.token_manager.is_pkce());
+    }
+
+    #[test]
+    fn tenant_name_lookup_is_case_insensitive_and_unambiguous() {
+        let connection = |name: &str| Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            tenant_type: "ORGANISATION".to_string(),
+            tenant_name: Some(name.to_string()),
+        };
+        let acme = connection("Acme Ltd");
+        let connections = vec![acme.clone(), connection("Demo Co"), connection("DEMO CO")];
+
+        assert_eq!(
+            find_tenant_by_name(&connections, " acme ltd").unwrap(),
+            acme.tenant_id
+        );
+        assert!(matches!(
+            find_tenant_by_name(&connections, "Demo Co"),
+            Err(XeroError::Validation(message)) if message.starts_with("2 connected")
+        ));
+        assert!(find_tenant_by_name(&connections, "Missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn diagnose_without_a_token_is_unauthenticated() {
+        let client = test_support::client().await;
+        let diagnostics = client.diagnose().await.unwrap();
+        assert!(!diagnostics.authenticated);
+        assert!(diagnostics.token_expires_at.is_none());
+        assert!(diagnostics.connected_tenants.is_empty());
+        assert!(diagnostics.granted_scopes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ensure_authenticated_without_a_token_requires_reauth() {
+        let client = test_support::client().await;
+        let err = client.ensure_authenticated().await.unwrap_err();
+        assert!(matches!(err, XeroError::ReauthRequired(_)), "{err:?}");
+
+        client.token_manager.set_token(&token("token")).await;
+        client.ensure_authenticated().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn connections_cache_respects_ttl_and_invalidation() {
+        let client = test_support::client().await;
+        let connection = Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            tenant_type: "ORGANISATION".to_string(),
+            tenant_name: Some("Demo Company (NZ)".to_string()),
+        };
+        *client.connections_cache.lock().await = Some((Instant::now(), vec![connection.clone()]));
+
+        // A fresh entry is served from the cache on every clone, without a token.
+        let cached = client
+            .clone()
+            .get_connections_cached(Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(cached[0].tenant_id, connection.tenant_id);
+
+        // Once stale or invalidated, a refresh is attempted, which fails here
+        // because no token has been set.
+        assert!(client.get_connections_cached(Duration::ZERO).await.is_err());
+        client.invalidate_connections_cache().await;
+        assert!(client.connections_cache.lock().await.is_none());
+    }
+}
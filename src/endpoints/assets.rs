@@ -30,6 +30,9 @@ impl AssetsApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
@@ -56,6 +59,9 @@ impl AssetsApi {
     }
 
     /// Retrieves a list of assets.
+    ///
+    /// See [`AssetsApi::query`] for a typed alternative that checks the
+    /// `orderBy` field and sort direction at compile time.
     #[allow(clippy::too_many_arguments)]
     pub async fn get_assets(
         &self,
@@ -82,10 +88,26 @@ impl AssetsApi {
         if let Some(f) = filter_by {
             query.push(("filterBy".to_string(), f));
         }
+        self.fetch_assets(&query).await
+    }
+
+    /// Builds a typed request to list assets with the given status.
+    pub fn query(&self, status: AssetStatus) -> AssetQuery<'_> {
+        AssetQuery {
+            api: self,
+            status,
+            page: None,
+            page_size: None,
+            order_by: None,
+            sort_direction: None,
+            filter_by: None,
+        }
+    }
 
+    async fn fetch_assets(&self, query: &[(String, String)]) -> Result<Vec<Asset>, XeroError> {
         let resp: AssetsResponse = self
             .client
-            .send_request(Method::GET, "/Assets", Some(&query), None::<()>)
+            .send_request(Method::GET, "/Assets", Some(query), None::<()>)
             .await?;
         Ok(resp.items)
     }
@@ -121,3 +143,155 @@ impl AssetsApi {
             .await
     }
 }
+
+/// Fields the Assets API can order results by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetField {
+    AssetType,
+    AssetName,
+    AssetNumber,
+    PurchaseDate,
+    PurchasePrice,
+    /// Only valid when listing `DISPOSED` assets.
+    DisposalDate,
+    /// Only valid when listing `DISPOSED` assets.
+    DisposalPrice,
+}
+
+impl AssetField {
+    /// The exact, case-sensitive name the API expects.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssetField::AssetType => "AssetType",
+            AssetField::AssetName => "AssetName",
+            AssetField::AssetNumber => "AssetNumber",
+            AssetField::PurchaseDate => "PurchaseDate",
+            AssetField::PurchasePrice => "PurchasePrice",
+            AssetField::DisposalDate => "DisposalDate",
+            AssetField::DisposalPrice => "DisposalPrice",
+        }
+    }
+}
+
+/// Sort direction for asset listings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// The value the API expects.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+}
+
+/// Builder for typed asset list requests, created with [`AssetsApi::query`].
+#[derive(Debug, Clone)]
+pub struct AssetQuery<'a> {
+    api: &'a AssetsApi,
+    status: AssetStatus,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    order_by: Option<AssetField>,
+    sort_direction: Option<SortDirection>,
+    filter_by: Option<String>,
+}
+
+impl AssetQuery<'_> {
+    /// Sets the page number.
+    #[must_use]
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Sets the page size.
+    #[must_use]
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Orders results by `field` in `direction`.
+    #[must_use]
+    pub fn order_by(mut self, field: AssetField, direction: SortDirection) -> Self {
+        self.order_by = Some(field);
+        self.sort_direction = Some(direction);
+        self
+    }
+
+    /// Only returns assets whose name, number, description or asset type
+    /// name contains `text`.
+    #[must_use]
+    pub fn filter_by(mut self, text: impl Into<String>) -> Self {
+        self.filter_by = Some(text.into());
+        self
+    }
+
+    fn to_query(&self) -> Vec<(String, String)> {
+        let mut query = vec![(
+            "status".to_string(),
+            format!("{:?}", self.status).to_uppercase(),
+        )];
+        if let Some(page) = self.page {
+            query.push(("page".to_string(), page.to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            query.push(("pageSize".to_string(), page_size.to_string()));
+        }
+        if let Some(field) = self.order_by {
+            query.push(("orderBy".to_string(), field.as_str().to_string()));
+        }
+        if let Some(direction) = self.sort_direction {
+            query.push(("sortDirection".to_string(), direction.as_str().to_string()));
+        }
+        if let Some(text) = &self.filter_by {
+            query.push(("filterBy".to_string(), text.clone()));
+        }
+        query
+    }
+
+    /// Executes the list request.
+    pub async fn send(self) -> Result<Vec<Asset>, XeroError> {
+        self.api.fetch_assets(&self.to_query()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn typed_query_formats_fields() {
+        let api = AssetsApi::new(test_support::client().await, Uuid::nil());
+
+        let query = api
+            .query(AssetStatus::Disposed)
+            .page_size(50)
+            .order_by(AssetField::DisposalDate, SortDirection::Desc)
+            .filter_by("laptop")
+            .to_query();
+        let query: Vec<(&str, &str)> = query
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            query,
+            vec![
+                ("status", "DISPOSED"),
+                ("pageSize", "50"),
+                ("orderBy", "DisposalDate"),
+                ("sortDirection", "desc"),
+                ("filterBy", "laptop"),
+            ]
+        );
+    }
+}
@@ -3,18 +3,26 @@
 use crate::auth::TokenSet;
 use crate::client::XeroClient;
 use crate::error::XeroError;
-use crate::http::ApiClient;
+use crate::fan_out::{fan_out, PER_TENANT_FAN_OUT};
+use crate::http::{ApiClient, RequestContext};
 use crate::models::files::{
     association::{Association, AssociationCount, AssociationsResponse},
     file::{File, FilesResponse},
     folder::{Folder, FoldersResponse},
 };
-use reqwest::{multipart, Method};
+use log::debug;
+use reqwest::{multipart, Method, RequestBuilder};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 const BASE_URL: &str = "https://api.xero.com/files.xro/1.0";
+// Largest page size the Files API accepts.
+const SCAN_PAGE_SIZE: u32 = 100;
+// Object IDs per `/Associations/Count` request, keeping the query string well
+// under URL length limits.
+const ASSOCIATION_COUNT_CHUNK: usize = 100;
 
 /// A handle to the Files API endpoints.
 #[derive(Debug, Clone)]
@@ -31,6 +39,9 @@ impl FilesApi {
                 client.http_client.clone(),
                 client.token_manager.clone(),
                 client.rate_limiter.clone(),
+                client.on_request.clone(),
+                client.on_response.clone(),
+                client.cassette.clone(),
             ),
         }
     }
@@ -40,6 +51,12 @@ impl FilesApi {
         self
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.client = self.client.with_base_url(base_url);
+        self
+    }
+
     // --- Files ---
     /// Retrieves a list of files.
     pub async fn get_files(
@@ -69,6 +86,61 @@ impl FilesApi {
         Ok(resp.items)
     }
 
+    /// Pages through all files, stopping early once `visit` returns `false`.
+    async fn scan_files(&self, mut visit: impl FnMut(File) -> bool) -> Result<(), XeroError> {
+        let mut page = 1;
+        loop {
+            let files = self
+                .get_files(Some(SCAN_PAGE_SIZE), Some(page), None, None)
+                .await?;
+            let last_page = files.len() < SCAN_PAGE_SIZE as usize;
+            for file in files {
+                if !visit(file) {
+                    return Ok(());
+                }
+            }
+            if last_page {
+                return Ok(());
+            }
+            page += 1;
+        }
+    }
+
+    /// Retrieves all files in a folder.
+    ///
+    /// The Files API has no folder filter on `GET /Files`, so this pages
+    /// through every file and filters on `FolderId` client-side. Files uploaded
+    /// without a folder live in the Inbox folder.
+    pub async fn get_files_in_folder(&self, folder_id: Uuid) -> Result<Vec<File>, XeroError> {
+        let mut matches = Vec::new();
+        self.scan_files(|file| {
+            if file.folder_id == Some(folder_id) {
+                matches.push(file);
+            }
+            true
+        })
+        .await?;
+        Ok(matches)
+    }
+
+    /// Finds the first file whose name matches `name`, ignoring case.
+    ///
+    /// The Files API has no name search, so this pages through files
+    /// client-side and stops at the first match.
+    pub async fn find_file_by_name(&self, name: &str) -> Result<Option<File>, XeroError> {
+        let mut found = None;
+        self.scan_files(|file| {
+            if file.name.eq_ignore_ascii_case(name) {
+                found = Some(file);
+                false
+            } else {
+                true
+            }
+        })
+        .await?;
+        Ok(found)
+    }
+
     /// Retrieves a specific file by its ID.
     pub async fn get_file_by_id(&self, file_id: Uuid) -> Result<File, XeroError> {
         let path = format!("/Files/{file_id}");
@@ -85,19 +157,62 @@ impl FilesApi {
             .await
     }
 
+    /// Streams the content of a file into `writer`, returning the number of
+    /// bytes written.
+    ///
+    /// Unlike [`FilesApi::get_file_content`], the file is never held in
+    /// memory, so this suits large documents written to disk or another sink.
+    /// If the download fails partway, `writer` keeps the bytes already written.
+    pub async fn download_file_to_writer<W>(
+        &self,
+        file_id: Uuid,
+        writer: &mut W,
+    ) -> Result<u64, XeroError>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        let path = format!("/Files/{file_id}/Content");
+        self.client
+            .send_request_to_writer(Method::GET, &path, None, writer)
+            .await
+    }
+
     async fn upload_file_internal(
         &self,
         path: &str,
         file_name: String,
         body: Vec<u8>,
     ) -> Result<File, XeroError> {
-        let part = multipart::Part::bytes(body).file_name(file_name.clone());
+        let (ctx, builder) = self.upload_request(path, file_name, body).await?;
+        self.client.send_built_request(&ctx, builder).await
+    }
+
+    /// The Files API, unlike Accounting attachments, requires `multipart/form-data`.
+    async fn upload_request(
+        &self,
+        path: &str,
+        file_name: String,
+        body: Vec<u8>,
+    ) -> Result<(RequestContext, RequestBuilder), XeroError> {
+        let part = multipart::Part::bytes(body).file_name(file_name);
         let form = multipart::Form::new().part("file", part);
         self.client
-            .send_request_multipart(Method::POST, path, form)
+            .build_multipart_request(Method::POST, path, form)
             .await
     }
 
+    /// Uploads a file to the folder named `folder_name`, creating the folder
+    /// if needed. See [`FilesApi::ensure_folder`].
+    pub async fn upload_file_to_named_folder(
+        &self,
+        folder_name: &str,
+        file_name: String,
+        body: Vec<u8>,
+    ) -> Result<File, XeroError> {
+        let folder = self.ensure_folder(folder_name).await?;
+        self.upload_file_to_folder(folder.id, file_name, body).await
+    }
+
     /// Uploads a file to the inbox.
     pub async fn upload_file(&self, file_name: String, body: Vec<u8>) -> Result<File, XeroError> {
         self.upload_file_internal("/Files", file_name, body).await
@@ -114,20 +229,20 @@ impl FilesApi {
         self.upload_file_internal(&path, file_name, body).await
     }
 
-    /// Updates a file's name or folder.
+    /// Updates a file's name, folder, or both.
+    ///
+    /// Returns [`XeroError::Validation`] without calling the API when neither
+    /// is given. See also [`FilesApi::rename_file`] and [`FilesApi::move_file`].
     pub async fn update_file(
         &self,
         file_id: Uuid,
         new_name: Option<String>,
         new_folder_id: Option<Uuid>,
     ) -> Result<File, XeroError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "PascalCase")]
-        struct UpdateFileRequest {
-            #[serde(skip_serializing_if = "Option::is_none")]
-            name: Option<String>,
-            #[serde(rename = "FolderId", skip_serializing_if = "Option::is_none")]
-            folder_id: Option<Uuid>,
+        if new_name.is_none() && new_folder_id.is_none() {
+            return Err(XeroError::Validation(format!(
+                "Updating file {file_id} needs a new name or folder"
+            )));
         }
         let body = UpdateFileRequest {
             name: new_name,
@@ -139,6 +254,20 @@ impl FilesApi {
             .await
     }
 
+    /// Renames a file, leaving it in its folder.
+    pub async fn rename_file(
+        &self,
+        file_id: Uuid,
+        new_name: impl Into<String>,
+    ) -> Result<File, XeroError> {
+        self.update_file(file_id, Some(new_name.into()), None).await
+    }
+
+    /// Moves a file to another folder, keeping its name.
+    pub async fn move_file(&self, file_id: Uuid, folder_id: Uuid) -> Result<File, XeroError> {
+        self.update_file(file_id, None, Some(folder_id)).await
+    }
+
     /// Deletes a file.
     pub async fn delete_file(&self, file_id: Uuid) -> Result<(), XeroError> {
         let path = format!("/Files/{file_id}");
@@ -181,6 +310,36 @@ impl FilesApi {
             .await
     }
 
+    /// Returns the folder named `name` (ignoring case), creating it if none exists.
+    ///
+    /// If the create fails, for example because another process created the
+    /// folder first and Xero rejected the duplicate name, the folders are
+    /// fetched again. A match found then is returned instead of the error.
+    pub async fn ensure_folder(&self, name: &str) -> Result<Folder, XeroError> {
+        if let Some(folder) = self.find_folder_by_name(name).await? {
+            return Ok(folder);
+        }
+        match self.create_folder(name.to_string()).await {
+            Ok(folder) => Ok(folder),
+            Err(XeroError::Api { status, message }) if status.is_client_error() => {
+                debug!(
+                    "Creating folder {name:?} failed ({status}); checking for a concurrent create"
+                );
+                self.find_folder_by_name(name)
+                    .await?
+                    .ok_or(XeroError::Api { status, message })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn find_folder_by_name(&self, name: &str) -> Result<Option<Folder>, XeroError> {
+        let folders = self.get_folders(None).await?;
+        Ok(folders
+            .into_iter()
+            .find(|folder| folder.name.eq_ignore_ascii_case(name)))
+    }
+
     /// Updates a folder's name.
     pub async fn update_folder(&self, folder_id: Uuid, name: String) -> Result<Folder, XeroError> {
         #[derive(Serialize)]
@@ -230,16 +389,63 @@ impl FilesApi {
     }
 
     /// Retrieves a count of associations for a list of objects.
+    ///
+    /// Large ID lists are split into requests of at most 100 IDs, sent at most
+    /// five at a time to stay within Xero's concurrent request limit, and
+    /// merged into one map. Fails if any chunk fails.
     pub async fn get_associations_count(
         &self,
         object_ids: Vec<Uuid>,
     ) -> Result<AssociationCount, XeroError> {
-        let ids_str = object_ids
+        let chunks = fan_out(
+            association_count_chunks(&object_ids),
+            PER_TENANT_FAN_OUT,
+            |ids| {
+                let api = self.clone();
+                async move { api.get_associations_count_chunk(ids).await }
+            },
+        )
+        .await;
+
+        let mut counts = AssociationCount::new();
+        for (_, chunk) in chunks {
+            counts.extend(chunk?);
+        }
+        Ok(counts)
+    }
+
+    /// Counts the files attached to each of a set of documents, such as invoices.
+    ///
+    /// `documents` pairs each document's ID with its `HasAttachments` flag from
+    /// the Accounting API, e.g. `(invoice_id, invoice.has_attachments)`. A
+    /// document flagged `false` counts as 0 without a request. Unless
+    /// `exact_counts` is set, a document flagged `true` counts as 1, so only
+    /// documents with no flag are looked up; with `exact_counts`, every
+    /// document not flagged `false` is counted with
+    /// [`Self::get_associations_count`].
+    pub async fn audit_attachments(
+        &self,
+        documents: Vec<(Uuid, Option<bool>)>,
+        exact_counts: bool,
+    ) -> Result<HashMap<Uuid, usize>, XeroError> {
+        let to_count: Vec<Uuid> = documents
             .iter()
-            .map(std::string::ToString::to_string)
-            .collect::<Vec<String>>()
-            .join(",");
-        let query = vec![("ObjectIds".to_string(), ids_str)];
+            .filter(|(_, flag)| needs_association_count(*flag, exact_counts))
+            .map(|(id, _)| *id)
+            .collect();
+        let counts = if to_count.is_empty() {
+            AssociationCount::new()
+        } else {
+            self.get_associations_count(to_count).await?
+        };
+        Ok(attachment_audit(&documents, &counts, exact_counts))
+    }
+
+    async fn get_associations_count_chunk(
+        &self,
+        ids: String,
+    ) -> Result<AssociationCount, XeroError> {
+        let query = vec![("ObjectIds".to_string(), ids)];
         self.client
             .send_request(Method::GET, "/Associations/Count", Some(&query), None::<()>)
             .await
@@ -269,3 +475,205 @@ impl FilesApi {
             .await
     }
 }
+
+/// Joins object IDs into comma-separated `ObjectIds` values of at most
+/// [`ASSOCIATION_COUNT_CHUNK`] IDs each.
+fn association_count_chunks(object_ids: &[Uuid]) -> Vec<String> {
+    object_ids
+        .chunks(ASSOCIATION_COUNT_CHUNK)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(std::string::ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect()
+}
+
+/// Whether a document with this `HasAttachments` flag has to be counted
+/// through the Files API.
+fn needs_association_count(flag: Option<bool>, exact_counts: bool) -> bool {
+    match flag {
+        Some(false) => false,
+        Some(true) => exact_counts,
+        None => true,
+    }
+}
+
+/// Combines `HasAttachments` flags with the association counts fetched for
+/// the documents [`needs_association_count`] selected.
+fn attachment_audit(
+    documents: &[(Uuid, Option<bool>)],
+    counts: &AssociationCount,
+    exact_counts: bool,
+) -> HashMap<Uuid, usize> {
+    documents
+        .iter()
+        .map(|&(id, flag)| {
+            let count = if needs_association_count(flag, exact_counts) {
+                let count = counts.get(&id).copied().unwrap_or(0) as usize;
+                if exact_counts {
+                    count
+                } else {
+                    usize::from(count > 0)
+                }
+            } else {
+                usize::from(flag == Some(true))
+            };
+            (id, count)
+        })
+        .collect()
+}
+
+/// Body of `PUT /Files/{FileId}`. The Files API spells the folder field `FolderId`.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct UpdateFileRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "FolderId", skip_serializing_if = "Option::is_none")]
+    folder_id: Option<Uuid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, response, MockServer};
+
+    #[tokio::test]
+    async fn empty_file_update_is_rejected_before_sending() {
+        let api = test_support::client()
+            .await
+            .tenant_with_token(Uuid::from_u128(1), TokenSet::default())
+            .files()
+            .with_base_url("http://xero.invalid");
+
+        let err = api.update_file(Uuid::nil(), None, None).await.unwrap_err();
+        assert!(matches!(err, XeroError::Validation(_)), "{err:?}");
+
+        let body = serde_json::to_value(UpdateFileRequest {
+            name: None,
+            folder_id: Some(Uuid::nil()),
+        })
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "FolderId": "00000000-0000-0000-0000-000000000000" })
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_uses_multipart_form_data() {
+        let api = test_support::tenant().await.files();
+
+        let (_, builder) = api
+            .upload_request("/Files", "scan.pdf".to_string(), b"%PDF".to_vec())
+            .await
+            .unwrap();
+        let request = builder.build().unwrap();
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.url().path(), "/files.xro/1.0/Files");
+        let content_type = request.headers()["Content-Type"].to_str().unwrap();
+        assert!(
+            content_type.starts_with("multipart/form-data; boundary="),
+            "unexpected content type: {content_type}"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_streams_content_into_writer() {
+        let server = MockServer::start(vec![
+            "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n\
+             4\r\n%PDF\r\n5\r\n-1.7\n\r\n0\r\n\r\n"
+                .to_string(),
+        ])
+        .await;
+        let api = test_support::tenant()
+            .await
+            .files()
+            .with_base_url(server.url());
+
+        let file_id = Uuid::new_v4();
+        let mut sink = Vec::new();
+        let written = api
+            .download_file_to_writer(file_id, &mut sink)
+            .await
+            .unwrap();
+        assert_eq!(written, 9);
+        assert_eq!(sink, b"%PDF-1.7\n");
+        let requests = server.requests().await;
+        assert!(requests[0].starts_with(&format!("GET /Files/{file_id}/Content ")));
+    }
+
+    #[tokio::test]
+    async fn ensure_folder_recovers_from_a_concurrent_create() {
+        let folder_id = Uuid::new_v4();
+        let existing = serde_json::json!([{
+            "Name": "Receipts 2024",
+            "FileCount": 0,
+            "IsInbox": false,
+            "Id": folder_id
+        }])
+        .to_string();
+        let responses = vec![
+            response("200 OK", "[]"),
+            response(
+                "400 Bad Request",
+                r#"{"Message":"Folder name already exists"}"#,
+            ),
+            response("200 OK", &existing),
+        ];
+        let server = MockServer::start(responses).await;
+        let api = test_support::tenant()
+            .await
+            .files()
+            .with_base_url(server.url());
+
+        let folder = api.ensure_folder("receipts 2024").await.unwrap();
+        assert_eq!(folder.id, folder_id);
+        assert_eq!(
+            server.request_lines().await,
+            vec!["GET /Folders", "POST /Folders", "GET /Folders"]
+        );
+    }
+
+    #[test]
+    fn association_count_ids_are_chunked() {
+        let ids: Vec<Uuid> = (0..250u128).map(Uuid::from_u128).collect();
+        let chunks = association_count_chunks(&ids);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].split(',').count(), 100);
+        assert_eq!(chunks[2].split(',').count(), 50);
+        assert!(chunks[2].ends_with(&Uuid::from_u128(249).to_string()));
+        assert!(association_count_chunks(&[]).is_empty());
+    }
+
+    #[test]
+    fn attachment_audit_uses_flags_unless_exact_counts_are_asked_for() {
+        let (flagged, bare, unflagged) =
+            (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let documents = vec![
+            (flagged, Some(true)),
+            (bare, Some(false)),
+            (unflagged, None),
+        ];
+        let counts = AssociationCount::from([(flagged, 3), (unflagged, 2)]);
+
+        assert!(!needs_association_count(Some(true), false));
+        assert!(needs_association_count(Some(true), true));
+        assert!(!needs_association_count(Some(false), true));
+        assert!(needs_association_count(None, false));
+
+        let cheap = attachment_audit(&documents, &counts, false);
+        assert_eq!(cheap[&flagged], 1);
+        assert_eq!(cheap[&bare], 0);
+        assert_eq!(cheap[&unflagged], 1);
+
+        let exact = attachment_audit(&documents, &counts, true);
+        assert_eq!(exact[&flagged], 3);
+        assert_eq!(exact[&bare], 0);
+        assert_eq!(exact[&unflagged], 2);
+    }
+}
@@ -1,13 +1,25 @@
 //! Contains the custom error types for the Xero API client.
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Represents all possible errors that can occur when interacting with the Xero API.
 #[derive(Error, Debug)]
 pub enum XeroError {
-    /// An error occurred during the request, originating from the `reqwest` library.
+    /// The request or response body did not complete within the client's timeout.
+    ///
+    /// Usually transient; the call can be retried.
+    #[error("HTTP request timed out: {0}")]
+    Timeout(#[source] reqwest::Error),
+
+    /// A connection to the server could not be established (DNS, refused, TLS handshake).
+    #[error("HTTP connection error: {0}")]
+    Connect(#[source] reqwest::Error),
+
+    /// Any other failure in the `reqwest` transport, such as a dropped connection
+    /// while reading the body or a request that could not be built.
     #[error("HTTP request error: {0}")]
-    Request(#[from] reqwest::Error),
+    Transport(#[source] reqwest::Error),
 
     /// An error occurred while (de)serializing JSON data.
     #[error("Serialization/Deserialization error: {0}")]
@@ -20,6 +32,27 @@ pub enum XeroError {
         body: String,
     },
 
+    /// A successful response could not be deserialized into the expected type.
+    ///
+    /// Records the request URL and HTTP status alongside the raw body so a
+    /// field-type mismatch can be traced back to the call that produced it.
+    #[error("Failed to deserialize response from {url} ({status}): {source}")]
+    Deserialize {
+        url: String,
+        status: reqwest::StatusCode,
+        source: serde_json::Error,
+        body: String,
+    },
+
+    /// A successful response carried something other than JSON, typically an
+    /// HTML maintenance or proxy error page served with a 200 during an outage.
+    #[error("Expected a JSON response but got {content_type}: {body_snippet}")]
+    UnexpectedContentType {
+        content_type: String,
+        /// The start of the body, enough to recognise the page.
+        body_snippet: String,
+    },
+
     /// The Xero API returned a non-success status code with an error message.
     #[error("Xero API error ({status}): {message}")]
     Api {
@@ -31,10 +64,27 @@ pub enum XeroError {
     #[error("XML deserialization error: {0}")]
     Xml(#[from] quick_xml::DeError),
 
+    /// The request was rejected locally before being sent to Xero.
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    /// The tenant id is the nil UUID, usually an unset or misconfigured
+    /// `XERO_TENANT_ID`. Caught before sending, since Xero would only answer
+    /// with an opaque 401/403.
+    #[error(
+        "Invalid tenant id {0}: use the tenantId of a connection from XeroClient::get_connections"
+    )]
+    InvalidTenant(uuid::Uuid),
+
     /// An error related to OAuth 2.0 authentication.
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    /// The refresh token was rejected with `invalid_grant` (revoked, expired or
+    /// already rotated). Retrying will not help; the user must authorize again.
+    #[error("Re-authentication required: {0}")]
+    ReauthRequired(String),
+
     /// An error occurred within the rate limiter.
     #[error("Rate limiter error: {0}")]
     RateLimiter(String),
@@ -43,3 +93,242 @@ pub enum XeroError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl From<reqwest::Error> for XeroError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            XeroError::Timeout(err)
+        } else if err.is_connect() {
+            XeroError::Connect(err)
+        } else {
+            XeroError::Transport(err)
+        }
+    }
+}
+
+/// Xero's JSON error envelope, returned with most 4xx responses.
+///
+/// A `ValidationException` carries the individual messages on each element
+/// of `elements`; other exception types usually only populate `message`.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ApiException {
+    pub error_number: Option<i64>,
+    #[serde(rename = "Type")]
+    pub exception_type: Option<String>,
+    pub message: Option<String>,
+    pub elements: Vec<ApiExceptionElement>,
+}
+
+/// An element of an [`ApiException`] that failed validation.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ApiExceptionElement {
+    pub validation_errors: Vec<ApiValidationError>,
+}
+
+/// A single validation message reported by Xero.
+///
+/// The same shape is used for the non-fatal `Warnings` Xero attaches to
+/// records it accepted, e.g. an invoice saved against a contact with no email.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ApiValidationError {
+    pub message: String,
+}
+
+/// A validation message tied to the record Xero rejected.
+///
+/// `index` is the record's position in the submitted batch; Xero echoes every
+/// submitted record back in `Elements`, in order, so it lines up with the
+/// `Vec` passed to the create or update call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementValidationError {
+    pub index: usize,
+    pub message: String,
+}
+
+impl ApiException {
+    /// Parses an error response body, returning `None` if it is not Xero's JSON envelope.
+    #[must_use]
+    pub fn parse(body: &str) -> Option<Self> {
+        serde_json::from_str(body.trim()).ok()
+    }
+
+    /// All validation messages across every element.
+    #[must_use]
+    pub fn validation_messages(&self) -> Vec<&str> {
+        self.elements
+            .iter()
+            .flat_map(|e| e.validation_errors.iter())
+            .map(|v| v.message.as_str())
+            .collect()
+    }
+
+    /// Validation messages paired with the index of the record they belong to.
+    #[must_use]
+    pub fn element_errors(&self) -> Vec<ElementValidationError> {
+        self.elements
+            .iter()
+            .enumerate()
+            .flat_map(|(index, element)| {
+                element
+                    .validation_errors
+                    .iter()
+                    .map(move |v| ElementValidationError {
+                        index,
+                        message: v.message.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// A one-line description: the validation messages if any, else the top-level message.
+    #[must_use]
+    pub fn summary(&self) -> Option<String> {
+        let messages = self.validation_messages();
+        if messages.is_empty() {
+            self.message.clone()
+        } else {
+            Some(messages.join("; "))
+        }
+    }
+}
+
+impl XeroError {
+    /// Parses Xero's error envelope out of an [`XeroError::Api`] response body.
+    #[must_use]
+    pub fn api_exception(&self) -> Option<ApiException> {
+        match self {
+            XeroError::Api { message, .. } => ApiException::parse(message),
+            _ => None,
+        }
+    }
+
+    /// The per-record validation errors from a rejected batch.
+    ///
+    /// With `summarizeErrors=true` Xero rejects the whole batch with a 400 if
+    /// any record is invalid; this lists exactly which records failed and why.
+    /// Empty for any other error.
+    #[must_use]
+    pub fn validation_errors(&self) -> Vec<ElementValidationError> {
+        match self {
+            XeroError::Api { status, .. } if *status == reqwest::StatusCode::BAD_REQUEST => self
+                .api_exception()
+                .map(|e| e.element_errors())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_ERROR_BODY: &str = r#"{
+        "ErrorNumber": 10,
+        "Type": "ValidationException",
+        "Message": "A validation exception occurred",
+        "Elements": [
+            {
+                "Type": "ACCREC",
+                "Contact": { "ContactID": "bd2270c3-8706-4c11-9cfb-000b551c3f51", "Name": "ABC Limited" },
+                "LineItems": [{ "Description": "Consulting", "Quantity": 1.0, "UnitAmount": 100.0 }],
+                "ValidationErrors": []
+            },
+            {
+                "Type": "ACCREC",
+                "Contact": { "Name": "" },
+                "LineItems": [],
+                "ValidationErrors": [
+                    { "Message": "A Contact must be specified for this type of transaction" },
+                    { "Message": "One or more line items must be specified" }
+                ]
+            },
+            {
+                "Type": "ACCREC",
+                "Contact": { "ContactID": "bd2270c3-8706-4c11-9cfb-000b551c3f51" },
+                "LineItems": [{ "Description": "Widgets", "AccountCode": "9999" }],
+                "ValidationErrors": [
+                    { "Message": "Account code '9999' is not a valid code for this document." }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn summarized_batch_errors_keep_record_indices() {
+        let err = XeroError::Api {
+            status: reqwest::StatusCode::BAD_REQUEST,
+            message: MULTI_ERROR_BODY.to_string(),
+        };
+
+        let errors = err.validation_errors();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0],
+            ElementValidationError {
+                index: 1,
+                message: "A Contact must be specified for this type of transaction".to_string(),
+            }
+        );
+        assert_eq!(errors[1].index, 1);
+        assert_eq!(errors[2].index, 2);
+        assert!(errors[2].message.contains("9999"));
+    }
+
+    #[test]
+    fn validation_errors_empty_for_other_errors() {
+        let not_found = XeroError::Api {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: MULTI_ERROR_BODY.to_string(),
+        };
+        assert!(not_found.validation_errors().is_empty());
+        assert!(XeroError::Validation("local".to_string())
+            .validation_errors()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn reqwest_errors_split_by_failure_kind() {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        // Accepts the connection but never answers.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let silent = listener.local_addr().unwrap();
+        let _server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+        let err = XeroError::from(
+            client
+                .get(format!("http://{silent}"))
+                .send()
+                .await
+                .unwrap_err(),
+        );
+        assert!(matches!(err, XeroError::Timeout(_)), "{err:?}");
+
+        // Nothing listening on the port once the listener is dropped.
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let err = XeroError::from(
+            client
+                .get(format!("http://{closed}"))
+                .send()
+                .await
+                .unwrap_err(),
+        );
+        assert!(matches!(err, XeroError::Connect(_)), "{err:?}");
+
+        let err = XeroError::from(client.get("not a url").send().await.unwrap_err());
+        assert!(matches!(err, XeroError::Transport(_)), "{err:?}");
+    }
+}
@@ -0,0 +1,110 @@
+//! Running one request per key concurrently without failing fast.
+//!
+//! Helpers that issue a request per tenant, payment or contact, such as
+//! [`crate::client::XeroClient::get_all_organisations`], report each key's
+//! outcome separately, so one failing request does not hide the results of
+//! the others.
+
+use crate::error::XeroError;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Most requests a batch helper for one tenant keeps in flight, matching
+/// Xero's limit of five concurrent requests per tenant.
+pub(crate) const PER_TENANT_FAN_OUT: usize = 5;
+
+/// Runs `f` for every key, at most `concurrency` at a time, and returns each
+/// key's result in the order of `keys`.
+///
+/// Errors are collected rather than short-circuiting. The calls still go
+/// through the client's rate limiter, so `concurrency` only bounds how many
+/// keys are in flight before that. A `concurrency` of 0 is treated as 1.
+/// A panic in `f` is propagated once every task has finished.
+pub(crate) async fn fan_out<K, T, F, Fut>(
+    keys: Vec<K>,
+    concurrency: usize,
+    f: F,
+) -> Vec<(K, Result<T, XeroError>)>
+where
+    K: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(K) -> Fut,
+    Fut: Future<Output = Result<T, XeroError>> + Send + 'static,
+{
+    let slots = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, key) in keys.into_iter().enumerate() {
+        let slots = slots.clone();
+        let call = f(key.clone());
+        tasks.spawn(async move {
+            // The semaphore is never closed, so acquiring cannot fail.
+            let _slot = slots.acquire_owned().await;
+            (index, key, call.await)
+        });
+    }
+
+    let mut outcomes = Vec::with_capacity(tasks.len());
+    let mut panic = None;
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(e) if e.is_panic() => panic = Some(e.into_panic()),
+            Err(_) => {}
+        }
+    }
+    if let Some(payload) = panic {
+        std::panic::resume_unwind(payload);
+    }
+    outcomes.sort_by_key(|(index, _, _)| *index);
+    outcomes
+        .into_iter()
+        .map(|(_, key, result)| (key, result))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn keeps_order_and_failures_within_the_concurrency_bound() {
+        let tenants: Vec<Uuid> = (1..=6).map(Uuid::from_u128).collect();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let results = fan_out(tenants.clone(), 2, |tenant_id| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if tenant_id.as_u128() % 3 == 0 {
+                    Err(XeroError::Validation(format!("tenant {tenant_id}")))
+                } else {
+                    Ok(tenant_id.as_u128())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(
+            results.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            tenants
+        );
+        let failed: Vec<u128> = results
+            .iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(id, _)| id.as_u128())
+            .collect();
+        assert_eq!(failed, vec![3, 6]);
+        assert!(matches!(results[0].1, Ok(1)));
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
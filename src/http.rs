@@ -1,32 +1,89 @@
 //! Shared HTTP client utilities for Xero APIs.
 
 use crate::auth::{TokenManager, TokenSet};
+use crate::cassette::Cassette;
+use crate::client::{RequestHook, ResponseHook, ResponseMetrics};
 use crate::error::XeroError;
-use crate::rate_limiter::RateLimiter;
+use crate::rate_limiter::RequestLimiter;
+use crate::response::ResponseMeta;
 use log::{debug, error, trace};
-use reqwest::{multipart::Form, Client, Method, RequestBuilder};
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{multipart::Form, Client, Method, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 use uuid::Uuid;
 
+/// Source of the per-process request ids shown in log lines.
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies one API call in log output.
+///
+/// Displays as `[tenant <id> req <n>] <METHOD> <url>`, so every line logged for
+/// a call, including its retry, can be grepped by tenant or request id.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestContext {
+    pub(crate) url: String,
+    tenant_id: Uuid,
+    method: Method,
+    id: u64,
+}
+
+impl RequestContext {
+    fn new(tenant_id: Uuid, method: Method, url: String) -> Self {
+        Self {
+            url,
+            tenant_id,
+            method,
+            id: NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl std::fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[tenant {} req {}] {} {}",
+            self.tenant_id, self.id, self.method, self.url
+        )
+    }
+}
+
+/// A successful response to [`ApiClient::send_json`], before parsing.
+struct JsonResponse {
+    ctx: RequestContext,
+    status: StatusCode,
+    text: String,
+    meta: ResponseMeta,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ApiClient {
     base_url: String,
     tenant_id: Uuid,
     http_client: Client,
     token_manager: Arc<TokenManager>,
-    rate_limiter: Arc<RateLimiter>,
+    rate_limiter: Arc<dyn RequestLimiter>,
     token_override: Option<Arc<TokenSet>>,
+    on_request: Option<RequestHook>,
+    on_response: Option<ResponseHook>,
+    cassette: Option<Arc<Cassette>>,
 }
 
 impl ApiClient {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         base_url: impl Into<String>,
         tenant_id: Uuid,
         http_client: Client,
         token_manager: Arc<TokenManager>,
-        rate_limiter: Arc<RateLimiter>,
+        rate_limiter: Arc<dyn RequestLimiter>,
+        on_request: Option<RequestHook>,
+        on_response: Option<ResponseHook>,
+        cassette: Option<Arc<Cassette>>,
     ) -> Self {
         let mut base_url = base_url.into();
         while base_url.ends_with('/') {
@@ -39,6 +96,9 @@ impl ApiClient {
             token_manager,
             rate_limiter,
             token_override: None,
+            on_request,
+            on_response,
+            cassette,
         }
     }
 
@@ -47,6 +107,17 @@ impl ApiClient {
         self
     }
 
+    /// Points the client at a different host, e.g. a local mock server in tests.
+    #[cfg(test)]
+    pub(crate) fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub(crate) fn tenant_id(&self) -> Uuid {
+        self.tenant_id
+    }
+
     fn url(&self, path: &str) -> String {
         format!("{}{}", self.base_url, path)
     }
@@ -64,14 +135,17 @@ impl ApiClient {
         method: Method,
         path: &str,
         accept_json: bool,
-    ) -> Result<(String, RequestBuilder), XeroError> {
-        let url = self.url(path);
-        debug!("Sending API request: {} {}", method, url);
+    ) -> Result<(RequestContext, RequestBuilder), XeroError> {
+        if self.tenant_id.is_nil() {
+            return Err(XeroError::InvalidTenant(self.tenant_id));
+        }
+        let ctx = RequestContext::new(self.tenant_id, method.clone(), self.url(path));
+        debug!("{ctx}: sending API request");
         let access_token = self.access_token().await?;
 
         let mut builder = self
             .http_client
-            .request(method, &url)
+            .request(method, &ctx.url)
             .bearer_auth(access_token)
             .header("xero-tenant-id", self.tenant_id.to_string());
 
@@ -79,41 +153,129 @@ impl ApiClient {
             builder = builder.header("Accept", "application/json");
         }
 
-        Ok((url, builder))
+        Ok((ctx, builder))
     }
 
-    async fn send(&self, builder: RequestBuilder) -> Result<reqwest::Response, XeroError> {
-        let _permit = self.rate_limiter.acquire_permit(self.tenant_id).await?;
-        trace!("Rate limiter permit acquired for tenant {}", self.tenant_id);
-        Ok(builder.send().await?)
+    /// Applies the client's request hook, if any.
+    fn prepare(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.on_request {
+            Some(hook) => hook.apply(builder),
+            None => builder,
+        }
+    }
+
+    /// Sends a request. A 401 forces one token refresh and a single retry, so
+    /// an access token that expired early (e.g. through clock skew) heals
+    /// itself; a second 401 is reported as [`XeroError::ReauthRequired`].
+    async fn send(
+        &self,
+        ctx: &RequestContext,
+        builder: RequestBuilder,
+    ) -> Result<reqwest::Response, XeroError> {
+        let request = self.prepare(builder).build()?;
+        // Streaming bodies cannot be cloned; those requests are not retried.
+        let retry = request.try_clone();
+        let response = self.execute(ctx, request).await?;
+        if response.status() != StatusCode::UNAUTHORIZED || self.token_override.is_some() {
+            return Ok(response);
+        }
+        let Some(mut retry) = retry else {
+            return Ok(response);
+        };
+
+        let rejected = retry
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default()
+            .to_string();
+        let access_token = self
+            .token_manager
+            .refresh_after_unauthorized(&rejected)
+            .await?;
+        let header = HeaderValue::from_str(&format!("Bearer {access_token}"))
+            .map_err(|e| XeroError::Auth(format!("Invalid access token: {e}")))?;
+        retry.headers_mut().insert(AUTHORIZATION, header);
+
+        debug!("{ctx}: retrying with a refreshed token");
+        let response = self.execute(ctx, retry).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let message = response.text().await?;
+            return Err(XeroError::ReauthRequired(format!(
+                "Request still unauthorized after refreshing the token: {message}"
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &RequestContext,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, XeroError> {
+        let _permit = self.rate_limiter.acquire(self.tenant_id).await?;
+        trace!("{ctx}: rate limiter permit acquired");
+        let method = request.method().clone();
+        let path = request.url().path().to_string();
+        let started = Instant::now();
+        let response = match &self.cassette {
+            Some(cassette) => cassette.execute(&self.http_client, request).await?,
+            None => self.http_client.execute(request).await?,
+        };
+        let elapsed = started.elapsed();
+        debug!("{ctx}: {} in {elapsed:?}", response.status());
+        if let Some(hook) = &self.on_response {
+            hook.call(&ResponseMetrics {
+                method: &method,
+                path: &path,
+                status: response.status(),
+                elapsed,
+                tenant_id: self.tenant_id,
+            });
+        }
+        Ok(response)
     }
 
     async fn send_expect_success(
         &self,
+        ctx: &RequestContext,
         builder: RequestBuilder,
     ) -> Result<reqwest::Response, XeroError> {
-        let response = self.send(builder).await?;
+        let response = self.send(ctx, builder).await?;
         if response.status().is_success() {
             Ok(response)
         } else {
             let status = response.status();
             let message = response.text().await?;
+            debug!("{ctx}: failed with {status}: {message}");
             Err(XeroError::Api { status, message })
         }
     }
 
-    fn deserialize_json<R>(&self, url: &str, response_text: &str) -> Result<R, XeroError>
-    where
-        R: DeserializeOwned,
-    {
-        let trimmed = response_text.trim();
-        serde_json::from_str::<R>(trimmed).map_err(|e| {
-            error!("Failed to deserialize JSON response from {url}: {e}");
-            error!("Raw JSON response that failed to parse:\n---\n{trimmed}\n---");
-            XeroError::SerdeWithBody {
-                source: e,
-                body: response_text.to_string(),
-            }
+    /// Like `send_expect_success`, but also rejects a successful response
+    /// whose `Content-Type` is not JSON, instead of letting it fail to parse.
+    ///
+    /// A response without a `Content-Type` is let through.
+    async fn send_expect_json(
+        &self,
+        ctx: &RequestContext,
+        builder: RequestBuilder,
+    ) -> Result<reqwest::Response, XeroError> {
+        let response = self.send_expect_success(ctx, builder).await?;
+        let content_type = match response.headers().get(CONTENT_TYPE) {
+            Some(value) => String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            None => return Ok(response),
+        };
+        if is_json_content_type(&content_type) {
+            return Ok(response);
+        }
+        let body = response.text().await?;
+        let body_snippet: String = body.trim().chars().take(BODY_SNIPPET_CHARS).collect();
+        error!("{ctx}: expected JSON but got {content_type}: {body_snippet}");
+        Err(XeroError::UnexpectedContentType {
+            content_type,
+            body_snippet,
         })
     }
 
@@ -128,19 +290,79 @@ impl ApiClient {
         R: DeserializeOwned,
         B: Serialize,
     {
-        let (url, mut builder) = self.build_request(method, path, true).await?;
+        let response = self.send_json(method, path, query, body).await?;
+        deserialize_json(&response.ctx, response.status, &response.text)
+    }
+
+    /// Like `send_request`, but also returns the response body as untyped
+    /// JSON, including any fields the models do not cover.
+    pub(crate) async fn send_request_with_raw<R, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(String, String)]>,
+        body: Option<B>,
+    ) -> Result<(R, serde_json::Value), XeroError>
+    where
+        R: DeserializeOwned,
+        B: Serialize,
+    {
+        let response = self.send_json(method, path, query, body).await?;
+        let parsed = deserialize_json(&response.ctx, response.status, &response.text)?;
+        let raw = deserialize_json(&response.ctx, response.status, &response.text)?;
+        Ok((parsed, raw))
+    }
+
+    /// Like `send_request`, but also returns the rate limit and correlation
+    /// headers of the response.
+    pub(crate) async fn send_request_detailed<R, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(String, String)]>,
+        body: Option<B>,
+    ) -> Result<(R, ResponseMeta), XeroError>
+    where
+        R: DeserializeOwned,
+        B: Serialize,
+    {
+        let response = self.send_json(method, path, query, body).await?;
+        let parsed = deserialize_json(&response.ctx, response.status, &response.text)?;
+        Ok((parsed, response.meta))
+    }
+
+    /// Sends a request with an optional JSON body and returns the successful
+    /// response's text.
+    async fn send_json<B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(String, String)]>,
+        body: Option<B>,
+    ) -> Result<JsonResponse, XeroError>
+    where
+        B: Serialize,
+    {
+        let (ctx, mut builder) = self.build_request(method, path, true).await?;
         if let Some(q) = &query {
-            trace!("Request query: {q:?}");
+            trace!("{ctx}: query {q:?}");
             builder = builder.query(q);
         }
         if let Some(b) = body {
-            trace!("Request has a JSON body.");
+            trace!("{ctx}: request has a JSON body");
             builder = builder.json(&b);
         }
 
-        let response = self.send_expect_success(builder).await?;
-        let response_text = response.text().await?;
-        self.deserialize_json(&url, &response_text)
+        let response = self.send_expect_json(&ctx, builder).await?;
+        let status = response.status();
+        let meta = ResponseMeta::from_headers(response.headers());
+        let text = response.text().await?;
+        Ok(JsonResponse {
+            ctx,
+            status,
+            text,
+            meta,
+        })
     }
 
     /// Like `send_request` but adds an `If-Modified-Since` header.
@@ -157,35 +379,106 @@ impl ApiClient {
     where
         R: DeserializeOwned,
     {
-        let (url, mut builder) = self.build_request(method, path, true).await?;
+        let (ctx, mut builder) = self.build_request(method, path, true).await?;
         if let Some(q) = &query {
-            trace!("Request query: {q:?}");
+            trace!("{ctx}: query {q:?}");
             builder = builder.query(q);
         }
         let header_value = if_modified_since
             .format("%a, %d %b %Y %H:%M:%S GMT")
             .to_string();
-        trace!("If-Modified-Since: {header_value}");
+        trace!("{ctx}: If-Modified-Since {header_value}");
         builder = builder.header("If-Modified-Since", header_value);
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_json(&ctx, builder).await?;
+        let status = response.status();
         let response_text = response.text().await?;
-        self.deserialize_json(&url, &response_text)
+        deserialize_json(&ctx, status, &response_text)
     }
 
+    /// Like `send_request` but parses the body as it arrives instead of
+    /// buffering it into a `String` first.
+    ///
+    /// Meant for the largest responses (e.g. full journal exports), where the
+    /// buffered copy would double peak memory. Because the body is never held
+    /// in full, a parse failure is reported without it.
+    pub(crate) async fn send_request_streaming<R>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(String, String)]>,
+        if_modified_since: Option<&chrono::DateTime<chrono::Utc>>,
+    ) -> Result<R, XeroError>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        let (ctx, mut builder) = self.build_request(method, path, true).await?;
+        if let Some(q) = &query {
+            trace!("{ctx}: query {q:?}");
+            builder = builder.query(q);
+        }
+        if let Some(since) = if_modified_since {
+            let header_value = since.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            trace!("{ctx}: If-Modified-Since {header_value}");
+            builder = builder.header("If-Modified-Since", header_value);
+        }
+
+        let mut response = self.send_expect_json(&ctx, builder).await?;
+        let status = response.status();
+
+        let (tx, rx) = tokio::sync::mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let parser = tokio::task::spawn_blocking(move || {
+            serde_json::from_reader::<_, R>(ChunkReader::new(rx))
+        });
+        let mut read_error = None;
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    // The parser hung up early, so it has already failed.
+                    if tx.send(chunk).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    read_error = Some(e);
+                    break;
+                }
+            }
+        }
+        drop(tx);
+
+        let parsed = parser
+            .await
+            .map_err(|e| XeroError::Io(std::io::Error::other(e)))?;
+        if let Some(e) = read_error {
+            return Err(e.into());
+        }
+        parsed.map_err(|e| {
+            error!("{ctx}: failed to deserialize streamed JSON response ({status}): {e}");
+            XeroError::Deserialize {
+                url: ctx.url,
+                status,
+                source: e,
+                body: String::new(),
+            }
+        })
+    }
+
+    #[cfg_attr(not(feature = "assets"), allow(dead_code))]
     pub(crate) async fn send_request_text(
         &self,
         method: Method,
         path: &str,
         query: Option<&[(String, String)]>,
     ) -> Result<String, XeroError> {
-        let (_url, mut builder) = self.build_request(method, path, true).await?;
+        let (ctx, mut builder) = self.build_request(method, path, true).await?;
         if let Some(q) = &query {
-            trace!("Request query: {q:?}");
+            trace!("{ctx}: query {q:?}");
             builder = builder.query(q);
         }
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_success(&ctx, builder).await?;
         Ok(response.text().await?)
     }
 
@@ -198,32 +491,68 @@ impl ApiClient {
     where
         B: Serialize,
     {
-        let (_url, mut builder) = self.build_request(method, path, false).await?;
+        let (ctx, mut builder) = self.build_request(method, path, false).await?;
         if let Some(b) = body {
             builder = builder.json(&b);
         }
 
-        self.send_expect_success(builder).await?;
+        self.send_expect_success(&ctx, builder).await?;
         Ok(())
     }
 
-    pub(crate) async fn send_request_raw_body<R, B>(
+    /// Builds (but does not send) a request whose body is sent verbatim with
+    /// the given `Content-Type`, as the Accounting attachment endpoints expect.
+    pub(crate) async fn build_raw_body_request<B>(
         &self,
         method: Method,
         path: &str,
+        query: Option<&[(String, String)]>,
         content_type: &str,
         body: B,
-    ) -> Result<R, XeroError>
+    ) -> Result<(RequestContext, RequestBuilder), XeroError>
     where
-        R: DeserializeOwned,
         B: Into<reqwest::Body>,
     {
-        let (url, builder) = self.build_request(method, path, true).await?;
+        let (ctx, mut builder) = self.build_request(method, path, true).await?;
+        if let Some(q) = &query {
+            trace!("{ctx}: query {q:?}");
+            builder = builder.query(q);
+        }
         let builder = builder.header("Content-Type", content_type).body(body);
+        Ok((ctx, builder))
+    }
 
-        let response = self.send_expect_success(builder).await?;
+    /// Sends a request previously prepared by one of the `build_*` helpers and
+    /// deserializes the JSON response.
+    pub(crate) async fn send_built_request<R>(
+        &self,
+        ctx: &RequestContext,
+        builder: RequestBuilder,
+    ) -> Result<R, XeroError>
+    where
+        R: DeserializeOwned,
+    {
+        let response = self.send_expect_json(ctx, builder).await?;
+        let status = response.status();
         let response_text = response.text().await?;
-        self.deserialize_json(&url, &response_text)
+        deserialize_json(ctx, status, &response_text)
+    }
+
+    pub(crate) async fn send_request_raw_body<R, B>(
+        &self,
+        method: Method,
+        path: &str,
+        content_type: &str,
+        body: B,
+    ) -> Result<R, XeroError>
+    where
+        R: DeserializeOwned,
+        B: Into<reqwest::Body>,
+    {
+        let (ctx, builder) = self
+            .build_raw_body_request(method, path, None, content_type, body)
+            .await?;
+        self.send_built_request(&ctx, builder).await
     }
 
     pub(crate) async fn send_request_bytes(
@@ -232,15 +561,58 @@ impl ApiClient {
         path: &str,
         query: Option<&[(String, String)]>,
     ) -> Result<Vec<u8>, XeroError> {
-        let (_url, mut builder) = self.build_request(method, path, false).await?;
+        let (ctx, mut builder) = self.build_request(method, path, false).await?;
         if let Some(q) = &query {
             builder = builder.query(q);
         }
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_success(&ctx, builder).await?;
         Ok(response.bytes().await?.to_vec())
     }
 
+    /// Streams the response body into `writer` chunk by chunk, returning the
+    /// number of bytes written. The writer is flushed but not shut down.
+    #[cfg_attr(not(feature = "files"), allow(dead_code))]
+    pub(crate) async fn send_request_to_writer<W>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(String, String)]>,
+        writer: &mut W,
+    ) -> Result<u64, XeroError>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let (ctx, mut builder) = self.build_request(method, path, false).await?;
+        if let Some(q) = &query {
+            builder = builder.query(q);
+        }
+
+        let mut response = self.send_expect_success(&ctx, builder).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = response.chunk().await? {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        trace!("{ctx}: wrote {written} bytes");
+        Ok(written)
+    }
+
+    /// Builds (but does not send) a `multipart/form-data` request, as the Files
+    /// API upload endpoints expect.
+    pub(crate) async fn build_multipart_request(
+        &self,
+        method: Method,
+        path: &str,
+        form: Form,
+    ) -> Result<(RequestContext, RequestBuilder), XeroError> {
+        let (ctx, builder) = self.build_request(method, path, true).await?;
+        Ok((ctx, builder.multipart(form)))
+    }
+
     #[allow(dead_code)]
     pub(crate) async fn send_request_multipart<R>(
         &self,
@@ -251,19 +623,15 @@ impl ApiClient {
     where
         R: DeserializeOwned,
     {
-        let (url, builder) = self.build_request(method, path, true).await?;
-        let builder = builder.multipart(form);
-
-        let response = self.send_expect_success(builder).await?;
-        let response_text = response.text().await?;
-        self.deserialize_json(&url, &response_text)
+        let (ctx, builder) = self.build_multipart_request(method, path, form).await?;
+        self.send_built_request(&ctx, builder).await
     }
 
     // ── XML helpers (Practice Manager / XPM) ─────────────────────────
 
     /// Deserialize an XPM XML response, checking for the `<Status>ERROR</Status>` envelope first.
     #[allow(dead_code)]
-    fn deserialize_xml<R>(&self, url: &str, response_text: &str) -> Result<R, XeroError>
+    fn deserialize_xml<R>(&self, ctx: &RequestContext, response_text: &str) -> Result<R, XeroError>
     where
         R: DeserializeOwned,
     {
@@ -280,7 +648,7 @@ impl ApiClient {
         }
 
         quick_xml::de::from_str::<R>(trimmed).map_err(|e| {
-            error!("Failed to deserialize XML response from {url}: {e}");
+            error!("{ctx}: failed to deserialize XML response: {e}");
             error!("Raw XML response that failed to parse:\n---\n{trimmed}\n---");
             XeroError::Xml(e)
         })
@@ -297,15 +665,15 @@ impl ApiClient {
     where
         R: DeserializeOwned,
     {
-        let (url, mut builder) = self.build_request(method, path, false).await?;
+        let (ctx, mut builder) = self.build_request(method, path, false).await?;
         if let Some(q) = &query {
-            trace!("Request query: {q:?}");
+            trace!("{ctx}: query {q:?}");
             builder = builder.query(q);
         }
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_success(&ctx, builder).await?;
         let response_text = response.text().await?;
-        self.deserialize_xml(&url, &response_text)
+        self.deserialize_xml(&ctx, &response_text)
     }
 
     /// Send a request with an XML body, expecting an XML response.
@@ -319,14 +687,14 @@ impl ApiClient {
     where
         R: DeserializeOwned,
     {
-        let (url, builder) = self.build_request(method, path, false).await?;
+        let (ctx, builder) = self.build_request(method, path, false).await?;
         let builder = builder
             .header("Content-Type", "text/xml")
             .body(xml_body.to_string());
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_success(&ctx, builder).await?;
         let response_text = response.text().await?;
-        self.deserialize_xml(&url, &response_text)
+        self.deserialize_xml(&ctx, &response_text)
     }
 
     /// Send a request with an optional XML body, expecting only a success status (no body to parse).
@@ -337,14 +705,14 @@ impl ApiClient {
         path: &str,
         xml_body: Option<&str>,
     ) -> Result<(), XeroError> {
-        let (_url, mut builder) = self.build_request(method, path, false).await?;
+        let (ctx, mut builder) = self.build_request(method, path, false).await?;
         if let Some(body) = xml_body {
             builder = builder
                 .header("Content-Type", "text/xml")
                 .body(body.to_string());
         }
 
-        let response = self.send_expect_success(builder).await?;
+        let response = self.send_expect_success(&ctx, builder).await?;
         let response_text = response.text().await?;
         let trimmed = response_text.trim();
 
@@ -362,6 +730,52 @@ impl ApiClient {
     }
 }
 
+/// Number of response chunks buffered ahead of a streaming parse.
+const STREAM_CHANNEL_CAPACITY: usize = 16;
+
+/// How much of an unexpected non-JSON body is kept on the error.
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// Blocking [`Read`](std::io::Read) over response chunks sent from an async task.
+struct ChunkReader<B> {
+    rx: tokio::sync::mpsc::Receiver<B>,
+    current: Option<B>,
+    pos: usize,
+}
+
+impl<B> ChunkReader<B> {
+    fn new(rx: tokio::sync::mpsc::Receiver<B>) -> Self {
+        Self {
+            rx,
+            current: None,
+            pos: 0,
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> std::io::Read for ChunkReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if let Some(chunk) = &self.current {
+                let remaining = &chunk.as_ref()[self.pos..];
+                if !remaining.is_empty() {
+                    let n = remaining.len().min(buf.len());
+                    buf[..n].copy_from_slice(&remaining[..n]);
+                    self.pos += n;
+                    return Ok(n);
+                }
+            }
+            match self.rx.blocking_recv() {
+                Some(chunk) => {
+                    self.current = Some(chunk);
+                    self.pos = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
 /// Extract the text content of a simple XML tag (no attributes, no nesting).
 #[allow(dead_code)]
 fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
@@ -371,3 +785,276 @@ fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
     let end = xml[start..].find(&close)? + start;
     Some(xml[start..end].to_string())
 }
+
+/// Whether a `Content-Type` value names JSON, e.g. `application/json;
+/// charset=utf-8` or `application/problem+json`.
+fn is_json_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "application/json" || mime == "text/json" || mime.ends_with("+json")
+}
+
+/// Deserializes a JSON response body, recording where it came from on failure.
+fn deserialize_json<R>(
+    ctx: &RequestContext,
+    status: reqwest::StatusCode,
+    response_text: &str,
+) -> Result<R, XeroError>
+where
+    R: DeserializeOwned,
+{
+    let trimmed = response_text.trim();
+    serde_json::from_str::<R>(trimmed).map_err(|e| {
+        error!("{ctx}: failed to deserialize JSON response ({status}): {e}");
+        error!("Raw JSON response that failed to parse:\n---\n{trimmed}\n---");
+        XeroError::Deserialize {
+            url: ctx.url.clone(),
+            status,
+            source: e,
+            body: response_text.to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, response, token, MockServer};
+
+    #[tokio::test]
+    async fn request_hook_adds_headers() {
+        let client = test_support::client().await.on_request(|builder| {
+            builder
+                .header("traceparent", "00-abc-def-01")
+                .header("x-tenant-label", "acme")
+        });
+        client.token_manager.set_token(&token("token")).await;
+
+        let api = ApiClient::new(
+            "https://api.xero.com/api.xro/2.0",
+            tenant_id(),
+            client.http_client.clone(),
+            client.token_manager.clone(),
+            client.rate_limiter.clone(),
+            client.on_request.clone(),
+            client.on_response.clone(),
+            client.cassette.clone(),
+        );
+        let (_, builder) = api
+            .build_request(Method::GET, "/Items", true)
+            .await
+            .unwrap();
+        let request = api.prepare(builder).build().unwrap();
+
+        assert_eq!(request.headers()["traceparent"], "00-abc-def-01");
+        assert_eq!(request.headers()["x-tenant-label"], "acme");
+        assert_eq!(request.headers()["xero-tenant-id"], tenant_id().to_string());
+    }
+
+    fn tenant_id() -> Uuid {
+        Uuid::from_u128(1)
+    }
+
+    #[tokio::test]
+    async fn unauthorized_request_is_retried_with_refreshed_token() {
+        let responses = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = responses.clone();
+        let client = test_support::client().await.on_response(move |metrics| {
+            recorded.lock().unwrap().push((
+                metrics.method.clone(),
+                metrics.path.to_string(),
+                metrics.status,
+                metrics.tenant_id,
+            ));
+        });
+        client.token_manager.set_token(&token("stale")).await;
+
+        let token_manager = client.token_manager.clone();
+        let server = MockServer::respond_with(2, move |request| {
+            let token_manager = token_manager.clone();
+            async move {
+                if is_stale(&request) {
+                    // Another request refreshed the token while this one was in flight.
+                    token_manager.set_token(&token("fresh")).await;
+                    response("401 Unauthorized", "")
+                } else {
+                    response("200 OK", "{}")
+                }
+            }
+        })
+        .await;
+
+        let api = ApiClient::new(
+            server.url(),
+            tenant_id(),
+            client.http_client.clone(),
+            client.token_manager.clone(),
+            client.rate_limiter.clone(),
+            None,
+            client.on_response.clone(),
+            client.cassette.clone(),
+        );
+        let body: serde_json::Value = api
+            .send_request(Method::GET, "/Items", None, None::<()>)
+            .await
+            .unwrap();
+
+        assert_eq!(body, serde_json::json!({}));
+        let requests = server.requests().await;
+        assert!(is_stale(&requests[0]));
+        assert!(!is_stale(&requests[1]));
+        let get = |status| (Method::GET, "/Items".to_string(), status, tenant_id());
+        assert_eq!(
+            *responses.lock().unwrap(),
+            vec![get(StatusCode::UNAUTHORIZED), get(StatusCode::OK)]
+        );
+    }
+
+    fn is_stale(request: &str) -> bool {
+        request
+            .to_lowercase()
+            .contains("authorization: bearer stale")
+    }
+
+    #[tokio::test]
+    async fn raw_body_is_returned_alongside_parsed_value() {
+        #[derive(serde::Deserialize)]
+        struct Item {
+            #[serde(rename = "Code")]
+            code: String,
+        }
+
+        let client = test_support::client().await;
+        client.token_manager.set_token(&token("token")).await;
+        let server = MockServer::ok([r#"{"Code":"A1","NewField":true}"#]).await;
+
+        let api = ApiClient::new(
+            server.url(),
+            tenant_id(),
+            client.http_client.clone(),
+            client.token_manager.clone(),
+            client.rate_limiter.clone(),
+            None,
+            None,
+            None,
+        );
+        let (item, raw): (Item, _) = api
+            .send_request_with_raw(Method::GET, "/Items", None, None::<()>)
+            .await
+            .unwrap();
+        server.requests().await;
+
+        assert_eq!(item.code, "A1");
+        assert_eq!(raw["NewField"], serde_json::json!(true));
+    }
+
+    #[tokio::test]
+    async fn html_page_with_ok_status_is_reported_as_unexpected_content() {
+        let client = test_support::client().await;
+        client.token_manager.set_token(&token("token")).await;
+        let body = "<html><body>Xero is down for maintenance</body></html>";
+        let server = MockServer::start(vec![format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/html; charset=utf-8\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )])
+        .await;
+
+        let api = ApiClient::new(
+            server.url(),
+            tenant_id(),
+            client.http_client.clone(),
+            client.token_manager.clone(),
+            client.rate_limiter.clone(),
+            None,
+            None,
+            None,
+        );
+        let err = api
+            .send_request::<serde_json::Value, ()>(Method::GET, "/Items", None, None)
+            .await
+            .unwrap_err();
+        server.requests().await;
+
+        match err {
+            XeroError::UnexpectedContentType {
+                content_type,
+                body_snippet,
+            } => {
+                assert_eq!(content_type, "text/html; charset=utf-8");
+                assert!(body_snippet.contains("maintenance"));
+            }
+            other => panic!("expected UnexpectedContentType, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_content_types_are_recognised() {
+        assert!(is_json_content_type("application/json; charset=utf-8"));
+        assert!(is_json_content_type("Application/JSON"));
+        assert!(is_json_content_type("application/problem+json"));
+        assert!(!is_json_content_type("text/html"));
+    }
+
+    #[tokio::test]
+    async fn nil_tenant_is_rejected_before_sending() {
+        let client = test_support::client().await;
+        client.token_manager.set_token(&token("token")).await;
+
+        let api = ApiClient::new(
+            "http://xero.invalid",
+            Uuid::nil(),
+            client.http_client.clone(),
+            client.token_manager.clone(),
+            client.rate_limiter.clone(),
+            None,
+            None,
+            None,
+        );
+        let err = api
+            .send_request::<serde_json::Value, ()>(Method::GET, "/Items", None, None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, XeroError::InvalidTenant(id) if id.is_nil()),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn request_context_names_tenant_and_call() {
+        let tenant_id = Uuid::new_v4();
+        let first = RequestContext::new(tenant_id, Method::GET, "https://x/Items".to_string());
+        let second = RequestContext::new(tenant_id, Method::POST, "https://x/Items".to_string());
+        assert_ne!(first.id, second.id);
+        assert_eq!(
+            first.to_string(),
+            format!("[tenant {tenant_id} req {}] GET https://x/Items", first.id)
+        );
+    }
+
+    #[test]
+    fn deserialize_failure_reports_url_and_status() {
+        let url = "https://api.xero.com/api.xro/2.0/Invoices";
+        let ctx = RequestContext::new(Uuid::nil(), Method::GET, url.to_string());
+        let err = deserialize_json::<Vec<u32>>(&ctx, reqwest::StatusCode::OK, r#"{"Invoices":[]}"#)
+            .unwrap_err();
+        match &err {
+            XeroError::Deserialize {
+                url: failed_url,
+                status,
+                body,
+                ..
+            } => {
+                assert_eq!(failed_url, url);
+                assert_eq!(*status, reqwest::StatusCode::OK);
+                assert_eq!(body, r#"{"Invoices":[]}"#);
+            }
+            other => panic!("expected Deserialize error, got {other:?}"),
+        }
+        assert!(err.to_string().contains(url));
+    }
+}
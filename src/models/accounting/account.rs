@@ -1,8 +1,10 @@
 //! Model for the Account resource.
 
+use super::common::impl_xero_value;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -29,6 +31,8 @@ pub enum AccountType {
     Termliab,
 }
 
+impl_xero_value!(AccountType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum AccountStatus {
@@ -36,6 +40,8 @@ pub enum AccountStatus {
     Archived,
 }
 
+impl_xero_value!(AccountStatus);
+
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum BankAccountType {
@@ -45,6 +51,8 @@ pub enum BankAccountType {
     Unknown(String),
 }
 
+impl_xero_value!(BankAccountType);
+
 impl<'de> serde::Deserialize<'de> for BankAccountType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -112,8 +120,127 @@ pub enum AccountClass {
     Revenue,
 }
 
+impl_xero_value!(AccountClass);
+
+/// A Xero system account code, identifying control accounts such as debtors.
+///
+/// Codes this crate does not know are kept in [`SystemAccount::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SystemAccount {
+    Debtors,
+    Creditors,
+    BankCurrencyGain,
+    Gst,
+    GstOnImports,
+    Historical,
+    RealisedCurrencyGain,
+    RetainedEarnings,
+    Rounding,
+    TrackingTransfers,
+    UnpaidExpenseClaims,
+    UnrealisedCurrencyGain,
+    WagePayables,
+    CisAssets,
+    CisAsset,
+    CisLabour,
+    CisLabourExpense,
+    CisLabourIncome,
+    CisLiability,
+    CisMaterials,
+    Unknown(String),
+}
+
+impl SystemAccount {
+    /// Returns the code Xero uses for this system account.
+    #[must_use]
+    pub fn as_code(&self) -> &str {
+        match self {
+            Self::Debtors => "DEBTORS",
+            Self::Creditors => "CREDITORS",
+            Self::BankCurrencyGain => "BANKCURRENCYGAIN",
+            Self::Gst => "GST",
+            Self::GstOnImports => "GSTONIMPORTS",
+            Self::Historical => "HISTORICAL",
+            Self::RealisedCurrencyGain => "REALISEDCURRENCYGAIN",
+            Self::RetainedEarnings => "RETAINEDEARNINGS",
+            Self::Rounding => "ROUNDING",
+            Self::TrackingTransfers => "TRACKINGTRANSFERS",
+            Self::UnpaidExpenseClaims => "UNPAIDEXPCLM",
+            Self::UnrealisedCurrencyGain => "UNREALISEDCURRENCYGAIN",
+            Self::WagePayables => "WAGEPAYABLES",
+            Self::CisAssets => "CISASSETS",
+            Self::CisAsset => "CISASSET",
+            Self::CisLabour => "CISLABOUR",
+            Self::CisLabourExpense => "CISLABOUREXPENSE",
+            Self::CisLabourIncome => "CISLABOURINCOME",
+            Self::CisLiability => "CISLIABILITY",
+            Self::CisMaterials => "CISMATERIALS",
+            Self::Unknown(code) => code,
+        }
+    }
+
+    /// Parses a system account code, ignoring case.
+    #[must_use]
+    pub fn from_code(code: &str) -> Self {
+        match code.to_ascii_uppercase().as_str() {
+            "DEBTORS" => Self::Debtors,
+            "CREDITORS" => Self::Creditors,
+            "BANKCURRENCYGAIN" => Self::BankCurrencyGain,
+            "GST" => Self::Gst,
+            "GSTONIMPORTS" => Self::GstOnImports,
+            "HISTORICAL" => Self::Historical,
+            "REALISEDCURRENCYGAIN" => Self::RealisedCurrencyGain,
+            "RETAINEDEARNINGS" => Self::RetainedEarnings,
+            "ROUNDING" => Self::Rounding,
+            "TRACKINGTRANSFERS" => Self::TrackingTransfers,
+            "UNPAIDEXPCLM" => Self::UnpaidExpenseClaims,
+            "UNREALISEDCURRENCYGAIN" => Self::UnrealisedCurrencyGain,
+            "WAGEPAYABLES" => Self::WagePayables,
+            "CISASSETS" => Self::CisAssets,
+            "CISASSET" => Self::CisAsset,
+            "CISLABOUR" => Self::CisLabour,
+            "CISLABOUREXPENSE" => Self::CisLabourExpense,
+            "CISLABOURINCOME" => Self::CisLabourIncome,
+            "CISLIABILITY" => Self::CisLiability,
+            "CISMATERIALS" => Self::CisMaterials,
+            _ => Self::Unknown(code.to_string()),
+        }
+    }
+}
+
+impl Serialize for SystemAccount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_code())
+    }
+}
+
+impl<'de> Deserialize<'de> for SystemAccount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        Ok(Self::from_code(&code))
+    }
+}
+
+// Xero sends `"SystemAccount": ""` for ordinary accounts; treat that as absent.
+fn deserialize_system_account<'de, D>(deserializer: D) -> Result<Option<SystemAccount>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let code: Option<String> = Option::deserialize(deserializer)?;
+    Ok(code
+        .filter(|code| !code.trim().is_empty())
+        .map(|code| SystemAccount::from_code(code.trim())))
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Account {
     #[serde(rename = "AccountID", skip_serializing_if = "Option::is_none")]
     pub account_id: Option<Uuid>,
@@ -142,8 +269,12 @@ pub struct Account {
     pub show_in_expense_claims: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class: Option<AccountClass>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub system_account: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_system_account",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub system_account: Option<SystemAccount>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reporting_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -160,6 +291,65 @@ pub struct Account {
     pub add_to_watchlist: Option<bool>,
 }
 
+impl Account {
+    /// Returns `true` if this is one of Xero's system (control) accounts.
+    #[must_use]
+    pub fn is_system(&self) -> bool {
+        self.system_account.is_some()
+    }
+}
+
+/// A chart of accounts indexed by code and by `AccountID`.
+///
+/// Accounts without a code (bank accounts often have none) appear only in
+/// `by_id`, and accounts without an ID only in `by_code`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChartOfAccounts {
+    pub by_code: HashMap<String, Account>,
+    pub by_id: HashMap<Uuid, Account>,
+}
+
+impl ChartOfAccounts {
+    /// Indexes `accounts`.
+    #[must_use]
+    pub fn new(accounts: Vec<Account>) -> Self {
+        let mut chart = Self::default();
+        for account in accounts {
+            if let Some(code) = account.code.clone().filter(|code| !code.is_empty()) {
+                chart.by_code.insert(code, account.clone());
+            }
+            if let Some(account_id) = account.account_id {
+                chart.by_id.insert(account_id, account);
+            }
+        }
+        chart
+    }
+
+    /// Returns every bank account, including credit card and PayPal accounts.
+    #[must_use]
+    pub fn find_bank_accounts(&self) -> Vec<&Account> {
+        self.accounts()
+            .filter(|account| account.account_type == Some(AccountType::Bank))
+            .collect()
+    }
+
+    /// Returns the account acting as `system_account`, e.g. the debtors control account.
+    #[must_use]
+    pub fn system_account(&self, system_account: &SystemAccount) -> Option<&Account> {
+        self.accounts()
+            .find(|account| account.system_account.as_ref() == Some(system_account))
+    }
+
+    /// Iterates every account once, whether or not it has a code or ID.
+    fn accounts(&self) -> impl Iterator<Item = &Account> {
+        let coded_without_id = self
+            .by_code
+            .values()
+            .filter(|account| account.account_id.is_none());
+        self.by_id.values().chain(coded_without_id)
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -173,3 +363,67 @@ pub(crate) struct AccountsResponse {
 pub(crate) struct AccountsRequest {
     pub accounts: Vec<Account>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn account(system_account: serde_json::Value) -> Account {
+        serde_json::from_value(json!({ "Code": "610", "SystemAccount": system_account })).unwrap()
+    }
+
+    #[test]
+    fn system_account_codes_parse_leniently() {
+        assert_eq!(
+            account(json!("DEBTORS")).system_account,
+            Some(SystemAccount::Debtors)
+        );
+        assert_eq!(
+            account(json!("unpaidexpclm")).system_account,
+            Some(SystemAccount::UnpaidExpenseClaims)
+        );
+        assert_eq!(
+            account(json!("NEWCONTROL")).system_account,
+            Some(SystemAccount::Unknown("NEWCONTROL".to_string()))
+        );
+        assert!(account(json!("DEBTORS")).is_system());
+        assert!(!account(json!("")).is_system());
+        assert!(!account(json!(null)).is_system());
+
+        let missing: Account = serde_json::from_value(json!({ "Code": "200" })).unwrap();
+        assert!(!missing.is_system());
+    }
+
+    #[test]
+    fn system_account_serializes_as_code() {
+        let value = serde_json::to_value(account(json!("BankCurrencyGain"))).unwrap();
+        assert_eq!(value["SystemAccount"], "BANKCURRENCYGAIN");
+    }
+
+    #[test]
+    fn chart_indexes_by_code_and_id() {
+        let accounts: Vec<Account> = serde_json::from_value(json!([
+            { "AccountID": "00000000-0000-0000-0000-000000000001", "Code": "610",
+              "Type": "CURRLIAB", "SystemAccount": "DEBTORS" },
+            { "AccountID": "00000000-0000-0000-0000-000000000002", "Type": "BANK" },
+            { "AccountID": "00000000-0000-0000-0000-000000000003", "Code": "200", "Type": "REVENUE" }
+        ]))
+        .unwrap();
+        let chart = ChartOfAccounts::new(accounts);
+
+        assert_eq!(chart.by_code.len(), 2);
+        assert_eq!(chart.by_id.len(), 3);
+        assert_eq!(chart.by_code["200"].account_id, Some(Uuid::from_u128(3)));
+        let banks = chart.find_bank_accounts();
+        assert_eq!(banks.len(), 1);
+        assert_eq!(banks[0].account_id, Some(Uuid::from_u128(2)));
+        assert_eq!(
+            chart
+                .system_account(&SystemAccount::Debtors)
+                .and_then(|a| a.code.as_deref()),
+            Some("610")
+        );
+        assert!(chart.system_account(&SystemAccount::Creditors).is_none());
+    }
+}
@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Attachment {
     #[serde(rename = "AttachmentID")]
     pub attachment_id: Uuid,
@@ -20,6 +21,17 @@ pub struct Attachment {
     pub include_online: Option<bool>,
 }
 
+impl Attachment {
+    /// Returns `true` if Xero shows the file on the online invoice or credit note.
+    ///
+    /// Only an explicit `IncludeOnline: true` counts; Xero omits the flag for
+    /// attachments that are not online.
+    #[must_use]
+    pub fn is_online(&self) -> bool {
+        self.include_online == Some(true)
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -65,4 +77,23 @@ mod tests {
             serde_json::from_str(payload).expect("attachments response should deserialize");
         assert_eq!(parsed.attachments[0].content_length, Some(256_570));
     }
+
+    #[test]
+    fn deserializes_include_online() {
+        let payload = r#"{
+            "Attachments": [{
+                "AttachmentID": "44dc5d9e-7488-4db1-8185-177be8ab7c6f",
+                "FileName": "invoice.pdf",
+                "Url": "https://example.com/invoice.pdf",
+                "MimeType": "application/pdf",
+                "ContentLength": 10,
+                "IncludeOnline": true
+            }]
+        }"#;
+
+        let parsed: AttachmentsResponse =
+            serde_json::from_str(payload).expect("attachments response should deserialize");
+        assert_eq!(parsed.attachments[0].include_online, Some(true));
+        assert!(parsed.attachments[0].is_online());
+    }
 }
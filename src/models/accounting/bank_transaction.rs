@@ -4,9 +4,10 @@
 
 use super::account::Account;
 use super::batch_payment::BatchPayment;
-use super::common::{LineAmountType, LineItem};
+use super::common::{impl_xero_value, LineAmountType, LineItem};
 use super::contact::Contact;
 use super::payment::Payment; // FIX: Add this import
+use crate::error::{ApiValidationError, XeroError};
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -33,6 +34,17 @@ pub enum BankTransactionType {
     SpendTransfer,
 }
 
+impl_xero_value!(BankTransactionType);
+
+impl BankTransactionType {
+    /// Returns `true` for the `SPEND-TRANSFER`/`RECEIVE-TRANSFER` halves of a
+    /// bank transfer, which Xero creates itself and only reports on reads.
+    #[must_use]
+    pub fn is_transfer(&self) -> bool {
+        matches!(self, Self::SpendTransfer | Self::ReceiveTransfer)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum BankTransactionStatus {
@@ -40,6 +52,8 @@ pub enum BankTransactionStatus {
     Deleted,
 }
 
+impl_xero_value!(BankTransactionStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct BankTransaction {
@@ -53,6 +67,10 @@ pub struct BankTransaction {
     pub contact: Contact,
     pub line_items: Vec<LineItem>,
     pub bank_account: Account,
+    /// Whether the transaction is reconciled. Xero normally sets this by
+    /// matching a bank statement line; writing it is only honoured in limited
+    /// cases, chiefly conversions of transactions that have no statement line.
+    /// See `BankTransactionsResource::set_reconciled`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_reconciled: Option<bool>,
     #[serde(with = "xero_date_format")]
@@ -77,8 +95,10 @@ pub struct BankTransaction {
     pub total: Option<f64>,
     #[serde(rename = "BankTransactionID", skip_serializing_if = "Option::is_none")]
     pub bank_transaction_id: Option<Uuid>,
+    /// The prepayment this `*-PREPAYMENT` transaction created.
     #[serde(rename = "PrepaymentID", skip_serializing_if = "Option::is_none")]
     pub prepayment_id: Option<Uuid>,
+    /// The overpayment this `*-OVERPAYMENT` transaction created.
     #[serde(rename = "OverpaymentID", skip_serializing_if = "Option::is_none")]
     pub overpayment_id: Option<Uuid>,
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
@@ -90,6 +110,109 @@ pub struct BankTransaction {
     pub batch_payment: Option<Box<BatchPayment>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payments: Option<Vec<Box<Payment>>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiValidationError>,
+}
+
+impl BankTransaction {
+    /// Starts a [`BankTransactionBuilder`] for spend money out of `bank_account`.
+    pub fn spend(bank_account: Account, contact: Contact) -> BankTransactionBuilder {
+        BankTransactionBuilder::spend(bank_account, contact)
+    }
+
+    /// Starts a [`BankTransactionBuilder`] for receive money into `bank_account`.
+    pub fn receive(bank_account: Account, contact: Contact) -> BankTransactionBuilder {
+        BankTransactionBuilder::receive(bank_account, contact)
+    }
+}
+
+/// Builder for a new spend or receive money [`BankTransaction`].
+///
+/// Sets the transaction type for you. The bank account only needs its
+/// `account_id` or `code` set, and [`BankTransactionBuilder::build`] rejects
+/// one with neither, which Xero would report as "BankTransaction must have a
+/// bank account". The date defaults to now when not given.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct BankTransactionBuilder {
+    transaction: BankTransaction,
+}
+
+impl BankTransactionBuilder {
+    fn new(transaction_type: BankTransactionType, bank_account: Account, contact: Contact) -> Self {
+        Self {
+            transaction: BankTransaction {
+                transaction_type: Some(transaction_type),
+                bank_account,
+                contact,
+                date: Utc::now(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Creates a builder for money paid out of `bank_account` to `contact`.
+    pub fn spend(bank_account: Account, contact: Contact) -> Self {
+        Self::new(BankTransactionType::Spend, bank_account, contact)
+    }
+
+    /// Creates a builder for money paid into `bank_account` by `contact`.
+    pub fn receive(bank_account: Account, contact: Contact) -> Self {
+        Self::new(BankTransactionType::Receive, bank_account, contact)
+    }
+
+    /// Appends a line item.
+    pub fn line_item(mut self, line_item: LineItem) -> Self {
+        self.transaction.line_items.push(line_item);
+        self
+    }
+
+    /// Appends several line items.
+    pub fn line_items(mut self, line_items: impl IntoIterator<Item = LineItem>) -> Self {
+        self.transaction.line_items.extend(line_items);
+        self
+    }
+
+    /// Sets the transaction date.
+    pub fn date(mut self, date: DateTime<Utc>) -> Self {
+        self.transaction.date = date;
+        self
+    }
+
+    /// Sets the reference.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.transaction.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets whether line amounts include tax.
+    pub fn line_amount_types(mut self, line_amount_types: LineAmountType) -> Self {
+        self.transaction.line_amount_types = Some(line_amount_types);
+        self
+    }
+
+    /// Sets the currency code.
+    pub fn currency_code(mut self, currency_code: impl Into<String>) -> Self {
+        self.transaction.currency_code = Some(currency_code.into());
+        self
+    }
+
+    /// Returns the transaction, or [`XeroError::Validation`] if the bank
+    /// account has neither an ID nor a code, or there are no line items.
+    pub fn build(self) -> Result<BankTransaction, XeroError> {
+        let account = &self.transaction.bank_account;
+        if account.account_id.is_none() && account.code.as_deref().is_none_or(str::is_empty) {
+            return Err(XeroError::Validation(
+                "Bank transaction needs a bank account with an AccountID or Code".to_string(),
+            ));
+        }
+        if self.transaction.line_items.is_empty() {
+            return Err(XeroError::Validation(
+                "Bank transaction needs at least one line item".to_string(),
+            ));
+        }
+        Ok(self.transaction)
+    }
 }
 
 // Wrapper for the response
@@ -97,6 +220,8 @@ pub struct BankTransaction {
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct BankTransactionsResponse {
     pub bank_transactions: Vec<BankTransaction>,
+    #[serde(default)]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the request
@@ -105,3 +230,75 @@ pub(crate) struct BankTransactionsResponse {
 pub(crate) struct BankTransactionsRequest {
     pub bank_transactions: Vec<BankTransaction>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::accounting::common::Money;
+
+    fn bank(code: Option<&str>) -> Account {
+        Account {
+            code: code.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn contact() -> Contact {
+        Contact {
+            name: "Corner Cafe".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn coffee() -> LineItem {
+        LineItem {
+            description: Some("Coffee".to_string()),
+            account_code: Some("420".to_string()),
+            line_amount: Some(Money::from(5)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spend_and_receive_set_the_type() {
+        let spend = BankTransaction::spend(bank(Some("090")), contact())
+            .line_item(coffee())
+            .reference("June")
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&spend).unwrap();
+        assert_eq!(json["Type"], "SPEND");
+        assert_eq!(json["BankAccount"]["Code"], "090");
+        assert_eq!(json["LineItems"][0]["AccountCode"], "420");
+
+        let receive = BankTransactionBuilder::receive(bank(Some("090")), contact())
+            .line_item(coffee())
+            .build()
+            .unwrap();
+        assert_eq!(receive.transaction_type, Some(BankTransactionType::Receive));
+    }
+
+    #[test]
+    fn missing_bank_account_or_lines_are_rejected() {
+        for code in [None, Some("")] {
+            let err = BankTransaction::spend(bank(code), contact())
+                .line_item(coffee())
+                .build()
+                .unwrap_err();
+            assert!(matches!(err, XeroError::Validation(_)), "{err:?}");
+        }
+
+        let by_id = Account {
+            account_id: Some(Uuid::nil()),
+            ..Default::default()
+        };
+        assert!(BankTransaction::receive(by_id.clone(), contact())
+            .line_item(coffee())
+            .build()
+            .is_ok());
+        let err = BankTransaction::receive(by_id, contact())
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, XeroError::Validation(_)), "{err:?}");
+    }
+}
@@ -1,6 +1,7 @@
 //! Model for the `BankTransfer` resource.
 
 use super::account::Account;
+use super::bank_transaction::BankTransaction;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -29,6 +30,15 @@ pub struct BankTransfer {
         skip_serializing_if = "Option::is_none"
     )]
     pub to_bank_transaction_id: Option<Uuid>,
+    /// The spend side of the transfer, with its line items.
+    ///
+    /// Xero usually returns only `FromBankTransactionID`; use
+    /// `BankTransfersResource::get_with_transactions` to have both sides filled in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_bank_transaction: Option<Box<BankTransaction>>,
+    /// The receive side of the transfer, with its line items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_bank_transaction: Option<Box<BankTransaction>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub from_is_reconciled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -56,3 +66,28 @@ pub(crate) struct BankTransfersResponse {
 pub(crate) struct BankTransfersRequest {
     pub bank_transfers: Vec<BankTransfer>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_transactions_are_optional() {
+        let transfer: BankTransfer = serde_json::from_value(json!({
+            "BankTransferID": "6221458a-ef02-4a3b-8f4a-9a4a5a6a7a8a",
+            "FromBankAccount": { "AccountID": "ac993f75-035b-433c-82e0-7b7a2d40802c", "Name": "Business Bank Account" },
+            "ToBankAccount": { "AccountID": "ebd06280-af70-4bed-97c6-7451a454ad85", "Name": "Business Savings Account" },
+            "Amount": 20.0,
+            "Date": "/Date(1570060800000+0000)/",
+            "FromBankTransactionID": "9d8f3c2c-5c3a-4b0a-9b5e-1d2a3b4c5d6e",
+            "ToBankTransactionID": "1a2b3c4d-5e6f-4a0b-8c9d-0e1f2a3b4c5d"
+        }))
+        .unwrap();
+        assert!(transfer.from_bank_transaction.is_none());
+
+        let value = serde_json::to_value(&transfer).unwrap();
+        assert!(value.get("FromBankTransaction").is_none());
+        assert!(value.get("ToBankTransaction").is_none());
+    }
+}
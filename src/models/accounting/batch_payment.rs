@@ -1,7 +1,10 @@
 //! Model for the `BatchPayment` resource.
 
 use super::account::Account;
+use super::common::impl_xero_value;
 use super::invoice::Invoice;
+use super::payment::PaymentAccount;
+use crate::error::XeroError;
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -14,6 +17,8 @@ pub enum BatchPaymentType {
     Recbatch,
 }
 
+impl_xero_value!(BatchPaymentType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum BatchPaymentStatus {
@@ -21,6 +26,8 @@ pub enum BatchPaymentStatus {
     Deleted,
 }
 
+impl_xero_value!(BatchPaymentStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct BatchPayment {
@@ -54,6 +61,153 @@ pub struct BatchPayment {
     pub updated_date_utc: Option<DateTime<Utc>>,
 }
 
+impl BatchPayment {
+    /// Starts a [`BatchPaymentBuilder`] for a new batch payment.
+    pub fn builder() -> BatchPaymentBuilder {
+        BatchPaymentBuilder::default()
+    }
+}
+
+/// Builder for a new [`BatchPayment`] paying several invoices at once.
+///
+/// [`BatchPaymentBuilder::build`] rejects a batch without an account or
+/// without payments, and any payment with a nil invoice ID or a non-positive
+/// amount, all of which Xero would otherwise reject. The date defaults to now
+/// when not given.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct BatchPaymentBuilder {
+    account: Option<PaymentAccount>,
+    payments: Vec<(Uuid, f64)>,
+    date: Option<DateTime<Utc>>,
+    reference: Option<String>,
+    particulars: Option<String>,
+    code: Option<String>,
+    details: Option<String>,
+    narrative: Option<String>,
+}
+
+impl BatchPaymentBuilder {
+    /// Sets the bank account the batch is paid from or into.
+    pub fn account(mut self, account: PaymentAccount) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Adds a payment of `amount` against the invoice `invoice_id`.
+    pub fn payment(mut self, invoice_id: Uuid, amount: f64) -> Self {
+        self.payments.push((invoice_id, amount));
+        self
+    }
+
+    /// Sets the batch date.
+    pub fn date(mut self, date: DateTime<Utc>) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Sets the batch reference.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets the particulars shown on the bank statement (NZ only).
+    pub fn particulars(mut self, particulars: impl Into<String>) -> Self {
+        self.particulars = Some(particulars.into());
+        self
+    }
+
+    /// Sets the code shown on the bank statement (NZ only).
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the details shown on the bank statement.
+    pub fn details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    /// Sets the narrative (UK only).
+    pub fn narrative(mut self, narrative: impl Into<String>) -> Self {
+        self.narrative = Some(narrative.into());
+        self
+    }
+
+    /// Returns the batch payment, or [`XeroError::Validation`] if it has no
+    /// account, no payments, or a payment without an invoice or with an
+    /// amount that is not positive.
+    pub fn build(self) -> Result<BatchPayment, XeroError> {
+        let account = match self.account {
+            Some(PaymentAccount::Id(account_id)) => Account {
+                account_id: Some(account_id),
+                ..Default::default()
+            },
+            Some(PaymentAccount::Code(code)) if !code.is_empty() => Account {
+                code: Some(code),
+                ..Default::default()
+            },
+            _ => {
+                return Err(XeroError::Validation(
+                    "Batch payment needs an account with an AccountID or Code".to_string(),
+                ))
+            }
+        };
+        if self.payments.is_empty() {
+            return Err(XeroError::Validation(
+                "Batch payment needs at least one payment".to_string(),
+            ));
+        }
+
+        let mut payments = Vec::with_capacity(self.payments.len());
+        for (index, (invoice_id, amount)) in self.payments.into_iter().enumerate() {
+            if invoice_id.is_nil() {
+                return Err(XeroError::Validation(format!(
+                    "Batch payment {index} does not reference an invoice"
+                )));
+            }
+            if !(amount.is_finite() && amount > 0.0) {
+                return Err(XeroError::Validation(format!(
+                    "Batch payment {index} amount must be positive, got {amount}"
+                )));
+            }
+            payments.push(PaymentDetail {
+                invoice: Box::new(Invoice {
+                    invoice_id: Some(invoice_id),
+                    ..Default::default()
+                }),
+                amount,
+                payment_id: None,
+                bank_account_number: None,
+                particulars: None,
+                code: None,
+                reference: None,
+                details: None,
+                bank_amount: None,
+            });
+        }
+
+        Ok(BatchPayment {
+            account,
+            payments: Some(payments),
+            date: self.date.unwrap_or_else(Utc::now),
+            particulars: self.particulars,
+            code: self.code,
+            reference: self.reference,
+            details: self.details,
+            narrative: self.narrative,
+            batch_payment_id: None,
+            batch_payment_type: None,
+            status: None,
+            total_amount: None,
+            is_reconciled: None,
+            updated_date_utc: None,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct PaymentDetail {
@@ -81,3 +235,39 @@ pub struct PaymentDetail {
 pub(crate) struct BatchPaymentsResponse {
     pub batch_payments: Vec<BatchPayment>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_requires_account_and_positive_payments() {
+        let first = Uuid::new_v4();
+        let batch = BatchPayment::builder()
+            .account(PaymentAccount::Code("090".to_string()))
+            .payment(first, 120.0)
+            .payment(Uuid::new_v4(), 80.5)
+            .reference("March bills")
+            .build()
+            .unwrap();
+        assert_eq!(batch.account.code.as_deref(), Some("090"));
+        let payments = batch.payments.unwrap();
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[0].invoice.invoice_id, Some(first));
+        assert_eq!(payments[1].amount, 80.5);
+
+        let missing_account = BatchPayment::builder().payment(first, 10.0).build();
+        assert!(
+            matches!(missing_account, Err(XeroError::Validation(msg)) if msg.contains("account"))
+        );
+
+        let account = || BatchPayment::builder().account(PaymentAccount::Id(Uuid::new_v4()));
+        assert!(account().build().is_err());
+        assert!(matches!(
+            account().payment(first, 10.0).payment(Uuid::nil(), 5.0).build(),
+            Err(XeroError::Validation(msg)) if msg.contains("payment 1")
+        ));
+        assert!(account().payment(first, 0.0).build().is_err());
+        assert!(account().payment(first, f64::NAN).build().is_err());
+    }
+}
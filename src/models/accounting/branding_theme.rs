@@ -1,5 +1,6 @@
 //! Model for the `BrandingTheme` resource.
 
+use super::common::impl_xero_value;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -11,8 +12,11 @@ pub enum BrandingThemeType {
     Invoice,
 }
 
+impl_xero_value!(BrandingThemeType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct BrandingTheme {
     #[serde(rename = "BrandingThemeID")]
     pub branding_theme_id: Uuid,
@@ -1,8 +1,9 @@
 //! Model for the Budget resource.
 
+use super::common::impl_xero_value;
 use super::tracking_category::TrackingCategory;
 use crate::util::xero_date_format_opt;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -13,6 +14,8 @@ pub enum BudgetType {
     Tracking,
 }
 
+impl_xero_value!(BudgetType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Budget {
@@ -51,9 +54,40 @@ pub struct BudgetBalance {
     pub unit_amount: Option<f64>,
 }
 
+impl BudgetBalance {
+    /// The first day of the balance's month, or `None` if `period` is not `YYYY-MM`.
+    #[must_use]
+    pub fn period_start(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&format!("{}-01", self.period.trim()), "%Y-%m-%d").ok()
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct BudgetsResponse {
     pub budgets: Vec<Budget>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_periods_parse_as_month_starts() {
+        let line: BudgetLine = serde_json::from_value(serde_json::json!({
+            "AccountID": "00000000-0000-0000-0000-000000000001",
+            "AccountCode": "200",
+            "BudgetBalances": [
+                { "Period": "2024-03", "Amount": 100.0 },
+                { "Period": "March", "Amount": 5.0 }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            line.balances[0].period_start(),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+        assert_eq!(line.balances[1].period_start(), None);
+    }
+}
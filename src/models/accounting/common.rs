@@ -2,11 +2,81 @@
 
 //! Contains common data structures shared across multiple Accounting API endpoints.
 
+use crate::error::{ApiValidationError, ElementValidationError};
 use crate::util::xero_date_format;
 use chrono::{DateTime, Utc};
-use serde::{self, Deserialize, Deserializer, Serialize};
+use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
+/// The numeric type used for monetary amounts on the core transaction models
+/// (invoices, line items, payments and credit notes).
+///
+/// Defaults to `f64`. Enable the `decimal` feature to use
+/// [`rust_decimal::Decimal`] instead and avoid binary rounding error; amounts
+/// are still sent to and read from Xero as JSON numbers.
+#[cfg(not(feature = "decimal"))]
+pub type Money = f64;
+
+/// The numeric type used for monetary amounts on the core transaction models
+/// (invoices, line items, payments and credit notes).
+///
+/// The `decimal` feature is enabled, so amounts are [`rust_decimal::Decimal`];
+/// they are still sent to and read from Xero as JSON numbers.
+#[cfg(feature = "decimal")]
+pub type Money = rust_decimal::Decimal;
+
+/// Converts a rate or quantity to [`Money`] for arithmetic with amounts.
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn money_from_f64(value: f64) -> Money {
+    value
+}
+
+/// Converts a rate or quantity to [`Money`] for arithmetic with amounts.
+#[cfg(feature = "decimal")]
+pub(crate) fn money_from_f64(value: f64) -> Money {
+    Money::try_from(value).unwrap_or_default()
+}
+
+/// Rounds to whole cents, halves away from zero, as Xero does.
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn round_cents(amount: Money) -> Money {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Rounds to whole cents, halves away from zero, as Xero does.
+#[cfg(feature = "decimal")]
+pub(crate) fn round_cents(amount: Money) -> Money {
+    amount.round_dp_with_strategy(2, rust_decimal::RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Implements `as_xero_value` and `Display` for an enum from its serde
+/// representation, so the bare token (e.g. `AUTHORISED`) can be used in
+/// `where` filters without hand-written string tables.
+macro_rules! impl_xero_value {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            impl $name {
+                /// Returns the bare token Xero uses for this value, e.g. in `where` filters.
+                #[must_use]
+                pub fn as_xero_value(&self) -> String {
+                    match serde_json::to_value(self) {
+                        Ok(serde_json::Value::String(value)) => value,
+                        Ok(other) => other.to_string(),
+                        Err(_) => format!("{self:?}"),
+                    }
+                }
+            }
+
+            impl std::fmt::Display for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(&self.as_xero_value())
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_xero_value;
+
 // --- Enums from "Types and Codes" ---
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
@@ -18,6 +88,8 @@ pub enum AddressType {
     Delivery,
 }
 
+impl_xero_value!(AddressType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PhoneType {
@@ -29,6 +101,8 @@ pub enum PhoneType {
     Office,
 }
 
+impl_xero_value!(PhoneType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PaymentTermType {
@@ -38,14 +112,33 @@ pub enum PaymentTermType {
     Offollowingmonth,
 }
 
-#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+impl_xero_value!(PaymentTermType);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LineAmountType {
     Exclusive,
     Inclusive,
     NoTax,
+    /// Returned by some endpoints in place of `NoTax`; written as `NoTax`.
     None,
 }
 
+impl_xero_value!(LineAmountType);
+
+// Xero accepts only "Exclusive", "Inclusive" and "NoTax" on write.
+impl Serialize for LineAmountType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            LineAmountType::Exclusive => "Exclusive",
+            LineAmountType::Inclusive => "Inclusive",
+            LineAmountType::NoTax | LineAmountType::None => "NoTax",
+        })
+    }
+}
+
 // Custom deserializer to handle both "Exclusive" and "EXCLUSIVE" etc.
 impl<'de> Deserialize<'de> for LineAmountType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -68,6 +161,45 @@ impl<'de> Deserialize<'de> for LineAmountType {
 
 // --- Shared Structs ---
 
+/// The records returned by a create call together with the response-level
+/// `Warnings` Xero sent alongside them.
+///
+/// Warnings about a specific record are on that record's `warnings` field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithWarnings<T> {
+    pub items: Vec<T>,
+    pub warnings: Vec<ApiValidationError>,
+}
+
+/// A record that Xero can reject individually within a batch.
+pub trait BatchRecord {
+    /// The `ValidationErrors` Xero attached to this record.
+    fn validation_errors(&self) -> &[ApiValidationError];
+}
+
+/// Pairs each rejected record in a batch response with its position and
+/// messages, one entry per message.
+///
+/// With `summarizeErrors=false` Xero saves the valid records and returns the
+/// others with `ValidationErrors` set (including errors about nested parts,
+/// such as a line item's tracking option). Records come back in the order
+/// they were sent, so `index` lines up with the submitted `Vec`.
+pub fn collect_validation_errors<T: BatchRecord>(records: &[T]) -> Vec<ElementValidationError> {
+    records
+        .iter()
+        .enumerate()
+        .flat_map(|(index, record)| {
+            record
+                .validation_errors()
+                .iter()
+                .map(move |e| ElementValidationError {
+                    index,
+                    message: e.message.clone(),
+                })
+        })
+        .collect()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct Address {
@@ -116,18 +248,56 @@ pub struct PaymentTerm {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Link {
+    /// What the link points at. Set on organisation external links.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<LinkType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
+impl Link {
+    /// Returns the link's type, falling back to a description naming one
+    /// (e.g. `"website"`) when `LinkType` is absent.
+    #[must_use]
+    pub fn kind(&self) -> Option<LinkType> {
+        self.link_type.clone().or_else(|| {
+            let description = self.description.as_deref()?.trim();
+            [
+                LinkType::Website,
+                LinkType::Facebook,
+                LinkType::LinkedIn,
+                LinkType::Twitter,
+                LinkType::GooglePlus,
+            ]
+            .into_iter()
+            .find(|kind| kind.as_xero_value().eq_ignore_ascii_case(description))
+        })
+    }
+}
+
+/// The kind of site an external link points at.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum LinkType {
+    Website,
+    Facebook,
+    LinkedIn,
+    Twitter,
+    GooglePlus,
+    /// A link type this crate does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+impl_xero_value!(LinkType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Allocation {
     #[serde(rename = "AllocationID", skip_serializing_if = "Option::is_none")]
     pub allocation_id: Option<Uuid>,
-    pub amount: f64,
+    pub amount: Money,
     #[serde(with = "xero_date_format")]
     pub date: DateTime<Utc>,
     pub invoice: InvoiceSummary,
@@ -154,7 +324,7 @@ pub struct LineItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quantity: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub unit_amount: Option<f64>,
+    pub unit_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub item_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -164,13 +334,13 @@ pub struct LineItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tax_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tax_amount: Option<f64>,
+    pub tax_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub line_amount: Option<f64>,
+    pub line_amount: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discount_rate: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub discount_amount: Option<f64>,
+    pub discount_amount: Option<Money>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tracking: Vec<TrackingCategory>,
 }
@@ -186,3 +356,24 @@ pub struct TrackingCategory {
     #[serde(rename = "TrackingOptionID", skip_serializing_if = "Option::is_none")]
     pub tracking_option_id: Option<Uuid>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_amount_types_serialize_to_the_tokens_xero_accepts() {
+        let cases = [
+            (LineAmountType::Exclusive, "Exclusive"),
+            (LineAmountType::Inclusive, "Inclusive"),
+            (LineAmountType::NoTax, "NoTax"),
+            (LineAmountType::None, "NoTax"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(serde_json::to_value(&value).unwrap(), expected);
+            assert_eq!(value.as_xero_value(), expected);
+        }
+        let parsed: LineAmountType = serde_json::from_str(r#""NONE""#).unwrap();
+        assert_eq!(parsed, LineAmountType::None);
+    }
+}
@@ -1,7 +1,10 @@
 //! Model for the Contact resource.
 
-use super::common::{Address, LineAmountType, PaymentTerm, Phone, TrackingCategory};
+use super::common::{
+    impl_xero_value, Address, BatchRecord, LineAmountType, PaymentTerm, Phone, TrackingCategory,
+};
 use super::contact_group::ContactGroup;
+use crate::error::ApiValidationError;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -15,6 +18,8 @@ pub enum ContactStatus {
     Gdprrequest,
 }
 
+impl_xero_value!(ContactStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct Contact {
@@ -56,6 +61,10 @@ pub struct Contact {
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
+    /// Additional people at the contact. Writing this replaces the whole list;
+    /// see [`ContactsResource::add_person`] to add one.
+    ///
+    /// [`ContactsResource::add_person`]: crate::api::accounting::contacts::ContactsResource::add_person
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub contact_persons: Vec<ContactPerson>,
     #[serde(rename = "XeroNetworkKey", skip_serializing_if = "Option::is_none")]
@@ -86,10 +95,109 @@ pub struct Contact {
     pub batch_payments: Option<BatchPaymentDetails>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discount: Option<f64>,
+    /// Outstanding and overdue AR/AP amounts.
+    ///
+    /// Xero only returns this on a single-contact GET; it is `None` in list
+    /// responses. See `ContactsResource::balances`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub balances: Option<ContactBalances>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_attachments: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiValidationError>,
+    /// `OK`, `WARNING` or `ERROR` on records returned from a batch create
+    /// or update sent with `summarizeErrors=false`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_attribute_string: Option<String>,
+    /// Why Xero rejected this record, on a batch sent with `summarizeErrors=false`.
+    /// See [`collect_validation_errors`](super::common::collect_validation_errors).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validation_errors: Vec<ApiValidationError>,
+}
+
+impl BatchRecord for Contact {
+    fn validation_errors(&self) -> &[ApiValidationError] {
+        &self.validation_errors
+    }
+}
+
+/// The outcome of an upsert keyed by account number, split by what Xero did
+/// with each contact.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UpsertedContacts {
+    pub created: Vec<Contact>,
+    pub updated: Vec<Contact>,
+}
+
+/// A partial update for a [`Contact`].
+///
+/// Only fields that are `Some` are serialized, so Xero leaves every other field
+/// untouched. Scalar fields are merged. Collection fields (`addresses`, `phones`,
+/// `contact_persons`) are **replaced wholesale** by Xero: setting one to
+/// `Some(vec)` overwrites the contact's entire collection, so include every
+/// entry you want to keep. Leave them `None` to preserve the existing entries.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContactPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_status: Option<ContactStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account_details: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts_receivable_tax_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts_payable_tax_type: Option<String>,
+    /// Replaces all of the contact's addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Vec<Address>>,
+    /// Replaces all of the contact's phone numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phones: Option<Vec<Phone>>,
+    /// Replaces all of the contact's additional persons.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_persons: Option<Vec<ContactPerson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sales_default_account_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purchases_default_account_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_terms: Option<PaymentTerms>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discount: Option<f64>,
+}
+
+impl ContactPatch {
+    /// Returns `true` if no field is set, i.e. the patch would change nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` if the patch replaces any collection field wholesale.
+    #[must_use]
+    pub fn replaces_collections(&self) -> bool {
+        self.addresses.is_some() || self.phones.is_some() || self.contact_persons.is_some()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
@@ -155,6 +263,8 @@ pub struct CISSettings {
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct ContactsResponse {
     pub contacts: Vec<Contact>,
+    #[serde(default)]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the request
@@ -179,3 +289,49 @@ pub(crate) struct CISSettingsResponse {
 pub(crate) struct CISSettingsRequest {
     pub cis_settings: CISSettings,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contact_patch_serializes_only_set_fields() {
+        let patch = ContactPatch {
+            email_address: Some("ap@example.com".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&patch).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "EmailAddress": "ap@example.com" })
+        );
+        assert!(!patch.replaces_collections());
+    }
+
+    #[test]
+    fn contact_patch_sends_explicit_empty_collection() {
+        let patch = ContactPatch {
+            phones: Some(Vec::new()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&patch).unwrap();
+        assert_eq!(json, serde_json::json!({ "Phones": [] }));
+        assert!(patch.replaces_collections());
+    }
+
+    #[test]
+    fn deserializes_balances() {
+        let contact: Contact = serde_json::from_value(serde_json::json!({
+            "ContactID": "bd2270c3-8706-4c11-9cfb-000b551c3f51",
+            "Name": "ABC Limited",
+            "Balances": {
+                "AccountsReceivable": { "Outstanding": 760.0, "Overdue": 920.0 },
+                "AccountsPayable": { "Outstanding": 231.6, "Overdue": 360.0 }
+            }
+        }))
+        .unwrap();
+        let balances = contact.balances.unwrap();
+        assert_eq!(balances.accounts_receivable.outstanding, 760.0);
+        assert_eq!(balances.accounts_payable.overdue, 360.0);
+    }
+}
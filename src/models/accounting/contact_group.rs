@@ -1,5 +1,6 @@
 //! Model for the `ContactGroup` resource.
 
+use super::common::impl_xero_value;
 use super::contact::Contact;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -11,6 +12,8 @@ pub enum ContactGroupStatus {
     Deleted,
 }
 
+impl_xero_value!(ContactGroupStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ContactGroup {
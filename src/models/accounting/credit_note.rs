@@ -1,7 +1,8 @@
 //! Model for the `CreditNote` resource.
 
-use super::common::{Allocation, LineAmountType, LineItem};
+use super::common::{impl_xero_value, Allocation, LineAmountType, LineItem, Money};
 use super::contact::Contact;
+use crate::error::ApiValidationError;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,8 @@ pub enum CreditNoteType {
     AccountsReceivable,
 }
 
+impl_xero_value!(CreditNoteType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CreditNoteStatus {
@@ -27,6 +30,8 @@ pub enum CreditNoteStatus {
     Voided,
 }
 
+impl_xero_value!(CreditNoteStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct CreditNote {
@@ -45,13 +50,13 @@ pub struct CreditNote {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub line_items: Vec<LineItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sub_total: Option<f64>,
+    pub sub_total: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_tax: Option<f64>,
+    pub total_tax: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total: Option<f64>,
+    pub total: Option<Money>,
     #[serde(rename = "CISDeduction", skip_serializing_if = "Option::is_none")]
-    pub cis_deduction: Option<f64>,
+    pub cis_deduction: Option<Money>,
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
@@ -71,13 +76,19 @@ pub struct CreditNote {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_rate: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub remaining_credit: Option<f64>,
+    pub remaining_credit: Option<Money>,
+    /// How much of the credit note was applied to the invoice it is nested
+    /// in. Only set on the credit notes listed on an invoice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_amount: Option<Money>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allocations: Vec<Allocation>,
     #[serde(rename = "BrandingThemeID", skip_serializing_if = "Option::is_none")]
     pub branding_theme_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_attachments: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the response
@@ -85,6 +96,8 @@ pub struct CreditNote {
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct CreditNotesResponse {
     pub credit_notes: Vec<CreditNote>,
+    #[serde(default)]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the request
@@ -100,3 +113,50 @@ pub(crate) struct CreditNotesRequest {
 pub(crate) struct AllocationsResponse {
     pub allocations: Vec<Allocation>,
 }
+
+// Response to deleting an allocation: the deleted allocation (with
+// `IsDeleted: true`), either bare or wrapped in an `Allocations` array.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DeletedAllocationResponse {
+    Wrapped(AllocationsResponse),
+    Bare(Allocation),
+}
+
+impl DeletedAllocationResponse {
+    pub(crate) fn into_allocation(self) -> Option<Allocation> {
+        match self {
+            Self::Wrapped(resp) => resp.allocations.into_iter().next(),
+            Self::Bare(allocation) => Some(allocation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn allocation_json() -> serde_json::Value {
+        json!({
+            "AllocationID": "b0e8a5ad-5a46-4c5c-9a18-1e7c53a1b3e3",
+            "Amount": 25.0,
+            "Date": "/Date(1709251200000+0000)/",
+            "Invoice": { "InvoiceID": "3c7b6e2f-0a4e-4b8e-9d8e-0a9c2b0f1d11" },
+            "IsDeleted": true
+        })
+    }
+
+    #[test]
+    fn deleted_allocation_parses_bare_or_wrapped() {
+        for body in [
+            allocation_json(),
+            json!({ "Allocations": [allocation_json()] }),
+        ] {
+            let resp: DeletedAllocationResponse = serde_json::from_value(body).unwrap();
+            let allocation = resp.into_allocation().unwrap();
+            assert_eq!(allocation.is_deleted, Some(true));
+            assert_eq!(allocation.amount, Money::from(25));
+        }
+    }
+}
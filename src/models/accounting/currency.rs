@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Currency {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
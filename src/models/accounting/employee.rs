@@ -1,5 +1,5 @@
 //! Model for the Employee resource.
-use super::common::Link;
+use super::common::{impl_xero_value, Link};
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -12,6 +12,8 @@ pub enum EmployeeStatus {
     Archived,
 }
 
+impl_xero_value!(EmployeeStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Employee {
@@ -1,4 +1,5 @@
 //! Model for the `ExpenseClaim` resource.
+use super::common::impl_xero_value;
 use super::payment::Payment;
 use super::receipt::Receipt;
 use super::user::User;
@@ -17,13 +18,19 @@ pub enum ExpenseClaimStatus {
     Deleted,
 }
 
+impl_xero_value!(ExpenseClaimStatus);
+
+/// An expense claim grouping one or more receipts submitted by a user.
+///
+/// Expense claims are deprecated by Xero but existing organisations still return them.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ExpenseClaim {
     #[serde(rename = "ExpenseClaimID", skip_serializing_if = "Option::is_none")]
     pub expense_claim_id: Option<Uuid>,
     pub user: User,
-    pub receipts: Vec<Box<Receipt>>,
+    #[serde(default)]
+    pub receipts: Vec<Receipt>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<ExpenseClaimStatus>,
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
@@ -45,6 +52,17 @@ pub struct ExpenseClaim {
     pub payments: Option<Vec<Box<Payment>>>,
 }
 
+impl ExpenseClaim {
+    /// The IDs of the receipts on this claim, in order.
+    #[must_use]
+    pub fn receipt_ids(&self) -> Vec<Uuid> {
+        self.receipts
+            .iter()
+            .filter_map(|receipt| receipt.receipt_id)
+            .collect()
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -58,3 +76,67 @@ pub(crate) struct ExpenseClaimsResponse {
 pub(crate) struct ExpenseClaimsRequest {
     pub expense_claims: Vec<ExpenseClaim>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn claim_json() -> serde_json::Value {
+        json!({
+            "ExpenseClaimID": "646b15ab-b874-4e13-82ae-f4385b2ac4b6",
+            "Status": "AUTHORISED",
+            "UpdatedDateUTC": "/Date(1700000000000+0000)/",
+            "User": {
+                "UserID": "d1164823-0ac1-41ad-987b-b4e30fe0b273",
+                "FirstName": "John",
+                "LastName": "Smith"
+            },
+            "Receipts": [{
+                "ReceiptID": "dc9c6c7a-3e14-4d1e-a9b3-7e4b2e4f8d5c",
+                "ReceiptNumber": 1,
+                "Status": "AUTHORISED",
+                "User": {
+                    "UserID": "d1164823-0ac1-41ad-987b-b4e30fe0b273",
+                    "FirstName": "John",
+                    "LastName": "Smith"
+                },
+                "Date": "/Date(1699920000000+0000)/",
+                "SubTotal": 40.0,
+                "TotalTax": 0.0,
+                "Total": 40.0
+            }],
+            "Total": 40.0,
+            "AmountDue": 40.0,
+            "AmountPaid": 0.0,
+            "PaymentDueDate": "/Date(1701993600000+0000)/",
+            "ReportingDate": "/Date(1700000000000+0000)/"
+        })
+    }
+
+    #[test]
+    fn claim_with_summary_receipts_parses() {
+        let claim: ExpenseClaim = serde_json::from_value(claim_json()).unwrap();
+
+        assert_eq!(claim.status, Some(ExpenseClaimStatus::Authorised));
+        assert_eq!(claim.user.first_name, "John");
+        assert_eq!(claim.amount_due, Some(40.0));
+        assert_eq!(claim.amount_paid, Some(0.0));
+        assert!(claim.payment_due_date.is_some());
+        assert_eq!(claim.receipts.len(), 1);
+        assert_eq!(claim.receipts[0].receipt_number, Some(1));
+        assert!(claim.receipts[0].line_items.is_empty());
+        assert_eq!(
+            claim.receipt_ids(),
+            vec![Uuid::parse_str("dc9c6c7a-3e14-4d1e-a9b3-7e4b2e4f8d5c").unwrap()]
+        );
+    }
+
+    #[test]
+    fn claim_round_trips() {
+        let claim: ExpenseClaim = serde_json::from_value(claim_json()).unwrap();
+        let reparsed: ExpenseClaim =
+            serde_json::from_value(serde_json::to_value(&claim).unwrap()).unwrap();
+        assert_eq!(claim, reparsed);
+    }
+}
@@ -1,10 +1,15 @@
 //! Model for the Invoice resource.
-use super::common::{Address, LineAmountType, LineItem};
+use super::common::{
+    impl_xero_value, money_from_f64, round_cents, Address, BatchRecord, LineAmountType, LineItem,
+    Money,
+};
 use super::contact::Contact;
 use super::credit_note::CreditNote;
 use super::overpayment::Overpayment;
-use super::payment::Payment;
+use super::payment::{Payment, PaymentStatus};
 use super::prepayment::Prepayment;
+use super::tax_rate::TaxRate;
+use crate::error::ApiValidationError;
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -18,6 +23,8 @@ pub enum InvoiceType {
     Accrec,
 }
 
+impl_xero_value!(InvoiceType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum InvoiceStatus {
@@ -29,6 +36,8 @@ pub enum InvoiceStatus {
     Voided,
 }
 
+impl_xero_value!(InvoiceStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct Invoice {
@@ -72,6 +81,8 @@ pub struct Invoice {
     pub repeating_invoice_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_attachments: Option<bool>,
+    /// Payments applied to the invoice. Only populated when the invoice is
+    /// fetched by ID; list and `summaryOnly` responses omit it.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payments: Option<Vec<Box<Payment>>>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -81,29 +92,295 @@ pub struct Invoice {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub overpayments: Vec<Box<Overpayment>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amount_due: Option<f64>,
+    pub amount_due: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amount_paid: Option<f64>,
+    pub amount_paid: Option<Money>,
     #[serde(with = "xero_date_format_opt", default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fully_paid_on_date: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amount_credited: Option<f64>,
+    pub amount_credited: Option<Money>,
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sub_total: Option<f64>,
+    pub sub_total: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_tax: Option<f64>,
+    pub total_tax: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total: Option<f64>,
+    pub total: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_discount: Option<f64>,
+    pub total_discount: Option<Money>,
     #[serde(rename = "CISDeduction", skip_serializing_if = "Option::is_none")]
-    pub cis_deduction: Option<f64>,
+    pub cis_deduction: Option<Money>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub invoice_addresses: Vec<Address>,
+    /// The customer-facing "view online" link. Xero does not return this on
+    /// the invoice itself; it is filled in by `InvoicesResource::ensure_online_url`.
+    #[serde(skip)]
+    pub online_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiValidationError>,
+    /// `OK`, `WARNING` or `ERROR` on records returned from a batch create
+    /// or update sent with `summarizeErrors=false`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_attribute_string: Option<String>,
+    /// Why Xero rejected this record, on a batch sent with `summarizeErrors=false`.
+    /// See [`collect_validation_errors`](super::common::collect_validation_errors).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validation_errors: Vec<ApiValidationError>,
+}
+
+impl BatchRecord for Invoice {
+    fn validation_errors(&self) -> &[ApiValidationError] {
+        &self.validation_errors
+    }
+}
+
+impl Invoice {
+    /// Starts an [`InvoiceBuilder`] for a new invoice of `invoice_type` to `contact`.
+    pub fn builder(invoice_type: InvoiceType, contact: Contact) -> InvoiceBuilder {
+        InvoiceBuilder::new(invoice_type, contact)
+    }
+
+    /// Sums the amounts of the non-deleted payments in [`Invoice::payments`].
+    ///
+    /// Returns zero when `payments` was not included in the response, so use an
+    /// invoice fetched by ID (see `InvoicesResource::get_with_payments`).
+    #[must_use]
+    pub fn total_paid(&self) -> Money {
+        self.payments
+            .iter()
+            .flatten()
+            .filter(|payment| payment.status != Some(PaymentStatus::Deleted))
+            .map(|payment| payment.amount)
+            .sum()
+    }
+
+    /// Splits what has been applied against the invoice by where it came from.
+    ///
+    /// Xero's `AmountPaid` covers payments, prepayments and overpayments, and
+    /// `AmountCredited` covers credit notes; this reports each source
+    /// separately. Like [`Invoice::total_paid`], it needs the detailed invoice
+    /// fetched by ID, as list responses leave the arrays out.
+    #[must_use]
+    pub fn applied_breakdown(&self) -> AppliedBreakdown {
+        AppliedBreakdown {
+            direct_payments: self.total_paid(),
+            credit_notes_applied: self
+                .credit_notes
+                .iter()
+                .filter_map(|credit_note| credit_note.applied_amount)
+                .sum(),
+            prepayments_applied: self
+                .prepayments
+                .iter()
+                .filter_map(|prepayment| prepayment.applied_amount)
+                .map(money_from_f64)
+                .sum(),
+            overpayments_applied: self
+                .overpayments
+                .iter()
+                .filter_map(|overpayment| overpayment.applied_amount)
+                .map(money_from_f64)
+                .sum(),
+        }
+    }
+
+    /// Works out the sub-total, tax and total Xero would show for the line
+    /// items, without sending the invoice.
+    ///
+    /// Each line is `quantity × unit_amount` (or its `line_amount` when no
+    /// unit amount is set), less `discount_amount` or `discount_rate`, rounded
+    /// to cents. Tax is taken from the rate in `tax_rates` whose `tax_type`
+    /// matches the line and rounded per line: added on top for
+    /// [`LineAmountType::Exclusive`], backed out of the line amount for
+    /// [`LineAmountType::Inclusive`], and zero for `NoTax`.
+    ///
+    /// The result can be a cent off Xero's when the organisation rounds tax
+    /// on the invoice total rather than per line, or when a compound rate has
+    /// no `effective_rate` (its components are then simply added together).
+    /// Lines whose tax type is missing from `tax_rates` are taxed at zero and
+    /// listed in [`ComputedTotals::unknown_tax_types`].
+    #[must_use]
+    pub fn compute_totals(
+        &self,
+        tax_rates: &[TaxRate],
+        line_amount_type: LineAmountType,
+    ) -> ComputedTotals {
+        let hundred = Money::from(100);
+        let mut totals = ComputedTotals::default();
+        for line in &self.line_items {
+            let (gross, line_amount) = match line.unit_amount {
+                Some(unit_amount) => {
+                    let gross = unit_amount * money_from_f64(line.quantity.unwrap_or(1.0));
+                    let net = match (line.discount_amount, line.discount_rate) {
+                        (Some(discount), _) => gross - discount,
+                        (None, Some(rate)) => gross * (hundred - money_from_f64(rate)) / hundred,
+                        (None, None) => gross,
+                    };
+                    (round_cents(gross), round_cents(net))
+                }
+                None => {
+                    let amount = round_cents(line.line_amount.unwrap_or_default());
+                    (amount, amount)
+                }
+            };
+            totals.total_discount += gross - line_amount;
+
+            let rate = match (&line_amount_type, line.tax_type.as_deref()) {
+                (LineAmountType::NoTax | LineAmountType::None, _) | (_, None) => Money::default(),
+                (_, Some(tax_type)) => {
+                    match tax_rates
+                        .iter()
+                        .find(|r| r.tax_type.as_deref() == Some(tax_type))
+                    {
+                        Some(tax_rate) => {
+                            money_from_f64(tax_rate.effective_rate.unwrap_or_else(|| {
+                                tax_rate.tax_components.iter().map(|c| c.rate).sum()
+                            }))
+                        }
+                        None => {
+                            if !totals.unknown_tax_types.iter().any(|t| t == tax_type) {
+                                totals.unknown_tax_types.push(tax_type.to_string());
+                            }
+                            Money::default()
+                        }
+                    }
+                }
+            };
+
+            let (sub_total, tax) = match line_amount_type {
+                LineAmountType::Inclusive => {
+                    let tax = round_cents(line_amount * rate / (hundred + rate));
+                    (line_amount - tax, tax)
+                }
+                _ => (line_amount, round_cents(line_amount * rate / hundred)),
+            };
+            totals.sub_total += sub_total;
+            totals.total_tax += tax;
+        }
+        totals.total = totals.sub_total + totals.total_tax;
+        totals
+    }
+}
+
+/// The amounts applied to an invoice by source, from [`Invoice::applied_breakdown`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AppliedBreakdown {
+    /// Non-deleted payments.
+    pub direct_payments: Money,
+    pub credit_notes_applied: Money,
+    pub prepayments_applied: Money,
+    pub overpayments_applied: Money,
+}
+
+impl AppliedBreakdown {
+    /// Everything applied, which Xero deducts from the total to get `AmountDue`.
+    #[must_use]
+    pub fn total(&self) -> Money {
+        self.direct_payments
+            + self.credit_notes_applied
+            + self.prepayments_applied
+            + self.overpayments_applied
+    }
+}
+
+/// Totals worked out locally by [`Invoice::compute_totals`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComputedTotals {
+    /// The sum of the lines excluding tax.
+    pub sub_total: Money,
+    pub total_tax: Money,
+    pub total: Money,
+    /// The sum of line discounts.
+    pub total_discount: Money,
+    /// Tax types used on lines but not found in the given tax rates.
+    pub unknown_tax_types: Vec<String>,
+}
+
+/// Builder for a new [`Invoice`] that takes the fields Xero requires up front.
+///
+/// The contact only needs its `contact_id` (or `name`) set. The date defaults
+/// to now when not given.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct InvoiceBuilder {
+    invoice: Invoice,
+}
+
+impl InvoiceBuilder {
+    /// Creates a builder for an invoice of `invoice_type` to `contact`.
+    pub fn new(invoice_type: InvoiceType, contact: Contact) -> Self {
+        Self {
+            invoice: Invoice {
+                invoice_type,
+                contact,
+                date: Utc::now(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Appends a line item.
+    pub fn line_item(mut self, line_item: LineItem) -> Self {
+        self.invoice.line_items.push(line_item);
+        self
+    }
+
+    /// Appends several line items.
+    pub fn line_items(mut self, line_items: impl IntoIterator<Item = LineItem>) -> Self {
+        self.invoice.line_items.extend(line_items);
+        self
+    }
+
+    /// Sets the invoice date.
+    pub fn date(mut self, date: DateTime<Utc>) -> Self {
+        self.invoice.date = date;
+        self
+    }
+
+    /// Sets the due date.
+    pub fn due_date(mut self, due_date: DateTime<Utc>) -> Self {
+        self.invoice.due_date = Some(due_date);
+        self
+    }
+
+    /// Sets the status. Xero creates invoices as `DRAFT` when none is given.
+    pub fn status(mut self, status: InvoiceStatus) -> Self {
+        self.invoice.status = Some(status);
+        self
+    }
+
+    /// Sets whether line amounts include tax.
+    pub fn line_amount_types(mut self, line_amount_types: LineAmountType) -> Self {
+        self.invoice.line_amount_types = Some(line_amount_types);
+        self
+    }
+
+    /// Sets the invoice number. Xero assigns one for `ACCREC` invoices otherwise.
+    pub fn invoice_number(mut self, invoice_number: impl Into<String>) -> Self {
+        self.invoice.invoice_number = Some(invoice_number.into());
+        self
+    }
+
+    /// Sets the reference.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.invoice.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets the currency code.
+    pub fn currency_code(mut self, currency_code: impl Into<String>) -> Self {
+        self.invoice.currency_code = Some(currency_code.into());
+        self
+    }
+
+    /// Returns the invoice.
+    #[must_use]
+    pub fn build(self) -> Invoice {
+        self.invoice
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -112,11 +389,104 @@ pub struct OnlineInvoice {
     pub online_invoice_url: String,
 }
 
+/// The headline fields of an invoice, read from a `summaryOnly=true` list.
+///
+/// Summary responses leave out line items, payments, credit notes,
+/// prepayments and overpayments, which keeps large pulls for list views and
+/// aging screens small.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(from = "InvoiceHeaderRecord")]
+pub struct InvoiceHeader {
+    pub invoice_id: Uuid,
+    pub invoice_number: Option<String>,
+    pub invoice_type: InvoiceType,
+    pub contact_id: Option<Uuid>,
+    pub contact_name: String,
+    pub status: Option<InvoiceStatus>,
+    pub reference: Option<String>,
+    pub currency_code: Option<String>,
+    pub date: DateTime<Utc>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub total: Option<Money>,
+    pub amount_due: Option<Money>,
+    pub amount_paid: Option<Money>,
+    pub updated_date_utc: Option<DateTime<Utc>>,
+}
+
+/// An invoice as Xero sends it, reduced to the fields of [`InvoiceHeader`].
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InvoiceHeaderRecord {
+    #[serde(rename = "InvoiceID")]
+    invoice_id: Uuid,
+    #[serde(default)]
+    invoice_number: Option<String>,
+    #[serde(rename = "Type")]
+    invoice_type: InvoiceType,
+    contact: InvoiceHeaderContact,
+    #[serde(default)]
+    status: Option<InvoiceStatus>,
+    #[serde(default)]
+    reference: Option<String>,
+    #[serde(default)]
+    currency_code: Option<String>,
+    #[serde(with = "xero_date_format")]
+    date: DateTime<Utc>,
+    #[serde(with = "xero_date_format_opt", default)]
+    due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    total: Option<Money>,
+    #[serde(default)]
+    amount_due: Option<Money>,
+    #[serde(default)]
+    amount_paid: Option<Money>,
+    #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
+    updated_date_utc: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InvoiceHeaderContact {
+    #[serde(rename = "ContactID", default)]
+    contact_id: Option<Uuid>,
+    #[serde(default)]
+    name: String,
+}
+
+impl From<InvoiceHeaderRecord> for InvoiceHeader {
+    fn from(record: InvoiceHeaderRecord) -> Self {
+        Self {
+            invoice_id: record.invoice_id,
+            invoice_number: record.invoice_number,
+            invoice_type: record.invoice_type,
+            contact_id: record.contact.contact_id,
+            contact_name: record.contact.name,
+            status: record.status,
+            reference: record.reference,
+            currency_code: record.currency_code,
+            date: record.date,
+            due_date: record.due_date,
+            total: record.total,
+            amount_due: record.amount_due,
+            amount_paid: record.amount_paid,
+            updated_date_utc: record.updated_date_utc,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct InvoiceHeadersResponse {
+    pub invoices: Vec<InvoiceHeader>,
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct InvoicesResponse {
     pub invoices: Vec<Invoice>,
+    #[serde(default)]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the request
@@ -132,3 +502,273 @@ pub(crate) struct InvoicesRequest {
 pub(crate) struct OnlineInvoicesResponse {
     pub online_invoices: Vec<OnlineInvoice>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ElementValidationError;
+    use crate::models::accounting::common::collect_validation_errors;
+
+    /// Parses `amount` so the same literal works for `f64` and `Decimal` money.
+    fn money(amount: &str) -> Money {
+        amount.parse().unwrap()
+    }
+
+    fn payment(amount: &str, status: PaymentStatus) -> Box<Payment> {
+        Box::new(Payment {
+            amount: money(amount),
+            status: Some(status),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn total_paid_skips_deleted_payments() {
+        let invoice = Invoice {
+            payments: Some(vec![
+                payment("40", PaymentStatus::Authorised),
+                payment("15.5", PaymentStatus::Authorised),
+                payment("99", PaymentStatus::Deleted),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(invoice.total_paid(), money("55.5"));
+        assert_eq!(Invoice::default().total_paid(), money("0"));
+    }
+
+    #[test]
+    fn summary_rows_parse_into_headers() {
+        let resp: InvoiceHeadersResponse = serde_json::from_value(serde_json::json!({
+            "Invoices": [{
+                "Type": "ACCREC",
+                "InvoiceID": "243216c5-369e-4056-ac67-05388f86dc81",
+                "InvoiceNumber": "INV-0001",
+                "Contact": {
+                    "ContactID": "025867f1-d741-4d6b-b1af-9ac774b59ba7",
+                    "Name": "City Agency"
+                },
+                "Date": "/Date(1518685950940+0000)/",
+                "DueDate": "/Date(1519290750940+0000)/",
+                "Status": "AUTHORISED",
+                "Total": 115,
+                "AmountDue": 15,
+                "AmountPaid": 100,
+                "UpdatedDateUTC": "/Date(1518685950940+0000)/"
+            }]
+        }))
+        .unwrap();
+
+        let header = &resp.invoices[0];
+        assert_eq!(header.invoice_number.as_deref(), Some("INV-0001"));
+        assert_eq!(header.contact_name, "City Agency");
+        assert!(header.contact_id.is_some());
+        assert_eq!(header.status, Some(InvoiceStatus::Authorised));
+        assert_eq!(header.total, Some(Money::from(115)));
+        assert_eq!(header.amount_due, Some(Money::from(15)));
+        assert!(header.due_date.is_some());
+    }
+
+    #[test]
+    fn applied_breakdown_separates_each_source() {
+        let payload = r#"{
+            "Type": "ACCREC",
+            "Contact": { "Name": "Boom FM" },
+            "Date": "/Date(1700000000000+0000)/",
+            "Total": 200.00,
+            "AmountPaid": 80.00,
+            "AmountCredited": 25.00,
+            "AmountDue": 95.00,
+            "Payments": [
+                { "PaymentID": "0d666415-cf77-43fa-80c7-56775591d426", "Amount": 50.00 }
+            ],
+            "CreditNotes": [
+                { "CreditNoteID": "7df8949c-b71f-40c0-bbcf-39f2f450f286", "AppliedAmount": 25.00, "Total": 40.00 }
+            ],
+            "Prepayments": [
+                { "PrepaymentID": "aea95d78-ea48-456b-be08-6bc012df1e86", "Date": "/Date(1700000000000+0000)/", "AppliedAmount": 20.00, "Total": 20.00 }
+            ],
+            "Overpayments": [
+                { "OverpaymentID": "ed7f0cd4-5a65-4b8e-9cfd-4a23e1cdd8e6", "Date": "/Date(1700000000000+0000)/", "AppliedAmount": 10.00, "Total": 30.00 }
+            ]
+        }"#;
+
+        let invoice: Invoice = serde_json::from_str(payload).unwrap();
+        let breakdown = invoice.applied_breakdown();
+        assert_eq!(breakdown.direct_payments, Money::from(50));
+        assert_eq!(breakdown.credit_notes_applied, Money::from(25));
+        assert_eq!(breakdown.prepayments_applied, Money::from(20));
+        assert_eq!(breakdown.overpayments_applied, Money::from(10));
+        assert_eq!(
+            breakdown.total(),
+            invoice.amount_paid.unwrap() + invoice.amount_credited.unwrap()
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal_amounts_parse_exactly() {
+        use std::str::FromStr;
+
+        let line: LineItem =
+            serde_json::from_str(r#"{"UnitAmount": 0.1, "LineAmount": 0.3}"#).unwrap();
+        assert_eq!(line.unit_amount, Some(Money::from_str("0.1").unwrap()));
+        assert_eq!(line.line_amount, Some(Money::from_str("0.3").unwrap()));
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"UnitAmount":0.1,"LineAmount":0.3}"#
+        );
+    }
+
+    #[test]
+    fn enums_render_bare_xero_tokens() {
+        assert_eq!(InvoiceType::Accrec.as_xero_value(), "ACCREC");
+        assert_eq!(InvoiceStatus::Authorised.to_string(), "AUTHORISED");
+        assert_eq!(
+            format!("Status==\"{}\"", InvoiceStatus::Paid),
+            "Status==\"PAID\""
+        );
+        assert_eq!(LineAmountType::NoTax.as_xero_value(), "NoTax");
+    }
+
+    #[test]
+    fn builder_sets_required_fields() {
+        let contact_id = Uuid::new_v4();
+        let date = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 3, 1, 0, 0, 0).unwrap();
+        let invoice = Invoice::builder(
+            InvoiceType::Accpay,
+            Contact {
+                contact_id: Some(contact_id),
+                ..Default::default()
+            },
+        )
+        .date(date)
+        .line_item(LineItem {
+            description: Some("Consulting".to_string()),
+            quantity: Some(2.0),
+            unit_amount: Some(Money::from(150)),
+            ..Default::default()
+        })
+        .status(InvoiceStatus::Authorised)
+        .reference("PO-1")
+        .build();
+
+        assert_eq!(invoice.invoice_type, InvoiceType::Accpay);
+        assert_eq!(invoice.contact.contact_id, Some(contact_id));
+        assert_eq!(invoice.date, date);
+        assert_eq!(invoice.line_items.len(), 1);
+        assert_eq!(invoice.status, Some(InvoiceStatus::Authorised));
+
+        let json = serde_json::to_value(&invoice).unwrap();
+        assert_eq!(json["Type"], "ACCPAY");
+        assert_eq!(json["Reference"], "PO-1");
+        assert!(json.get("DueDate").is_none());
+    }
+
+    #[test]
+    fn computed_totals_apply_discounts_and_tax_treatment() {
+        let tax_rates: Vec<TaxRate> = serde_json::from_value(serde_json::json!([{
+            "Name": "15% GST on Income",
+            "TaxType": "OUTPUT2",
+            "TaxComponents": [{ "Name": "GST", "Rate": 15.0 }],
+            "EffectiveRate": 15.0
+        }]))
+        .unwrap();
+        let invoice = Invoice {
+            line_items: vec![
+                LineItem {
+                    quantity: Some(2.0),
+                    unit_amount: Some(Money::from(10)),
+                    discount_rate: Some(10.0),
+                    tax_type: Some("OUTPUT2".to_string()),
+                    ..Default::default()
+                },
+                LineItem {
+                    line_amount: Some(Money::from(5)),
+                    tax_type: Some("MYSTERY".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        // Rounded so binary noise in f64 sums does not matter.
+        let cents = |n: i32| Money::from(n) / Money::from(100);
+        let check = |line_amount_type, sub_total, total_tax, total| {
+            let totals = invoice.compute_totals(&tax_rates, line_amount_type);
+            assert_eq!(round_cents(totals.sub_total), cents(sub_total));
+            assert_eq!(round_cents(totals.total_tax), cents(total_tax));
+            assert_eq!(round_cents(totals.total), cents(total));
+            assert_eq!(round_cents(totals.total_discount), cents(200));
+            totals
+        };
+
+        let exclusive = check(LineAmountType::Exclusive, 2300, 270, 2570);
+        assert_eq!(exclusive.unknown_tax_types, vec!["MYSTERY"]);
+        // 18.00 × 15/115 = 2.3478…, rounded on the line.
+        check(LineAmountType::Inclusive, 2065, 235, 2300);
+        let no_tax = check(LineAmountType::NoTax, 2300, 0, 2300);
+        assert!(no_tax.unknown_tax_types.is_empty());
+    }
+
+    #[test]
+    fn unsummarized_batch_errors_are_paired_with_their_rows() {
+        let payload = r#"{
+            "Invoices": [
+                {
+                    "Type": "ACCREC",
+                    "Contact": { "Name": "Boom FM" },
+                    "Date": "/Date(1700000000000+0000)/",
+                    "StatusAttributeString": "OK"
+                },
+                {
+                    "Type": "ACCREC",
+                    "Contact": { "Name": "Boom FM" },
+                    "Date": "/Date(1700000000000+0000)/",
+                    "StatusAttributeString": "ERROR",
+                    "ValidationErrors": [
+                        { "Message": "The TrackingOption 'North' does not exist." }
+                    ]
+                }
+            ]
+        }"#;
+
+        let parsed: InvoicesResponse = serde_json::from_str(payload).unwrap();
+        assert_eq!(
+            parsed.invoices[1].status_attribute_string.as_deref(),
+            Some("ERROR")
+        );
+        assert_eq!(
+            collect_validation_errors(&parsed.invoices),
+            vec![ElementValidationError {
+                index: 1,
+                message: "The TrackingOption 'North' does not exist.".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn response_and_record_warnings_are_kept() {
+        let payload = r#"{
+            "Warnings": [{ "Message": "Rounding applied to line amounts" }],
+            "Invoices": [{
+                "Type": "ACCREC",
+                "Contact": { "ContactID": "9a59ea90-942e-484d-9b71-d00ab607e03b", "Name": "Boom FM" },
+                "Date": "/Date(1700000000000+0000)/",
+                "StatusAttributeString": "WARNING",
+                "Warnings": [{ "Message": "Contact has no email address" }]
+            }]
+        }"#;
+
+        let parsed: InvoicesResponse = serde_json::from_str(payload).unwrap();
+        assert_eq!(
+            parsed.warnings[0].message,
+            "Rounding applied to line amounts"
+        );
+        assert_eq!(
+            parsed.invoices[0].warnings[0].message,
+            "Contact has no email address"
+        );
+
+        let json = serde_json::to_value(Invoice::default()).unwrap();
+        assert!(json.get("Warnings").is_none());
+    }
+}
@@ -51,6 +51,15 @@ pub struct ItemDetails {
     pub tax_type: Option<String>,
 }
 
+impl Item {
+    /// Returns `true` if the item is tracked as inventory.
+    ///
+    /// Only tracked items carry `quantity_on_hand` and `total_cost_pool`.
+    pub fn is_inventory(&self) -> bool {
+        self.is_tracked_as_inventory == Some(true)
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -64,3 +73,32 @@ pub(crate) struct ItemsResponse {
 pub(crate) struct ItemsRequest {
     pub items: Vec<Item>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_item_exposes_inventory_and_unit_prices() {
+        let item: Item = serde_json::from_str(
+            r#"{
+                "ItemID": "9a59ea90-942e-484d-9b71-d00ab607e03b",
+                "Code": "Merino-2011-LG",
+                "IsTrackedAsInventory": true,
+                "InventoryAssetAccountCode": "630",
+                "TotalCostPool": 2000.00,
+                "QuantityOnHand": 100.0000,
+                "PurchaseDetails": { "UnitPrice": 20.0000, "COGSAccountCode": "310" },
+                "SalesDetails": { "UnitPrice": 49.0000, "AccountCode": "200" }
+            }"#,
+        )
+        .unwrap();
+        assert!(item.is_inventory());
+        assert_eq!(item.quantity_on_hand, Some(100.0));
+        assert_eq!(item.purchase_details.unwrap().unit_price, Some(20.0));
+        assert_eq!(item.sales_details.unwrap().unit_price, Some(49.0));
+
+        let untracked: Item = serde_json::from_str(r#"{ "Code": "SVC" }"#).unwrap();
+        assert!(!untracked.is_inventory());
+    }
+}
@@ -1,7 +1,7 @@
 //! Model for the Journal resource.
 
 use super::account::AccountType;
-use super::common::TrackingCategory;
+use super::common::{impl_xero_value, TrackingCategory};
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -37,6 +37,8 @@ pub enum JournalSourceType {
     Integratedpayrollcn,
 }
 
+impl_xero_value!(JournalSourceType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Journal {
@@ -44,6 +46,7 @@ pub struct Journal {
     pub journal_id: Uuid,
     #[serde(with = "xero_date_format")]
     pub journal_date: DateTime<Utc>,
+    #[serde(deserialize_with = "crate::util::deserialize_i32_from_string_or_number")]
     pub journal_number: i32,
     #[serde(with = "xero_date_format_opt", default, rename = "CreatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,5 +1,6 @@
 //! Model for the `LinkedTransaction` resource.
 
+use super::common::impl_xero_value;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -15,12 +16,16 @@ pub enum LinkedTransactionStatus {
     Voided,
 }
 
+impl_xero_value!(LinkedTransactionStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum LinkedTransactionType {
     Billableexpense,
 }
 
+impl_xero_value!(LinkedTransactionType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum SourceTransactionTypeCode {
@@ -28,6 +33,8 @@ pub enum SourceTransactionTypeCode {
     Spend,
 }
 
+impl_xero_value!(SourceTransactionTypeCode);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct LinkedTransaction {
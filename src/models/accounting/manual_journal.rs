@@ -1,6 +1,7 @@
 //! Model for the `ManualJournal` resource.
 
-use super::common::{LineAmountType, TrackingCategory};
+use super::common::{impl_xero_value, LineAmountType, TrackingCategory};
+use crate::error::XeroError;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,8 @@ pub enum ManualJournalStatus {
     Voided,
 }
 
+impl_xero_value!(ManualJournalStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ManualJournal {
@@ -55,6 +58,108 @@ pub struct ManualJournalLine {
     pub tax_amount: Option<f64>,
 }
 
+impl ManualJournal {
+    /// Starts a [`ManualJournalBuilder`] for a new journal.
+    pub fn builder(narration: impl Into<String>, date: DateTime<Utc>) -> ManualJournalBuilder {
+        ManualJournalBuilder::new(narration, date)
+    }
+}
+
+impl ManualJournalLine {
+    /// Creates a line posting `line_amount` to `account_code`.
+    ///
+    /// Debits are positive and credits negative.
+    pub fn new(account_code: impl Into<String>, line_amount: f64) -> Self {
+        Self {
+            line_amount,
+            account_code: account_code.into(),
+            description: None,
+            tax_type: None,
+            tracking: Vec::new(),
+            tax_amount: None,
+        }
+    }
+}
+
+/// Builder for a new [`ManualJournal`] that checks the lines balance.
+///
+/// Xero rejects journals whose line amounts do not sum to zero, or that have
+/// fewer than two lines; [`ManualJournalBuilder::build`] catches both before
+/// the request is sent.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ManualJournalBuilder {
+    journal: ManualJournal,
+}
+
+impl ManualJournalBuilder {
+    /// Creates a builder for a journal with `narration`, dated `date`.
+    pub fn new(narration: impl Into<String>, date: DateTime<Utc>) -> Self {
+        Self {
+            journal: ManualJournal {
+                narration: narration.into(),
+                journal_lines: Vec::new(),
+                date: Some(date),
+                line_amount_types: None,
+                status: None,
+                url: None,
+                show_on_cash_basis_reports: None,
+                has_attachments: None,
+                updated_date_utc: None,
+                manual_journal_id: None,
+            },
+        }
+    }
+
+    /// Appends a journal line.
+    pub fn line(mut self, line: ManualJournalLine) -> Self {
+        self.journal.journal_lines.push(line);
+        self
+    }
+
+    /// Sets the status. Xero creates journals as `DRAFT` when none is given.
+    pub fn status(mut self, status: ManualJournalStatus) -> Self {
+        self.journal.status = Some(status);
+        self
+    }
+
+    /// Sets whether line amounts include tax.
+    pub fn line_amount_types(mut self, line_amount_types: LineAmountType) -> Self {
+        self.journal.line_amount_types = Some(line_amount_types);
+        self
+    }
+
+    /// Sets whether the journal appears on cash basis reports.
+    pub fn show_on_cash_basis_reports(mut self, show: bool) -> Self {
+        self.journal.show_on_cash_basis_reports = Some(show);
+        self
+    }
+
+    /// Returns the journal, or [`XeroError::Validation`] if it has fewer than
+    /// two lines or the line amounts do not sum to zero.
+    pub fn build(self) -> Result<ManualJournal, XeroError> {
+        let lines = &self.journal.journal_lines;
+        if lines.len() < 2 {
+            return Err(XeroError::Validation(format!(
+                "Manual journal needs at least two lines, got {}",
+                lines.len()
+            )));
+        }
+        // Compare in cents so float noise in the amounts does not count.
+        let net_cents: i64 = lines
+            .iter()
+            .map(|line| (line.line_amount * 100.0).round() as i64)
+            .sum();
+        if net_cents != 0 {
+            return Err(XeroError::Validation(format!(
+                "Manual journal lines must balance, but they net to {:.2}",
+                net_cents as f64 / 100.0
+            )));
+        }
+        Ok(self.journal)
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -69,3 +174,44 @@ pub(crate) struct ManualJournalsResponse {
 pub(crate) struct ManualJournalsRequest {
     pub manual_journals: Vec<ManualJournal>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 30, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn balanced_journal_builds() {
+        let journal = ManualJournal::builder("Accrue June rent", date())
+            .line(ManualJournalLine::new("429", 1000.10))
+            .line(ManualJournalLine::new("800", -600.05))
+            .line(ManualJournalLine::new("800", -400.05))
+            .show_on_cash_basis_reports(false)
+            .build()
+            .unwrap();
+        assert_eq!(journal.journal_lines.len(), 3);
+        assert_eq!(journal.date, Some(date()));
+
+        let json = serde_json::to_value(&journal).unwrap();
+        assert_eq!(json["ShowOnCashBasisReports"], false);
+        assert_eq!(json["JournalLines"][1]["AccountCode"], "800");
+    }
+
+    #[test]
+    fn unbalanced_or_single_line_journals_are_rejected() {
+        let unbalanced = ManualJournal::builder("Typo", date())
+            .line(ManualJournalLine::new("429", 100.0))
+            .line(ManualJournalLine::new("800", -90.0))
+            .build();
+        assert!(matches!(unbalanced, Err(XeroError::Validation(msg)) if msg.contains("10.00")));
+
+        let single = ManualJournal::builder("One side", date())
+            .line(ManualJournalLine::new("429", 0.0))
+            .build();
+        assert!(matches!(single, Err(XeroError::Validation(_))));
+    }
+}
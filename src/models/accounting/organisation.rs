@@ -1,8 +1,8 @@
 //! Model for the Organisation resource.
 
-use super::common::{Address, Link, PaymentTerm, Phone};
-use crate::util::xero_date_format_opt;
-use chrono::{DateTime, Utc};
+use super::common::{impl_xero_value, Address, Link, LinkType, PaymentTerm, Phone};
+use crate::util::{deserialize_bool_from_string_or_bool, xero_date_format_opt};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -68,6 +68,67 @@ pub struct Organisation {
     pub payment_terms: Option<OrganisationPaymentTerms>,
 }
 
+impl Organisation {
+    /// Returns `true` if `date` falls on or before the period or end-of-year lock date.
+    ///
+    /// The period lock date only binds users without the adviser role, so a
+    /// date that is locked here may still be writable for an adviser.
+    pub fn is_date_locked(&self, date: NaiveDate) -> bool {
+        [self.period_lock_date, self.end_of_year_lock_date]
+            .into_iter()
+            .flatten()
+            .any(|lock| date <= lock.date_naive())
+    }
+
+    /// Returns the URL of the organisation's website, if it has one.
+    #[must_use]
+    pub fn website(&self) -> Option<&str> {
+        self.external_link(&LinkType::Website)
+    }
+
+    /// Returns the URL of the first external link of `kind`.
+    #[must_use]
+    pub fn external_link(&self, kind: &LinkType) -> Option<&str> {
+        self.external_links
+            .iter()
+            .find(|link| link.kind().as_ref() == Some(kind))
+            .and_then(|link| link.url.as_deref())
+    }
+
+    /// Returns the first day of the financial year containing `date`.
+    pub fn financial_year_start(&self, date: NaiveDate) -> NaiveDate {
+        let this_year_end = self.financial_year_end_in(date.year());
+        let previous_end = if date > this_year_end {
+            this_year_end
+        } else {
+            self.financial_year_end_in(date.year() - 1)
+        };
+        previous_end + Days::new(1)
+    }
+
+    /// Returns the first day of the financial year containing today (UTC).
+    pub fn current_financial_year_start(&self) -> NaiveDate {
+        self.financial_year_start(Utc::now().date_naive())
+    }
+
+    /// The financial year end in `year`, clamping the day to the month length
+    /// so a 29 February year end falls on the 28th in other years.
+    fn financial_year_end_in(&self, year: i32) -> NaiveDate {
+        let month = self.financial_year_end_month.clamp(1, 12);
+        let first_of_next = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        }
+        .expect("first of month is always valid");
+        let last_of_month = first_of_next.pred_opt().expect("date has a predecessor");
+        let day = self.financial_year_end_day.clamp(1, last_of_month.day());
+        last_of_month
+            .with_day(day)
+            .expect("day is within the month")
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct OrganisationPaymentTerms {
@@ -77,11 +138,44 @@ pub struct OrganisationPaymentTerms {
     pub sales: Option<PaymentTerm>,
 }
 
+/// An action Xero reports permission for under `/Organisation/Actions`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrganisationActionName {
+    UseMulticurrency,
+    CreateApprovedInvoice,
+    CreateApprovedBill,
+    CreateApprovedCreditNote,
+    CreateApprovedCreditNoteBill,
+    CreateApprovedPurchaseOrder,
+    CreateDraftPurchaseOrder,
+    CreateApprovedQuote,
+    CreateDraftQuote,
+    CreateRepeatingInvoice,
+    CreatePayments,
+    AttachFiles,
+    SendInvoiceEmail,
+    /// An action this crate does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+impl_xero_value!(OrganisationActionName);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct OrganisationAction {
-    pub name: String,
-    pub status: String,
+    pub name: OrganisationActionName,
+    /// Whether the action is allowed. Xero sends this as a string.
+    #[serde(deserialize_with = "deserialize_bool_from_string_or_bool")]
+    pub status: bool,
+}
+
+impl OrganisationAction {
+    /// Returns `true` if the organisation allows this action.
+    #[must_use]
+    pub fn is_allowed(&self) -> bool {
+        self.status
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -106,3 +200,119 @@ pub(crate) struct OrganisationsResponse {
 pub(crate) struct ActionsResponse {
     pub actions: Vec<OrganisationAction>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn organisation(end_day: u32, end_month: u32) -> Organisation {
+        serde_json::from_value(serde_json::json!({
+            "Name": "Demo Company (NZ)",
+            "LegalName": "Demo Company (NZ)",
+            "PaysTax": true,
+            "Version": "NZ",
+            "OrganisationType": "COMPANY",
+            "BaseCurrency": "NZD",
+            "CountryCode": "NZ",
+            "IsDemoCompany": true,
+            "OrganisationStatus": "ACTIVE",
+            "FinancialYearEndDay": end_day,
+            "FinancialYearEndMonth": end_month,
+            "PeriodLockDate": "/Date(1711843200000+0000)/",
+            "EndOfYearLockDate": "/Date(1680220800000+0000)/",
+            "Timezone": "NEWZEALANDSTANDARDTIME",
+            "OrganisationEntityType": "COMPANY",
+            "OrganisationID": "b2c885a9-4bb9-4a00-9b6e-6c2bf60b1a2b",
+            "Edition": "BUSINESS",
+            "Class": "PREMIUM"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn actions_parse_typed_names_and_flags() {
+        let resp: ActionsResponse = serde_json::from_value(serde_json::json!({
+            "Actions": [
+                { "Name": "CreateApprovedInvoice", "Status": "ALLOWED" },
+                { "Name": "UseMulticurrency", "Status": "NOT-ALLOWED" },
+                { "Name": "AttachFiles", "Status": "true" },
+                { "Name": "SomethingNew", "Status": "false" }
+            ]
+        }))
+        .unwrap();
+        let actions: Vec<_> = resp
+            .actions
+            .iter()
+            .map(|a| (a.name, a.is_allowed()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (OrganisationActionName::CreateApprovedInvoice, true),
+                (OrganisationActionName::UseMulticurrency, false),
+                (OrganisationActionName::AttachFiles, true),
+                (OrganisationActionName::Unknown, false),
+            ]
+        );
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn lock_dates_are_inclusive() {
+        // Period lock 2024-03-31, end-of-year lock 2023-03-31.
+        let org = organisation(31, 3);
+        assert!(org.is_date_locked(date(2023, 1, 15)));
+        assert!(org.is_date_locked(date(2024, 3, 31)));
+        assert!(!org.is_date_locked(date(2024, 4, 1)));
+    }
+
+    #[test]
+    fn financial_year_start_follows_year_end() {
+        let march = organisation(31, 3);
+        assert_eq!(
+            march.financial_year_start(date(2024, 3, 31)),
+            date(2023, 4, 1)
+        );
+        assert_eq!(
+            march.financial_year_start(date(2024, 4, 1)),
+            date(2024, 4, 1)
+        );
+
+        let december = organisation(31, 12);
+        assert_eq!(
+            december.financial_year_start(date(2024, 6, 30)),
+            date(2024, 1, 1)
+        );
+
+        let leap = organisation(29, 2);
+        assert_eq!(
+            leap.financial_year_start(date(2023, 3, 1)),
+            date(2023, 3, 1)
+        );
+        assert_eq!(
+            leap.financial_year_start(date(2024, 2, 29)),
+            date(2023, 3, 1)
+        );
+    }
+
+    #[test]
+    fn website_reads_typed_and_described_links() {
+        let mut org = organisation(31, 3);
+        assert_eq!(org.website(), None);
+
+        org.external_links = serde_json::from_value(serde_json::json!([
+            { "LinkType": "Facebook", "Url": "https://facebook.com/demo" },
+            { "Description": "website", "Url": "https://demo.example" }
+        ]))
+        .unwrap();
+        assert_eq!(org.website(), Some("https://demo.example"));
+        assert_eq!(
+            org.external_link(&LinkType::Facebook),
+            Some("https://facebook.com/demo")
+        );
+        assert_eq!(org.external_link(&LinkType::Twitter), None);
+    }
+}
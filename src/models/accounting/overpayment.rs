@@ -1,6 +1,6 @@
 //! Model for the Overpayment resource.
 
-use super::common::{Allocation, LineAmountType, LineItem};
+use super::common::{impl_xero_value, Allocation, LineAmountType, LineItem};
 use super::contact::Contact;
 use super::payment::Payment;
 use crate::util::{xero_date_format, xero_date_format_opt};
@@ -17,6 +17,8 @@ pub enum OverpaymentType {
     SpendOverpayment,
 }
 
+impl_xero_value!(OverpaymentType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum OverpaymentStatus {
@@ -25,6 +27,8 @@ pub enum OverpaymentStatus {
     Voided,
 }
 
+impl_xero_value!(OverpaymentStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Overpayment {
@@ -38,6 +42,7 @@ pub struct Overpayment {
     pub status: Option<OverpaymentStatus>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line_amount_types: Option<LineAmountType>,
+    #[serde(default)]
     pub line_items: Vec<LineItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sub_total: Option<f64>,
@@ -55,6 +60,10 @@ pub struct Overpayment {
     pub currency_rate: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remaining_credit: Option<f64>,
+    /// How much of the overpayment was applied to the invoice it is nested in.
+    /// Only set on the overpayments listed on an invoice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_amount: Option<f64>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allocations: Vec<Allocation>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
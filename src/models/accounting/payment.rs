@@ -1,10 +1,12 @@
 //! Model for the Payment resource.
 
 use super::account::Account;
+use super::common::{impl_xero_value, Money};
 use super::credit_note::CreditNote;
 use super::invoice::Invoice;
 use super::overpayment::Overpayment;
 use super::prepayment::Prepayment;
+use crate::error::{ApiValidationError, XeroError};
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -23,6 +25,8 @@ pub enum PaymentType {
     Apoverpaymentpayment,
 }
 
+impl_xero_value!(PaymentType);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PaymentStatus {
@@ -30,6 +34,8 @@ pub enum PaymentStatus {
     Deleted,
 }
 
+impl_xero_value!(PaymentStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct Payment {
@@ -47,7 +53,7 @@ pub struct Payment {
     pub account: Option<Account>,
     #[serde(with = "xero_date_format")]
     pub date: DateTime<Utc>,
-    pub amount: f64,
+    pub amount: Money,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_rate: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +67,164 @@ pub struct Payment {
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiValidationError>,
+}
+
+impl Payment {
+    /// Starts a [`PaymentBuilder`] for a new payment.
+    pub fn builder(
+        target: PaymentTarget,
+        account: PaymentAccount,
+        amount: Money,
+    ) -> PaymentBuilder {
+        PaymentBuilder::new(target, account, amount)
+    }
+}
+
+/// The document a new payment is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentTarget {
+    Invoice(Uuid),
+    CreditNote(Uuid),
+    Prepayment(Uuid),
+    Overpayment(Uuid),
+}
+
+/// The bank account (or account with payments enabled) a payment is made from or to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentAccount {
+    Id(Uuid),
+    Code(String),
+}
+
+/// Builder for a new [`Payment`].
+///
+/// Xero rejects payments without a document to apply them to, without an
+/// account, or with a non-positive amount. The first two are required by
+/// [`PaymentBuilder::new`]; [`PaymentBuilder::build`] checks the amount. The
+/// date defaults to now when not given.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PaymentBuilder {
+    payment: Payment,
+}
+
+impl PaymentBuilder {
+    /// Creates a builder for a payment of `amount` against `target`, through `account`.
+    pub fn new(target: PaymentTarget, account: PaymentAccount, amount: Money) -> Self {
+        let date = Utc::now();
+        let mut payment = Payment {
+            account: Some(match account {
+                PaymentAccount::Id(account_id) => Account {
+                    account_id: Some(account_id),
+                    ..Default::default()
+                },
+                PaymentAccount::Code(code) => Account {
+                    code: Some(code),
+                    ..Default::default()
+                },
+            }),
+            date,
+            amount,
+            ..Default::default()
+        };
+        match target {
+            PaymentTarget::Invoice(invoice_id) => {
+                payment.invoice = Some(Box::new(Invoice {
+                    invoice_id: Some(invoice_id),
+                    ..Default::default()
+                }));
+            }
+            PaymentTarget::CreditNote(credit_note_id) => {
+                payment.credit_note = Some(Box::new(CreditNote {
+                    credit_note_id: Some(credit_note_id),
+                    ..Default::default()
+                }));
+            }
+            PaymentTarget::Prepayment(prepayment_id) => {
+                payment.prepayment = Some(Box::new(Prepayment {
+                    prepayment_type: None,
+                    contact: None,
+                    date,
+                    status: None,
+                    line_amount_types: None,
+                    line_items: Vec::new(),
+                    sub_total: None,
+                    total_tax: None,
+                    total: 0.0,
+                    updated_date_utc: None,
+                    currency_code: None,
+                    prepayment_id,
+                    currency_rate: None,
+                    reference: None,
+                    remaining_credit: None,
+                    applied_amount: None,
+                    allocations: Vec::new(),
+                    payments: None,
+                    has_attachments: None,
+                }));
+            }
+            PaymentTarget::Overpayment(overpayment_id) => {
+                payment.overpayment = Some(Box::new(Overpayment {
+                    overpayment_type: None,
+                    contact: None,
+                    date,
+                    status: None,
+                    line_amount_types: None,
+                    line_items: Vec::new(),
+                    sub_total: None,
+                    total_tax: None,
+                    total: 0.0,
+                    updated_date_utc: None,
+                    currency_code: None,
+                    overpayment_id,
+                    currency_rate: None,
+                    remaining_credit: None,
+                    applied_amount: None,
+                    allocations: Vec::new(),
+                    payments: None,
+                    has_attachments: None,
+                }));
+            }
+        }
+        Self { payment }
+    }
+
+    /// Sets the payment date.
+    pub fn date(mut self, date: DateTime<Utc>) -> Self {
+        self.payment.date = date;
+        self
+    }
+
+    /// Sets the payment reference.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.payment.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets the exchange rate for a payment in a foreign currency.
+    pub fn currency_rate(mut self, currency_rate: f64) -> Self {
+        self.payment.currency_rate = Some(currency_rate);
+        self
+    }
+
+    /// Marks the payment as already reconciled.
+    pub fn is_reconciled(mut self, is_reconciled: bool) -> Self {
+        self.payment.is_reconciled = Some(is_reconciled);
+        self
+    }
+
+    /// Returns the payment, or [`XeroError::Validation`] if the amount is not positive.
+    pub fn build(self) -> Result<Payment, XeroError> {
+        if self.payment.amount <= Money::from(0) {
+            return Err(XeroError::Validation(format!(
+                "Payment amount must be positive, got {}",
+                self.payment.amount
+            )));
+        }
+        Ok(self.payment)
+    }
 }
 
 // Wrapper for the response
@@ -68,6 +232,8 @@ pub struct Payment {
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct PaymentsResponse {
     pub payments: Option<Vec<Payment>>,
+    #[serde(default)]
+    pub warnings: Vec<ApiValidationError>,
 }
 
 // Wrapper for the request
@@ -76,3 +242,34 @@ pub(crate) struct PaymentsResponse {
 pub(crate) struct PaymentsRequest {
     pub payments: Option<Vec<Payment>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_requires_positive_amount() {
+        let invoice_id = Uuid::new_v4();
+        let payment = Payment::builder(
+            PaymentTarget::Invoice(invoice_id),
+            PaymentAccount::Code("090".to_string()),
+            Money::from(25),
+        )
+        .reference("INV-001")
+        .build()
+        .unwrap();
+        let json = serde_json::to_value(&payment).unwrap();
+        assert_eq!(json["Invoice"]["InvoiceID"], invoice_id.to_string());
+        assert_eq!(json["Account"], serde_json::json!({ "Code": "090" }));
+        assert_eq!(json["Reference"], "INV-001");
+
+        let err = Payment::builder(
+            PaymentTarget::CreditNote(Uuid::new_v4()),
+            PaymentAccount::Id(Uuid::new_v4()),
+            Money::from(0),
+        )
+        .build()
+        .unwrap_err();
+        assert!(matches!(err, XeroError::Validation(_)));
+    }
+}
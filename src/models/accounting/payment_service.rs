@@ -28,3 +28,28 @@ pub(crate) struct PaymentServicesResponse {
 pub(crate) struct PaymentServicesRequest {
     pub payment_services: Vec<PaymentService>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_branding_theme_payment_services() {
+        let payload = r#"{
+            "PaymentServices": [{
+                "PaymentServiceID": "dede7858-14e3-4a46-bf95-4d4cc491e645",
+                "PaymentServiceName": "ACME Payments",
+                "PaymentServiceUrl": "https://www.payupnow.com/?invoiceNo=[INVOICENUMBER]&currency=[CURRENCY]&amount=[AMOUNTDUE]",
+                "PayNowText": "Pay Now",
+                "PaymentServiceType": "Custom"
+            }]
+        }"#;
+
+        let parsed: PaymentServicesResponse =
+            serde_json::from_str(payload).expect("payment services response should deserialize");
+        let service = &parsed.payment_services[0];
+        assert_eq!(service.pay_now_text, "Pay Now");
+        assert_eq!(service.payment_service_type, "Custom");
+        assert!(service.payment_service_url.contains("[AMOUNTDUE]"));
+    }
+}
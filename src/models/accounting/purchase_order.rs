@@ -1,6 +1,6 @@
 //! Model for the `PurchaseOrder` resource.
 
-use super::common::{LineAmountType, LineItem};
+use super::common::{impl_xero_value, LineAmountType, LineItem};
 use super::contact::Contact;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
@@ -17,6 +17,8 @@ pub enum PurchaseOrderStatus {
     Deleted,
 }
 
+impl_xero_value!(PurchaseOrderStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct PurchaseOrder {
@@ -72,6 +74,42 @@ pub struct PurchaseOrder {
     pub updated_date_utc: Option<DateTime<Utc>>,
 }
 
+/// Where and how a purchase order should be delivered.
+///
+/// Xero keeps these as flat fields on [`PurchaseOrder`];
+/// [`PurchaseOrder::delivery_details`] and
+/// [`PurchaseOrder::set_delivery_details`] move them as one group, e.g. when
+/// copying a supplier's usual delivery instructions onto a new order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeliveryDetails {
+    /// The full delivery address as a single block of text.
+    pub delivery_address: Option<String>,
+    pub attention_to: Option<String>,
+    pub telephone: Option<String>,
+    pub delivery_instructions: Option<String>,
+}
+
+impl PurchaseOrder {
+    /// Returns a copy of the order's delivery fields.
+    #[must_use]
+    pub fn delivery_details(&self) -> DeliveryDetails {
+        DeliveryDetails {
+            delivery_address: self.delivery_address.clone(),
+            attention_to: self.attention_to.clone(),
+            telephone: self.telephone.clone(),
+            delivery_instructions: self.delivery_instructions.clone(),
+        }
+    }
+
+    /// Replaces all four delivery fields; `None` fields are left out of the request.
+    pub fn set_delivery_details(&mut self, details: DeliveryDetails) {
+        self.delivery_address = details.delivery_address;
+        self.attention_to = details.attention_to;
+        self.telephone = details.telephone;
+        self.delivery_instructions = details.delivery_instructions;
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -85,3 +123,68 @@ pub(crate) struct PurchaseOrdersResponse {
 pub(crate) struct PurchaseOrdersRequest {
     pub purchase_orders: Vec<PurchaseOrder>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trips_delivery_fields() {
+        let payload = json!({
+            "PurchaseOrderID": "44dc5d9e-7488-4db1-8185-177be8ab7c6f",
+            "Contact": { "Name": "Supplier Ltd" },
+            "LineItems": [],
+            "Date": "/Date(1699000000000+0000)/",
+            "AttentionTo": "Warehouse",
+            "Telephone": "021 555 0100",
+            "DeliveryInstructions": "Leave at loading dock",
+            "ExpectedArrivalDate": "/Date(1700000000000+0000)/",
+            "HasAttachments": true
+        });
+
+        let order: PurchaseOrder = serde_json::from_value(payload).unwrap();
+        assert_eq!(order.attention_to.as_deref(), Some("Warehouse"));
+        assert_eq!(order.telephone.as_deref(), Some("021 555 0100"));
+        assert_eq!(
+            order.delivery_instructions.as_deref(),
+            Some("Leave at loading dock")
+        );
+        assert_eq!(order.has_attachments, Some(true));
+
+        let value = serde_json::to_value(&order).unwrap();
+        assert_eq!(value["AttentionTo"], "Warehouse");
+        assert_eq!(value["Telephone"], "021 555 0100");
+        assert_eq!(value["DeliveryInstructions"], "Leave at loading dock");
+        assert_eq!(value["ExpectedArrivalDate"], "/Date(1700000000000)/");
+    }
+
+    #[test]
+    fn delivery_details_write_flat_fields_and_xero_dates() {
+        let mut order: PurchaseOrder = serde_json::from_value(json!({
+            "Contact": { "Name": "Supplier Ltd" },
+            "LineItems": [],
+            "Date": "/Date(1699000000000+0000)/"
+        }))
+        .unwrap();
+        let details = DeliveryDetails {
+            delivery_address: Some("23 Main Street\nCentral City".to_string()),
+            attention_to: Some("Warehouse".to_string()),
+            telephone: None,
+            delivery_instructions: Some("Leave at loading dock".to_string()),
+        };
+        order.set_delivery_details(details.clone());
+        order.delivery_date = DateTime::from_timestamp_millis(1_700_000_000_000);
+        order.expected_arrival_date = DateTime::from_timestamp_millis(1_700_086_400_000);
+        assert_eq!(order.delivery_details(), details);
+
+        let value = serde_json::to_value(&order).unwrap();
+        assert_eq!(value["DeliveryAddress"], "23 Main Street\nCentral City");
+        assert_eq!(value["AttentionTo"], "Warehouse");
+        assert_eq!(value["DeliveryInstructions"], "Leave at loading dock");
+        assert!(value.get("Telephone").is_none());
+        assert!(value.get("DeliveryDetails").is_none());
+        assert_eq!(value["DeliveryDate"], "/Date(1700000000000)/");
+        assert_eq!(value["ExpectedArrivalDate"], "/Date(1700086400000)/");
+    }
+}
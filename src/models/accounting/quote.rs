@@ -1,6 +1,6 @@
 //! Model for the Quote resource.
 
-use super::common::{LineAmountType, LineItem};
+use super::common::{impl_xero_value, LineAmountType, LineItem};
 use super::contact::Contact;
 use crate::util::{xero_date_format, xero_date_format_opt};
 use chrono::{DateTime, Utc};
@@ -18,6 +18,8 @@ pub enum QuoteStatus {
     Invoiced,
 }
 
+impl_xero_value!(QuoteStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Quote {
@@ -1,6 +1,6 @@
 //! Model for the Receipt resource.
 
-use super::common::{LineAmountType, LineItem};
+use super::common::{impl_xero_value, LineAmountType, LineItem};
 use super::contact::Contact;
 use super::user::User;
 use crate::util::{xero_date_format, xero_date_format_opt};
@@ -18,13 +18,18 @@ pub enum ReceiptStatus {
     Deleted,
 }
 
+impl_xero_value!(ReceiptStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Receipt {
     #[serde(rename = "ReceiptID", skip_serializing_if = "Option::is_none")]
     pub receipt_id: Option<Uuid>,
     pub user: User,
+    // Receipts embedded in an expense claim are summaries without contact or line details.
+    #[serde(default)]
     pub contact: Contact,
+    #[serde(default)]
     pub line_items: Vec<LineItem>,
     #[serde(with = "xero_date_format")]
     pub date: DateTime<Utc>,
@@ -40,7 +45,11 @@ pub struct Receipt {
     pub total: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<ReceiptStatus>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::util::deserialize_opt_u64_from_string_or_number"
+    )]
     pub receipt_number: Option<u64>,
     #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
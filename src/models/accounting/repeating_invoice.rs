@@ -1,6 +1,6 @@
 //! Model for the `RepeatingInvoice` resource.
 
-use super::common::{LineAmountType, LineItem, PaymentTermType};
+use super::common::{impl_xero_value, LineAmountType, LineItem, PaymentTermType};
 use super::contact::Contact;
 use super::invoice::InvoiceType;
 use crate::util::{xero_date_format, xero_date_format_opt};
@@ -16,6 +16,8 @@ pub enum RepeatingInvoiceStatus {
     Deleted,
 }
 
+impl_xero_value!(RepeatingInvoiceStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ScheduleUnit {
@@ -23,6 +25,8 @@ pub enum ScheduleUnit {
     Monthly,
 }
 
+impl_xero_value!(ScheduleUnit);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct RepeatingInvoice {
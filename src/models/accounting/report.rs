@@ -1,9 +1,11 @@
 //! Model for the Report resource.
 
-use crate::util::xero_date_format_opt;
-use chrono::{DateTime, Utc};
+use super::common::Money;
+use crate::util::flexible_datetime_opt;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
@@ -15,8 +17,10 @@ pub struct Report {
     pub report_type: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub report_titles: Vec<String>,
+    /// The date label Xero shows under the title, e.g. `"31 December 2019"` or
+    /// `"1 Jan 2023 to 31 Dec 2023"`. See [`Report::report_end_date`].
     pub report_date: String,
-    #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
+    #[serde(with = "flexible_datetime_opt", default, rename = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -45,12 +49,63 @@ pub struct ReportRow {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReportCell {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::util::deserialize_string_or_number"
+    )]
     pub value: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attributes: Vec<ReportAttribute>,
 }
 
+impl Report {
+    /// Parses the date the report runs to from [`Report::report_date`].
+    ///
+    /// That field is display text rather than a timestamp; for a range such as
+    /// `"1 Jan 2023 to 31 Dec 2023"` this returns the last date. `None` if the
+    /// label is not a day-month-year date.
+    #[must_use]
+    pub fn report_end_date(&self) -> Option<NaiveDate> {
+        let last = self.report_date.rsplit(" to ").next()?.trim();
+        ["%d %B %Y", "%d %b %Y"]
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(last, format).ok())
+    }
+}
+
+impl ReportCell {
+    /// Parses the cell value as an amount, tolerating regional formatting.
+    ///
+    /// Currency symbols and codes, thousands separators (`,`, spaces, `'`) and
+    /// accounting-style parentheses for negatives are handled, so
+    /// `"NZ$(1,234.50)"` parses as `-1234.5`. The decimal separator is assumed
+    /// to be `.`, which is what Xero's API returns; an org whose region uses a
+    /// decimal comma may still see locale-formatted values in some reports, so
+    /// check results against the org's region if they look off by a factor of
+    /// a thousand. Returns `None` for empty or non-numeric cells.
+    #[must_use]
+    pub fn value_as_decimal(&self) -> Option<Money> {
+        self.value.as_deref().and_then(parse_report_amount)
+    }
+}
+
+fn parse_report_amount(value: &str) -> Option<Money> {
+    let value = value.trim();
+    let negative_parens = value.contains('(') && value.ends_with(')');
+    let mut cleaned: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-'))
+        .collect();
+    if !cleaned.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if negative_parens && !cleaned.starts_with('-') {
+        cleaned.insert(0, '-');
+    }
+    cleaned.parse().ok()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReportAttribute {
@@ -155,3 +210,307 @@ impl From<TenNinetyNineReport> for Report {
         value.0
     }
 }
+
+impl TenNinetyNineReport {
+    /// Parses the report's `Contacts` maps into typed [`Vendor1099`] rows.
+    pub fn vendors(&self) -> Result<Vec<Vendor1099>, serde_json::Error> {
+        self.0
+            .contacts
+            .iter()
+            .map(|contact| {
+                let value = serde_json::Value::Object(
+                    contact
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                );
+                serde_json::from_value(value)
+            })
+            .collect()
+    }
+
+    /// Converts the report into a typed [`TenNinetyNine`].
+    pub fn to_typed(&self) -> Result<TenNinetyNine, serde_json::Error> {
+        Ok(TenNinetyNine {
+            report_name: self.0.report_name.clone(),
+            report_date: self.0.report_date.clone(),
+            vendors: self.vendors()?,
+        })
+    }
+}
+
+impl BankSummaryReport {
+    /// Reads one [`BankAccountSummary`] per bank account row.
+    ///
+    /// Columns are matched by their header titles, falling back to Xero's
+    /// usual order (account, opening, received, spent, closing) when a title
+    /// is not recognised. The total row is skipped, as are rows with fewer
+    /// than five cells. Blank amounts read as zero.
+    #[must_use]
+    pub fn accounts(&self) -> Vec<BankAccountSummary> {
+        let header = self.0.rows.iter().find(|row| row.row_type == "Header");
+        let column = |title: &str, fallback: usize| {
+            header
+                .and_then(|row| {
+                    row.cells.iter().position(|cell| {
+                        cell.value
+                            .as_deref()
+                            .is_some_and(|value| value.eq_ignore_ascii_case(title))
+                    })
+                })
+                .unwrap_or(fallback)
+        };
+        let columns = [
+            column("Opening Balance", 1),
+            column("Cash Received", 2),
+            column("Cash Spent", 3),
+            column("Closing Balance", 4),
+        ];
+
+        self.0
+            .rows
+            .iter()
+            .filter(|row| row.row_type == "Section")
+            .flat_map(|section| &section.rows)
+            .filter(|row| row.row_type == "Row" && row.cells.len() >= 5)
+            .map(|row| {
+                let amount = |index: usize| {
+                    row.cells
+                        .get(index)
+                        .and_then(|cell| cell.value_as_decimal())
+                        .unwrap_or_default()
+                };
+                let name_cell = &row.cells[0];
+                BankAccountSummary {
+                    account_id: name_cell
+                        .attributes
+                        .iter()
+                        .find(|attribute| attribute.id.eq_ignore_ascii_case("accountID"))
+                        .and_then(|attribute| attribute.value.parse().ok()),
+                    account_name: name_cell.value.clone().unwrap_or_default(),
+                    opening: amount(columns[0]),
+                    cash_received: amount(columns[1]),
+                    cash_spent: amount(columns[2]),
+                    closing: amount(columns[3]),
+                }
+            })
+            .collect()
+    }
+
+    /// Converts the report into a typed [`BankSummary`].
+    #[must_use]
+    pub fn to_typed(&self) -> BankSummary {
+        BankSummary {
+            report_date: self.0.report_date.clone(),
+            accounts: self.accounts(),
+        }
+    }
+}
+
+/// A typed Bank Summary report: cash movement through each bank account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BankSummary {
+    /// The period label, e.g. `"1 Jan 2024 to 31 Jan 2024"`.
+    pub report_date: String,
+    pub accounts: Vec<BankAccountSummary>,
+}
+
+/// One bank account's row on the Bank Summary report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BankAccountSummary {
+    pub account_id: Option<Uuid>,
+    pub account_name: String,
+    pub opening: Money,
+    pub cash_received: Money,
+    pub cash_spent: Money,
+    pub closing: Money,
+}
+
+/// A typed 1099 report: one per form (e.g. 1099-NEC, 1099-MISC).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenNinetyNine {
+    pub report_name: String,
+    pub report_date: String,
+    pub vendors: Vec<Vendor1099>,
+}
+
+/// A single vendor row on a 1099 report, with the amount reported in each box.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Vendor1099 {
+    #[serde(rename = "ContactId")]
+    pub contact_id: Option<Uuid>,
+    pub name: String,
+    pub legal_name: Option<String>,
+    pub business_name: Option<String>,
+    #[serde(rename = "FederalTaxIDType")]
+    pub federal_tax_id_type: Option<String>,
+    #[serde(rename = "TaxID")]
+    pub tax_id: Option<String>,
+    pub email: Option<String>,
+    pub street_address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub box1: f64,
+    pub box2: f64,
+    pub box3: f64,
+    pub box4: f64,
+    pub box5: f64,
+    pub box6: f64,
+    pub box7: f64,
+    pub box8: f64,
+    pub box9: f64,
+    pub box10: f64,
+    pub box11: f64,
+    pub box12: f64,
+    pub box13: f64,
+    pub box14: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_values_accept_numbers_and_strings() {
+        let cells: Vec<ReportCell> =
+            serde_json::from_str(r#"[{"Value": 1234.5}, {"Value": "1,234.50"}, {}]"#).unwrap();
+        assert_eq!(cells[0].value.as_deref(), Some("1234.5"));
+        assert_eq!(cells[0].value_as_decimal(), cells[1].value_as_decimal());
+        assert_eq!(cells[2].value, None);
+    }
+
+    fn dated(report_date: &str, updated: &str) -> Report {
+        serde_json::from_value(serde_json::json!({
+            "ReportName": "Balance Sheet",
+            "ReportDate": report_date,
+            "UpdatedDateUTC": updated
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn report_dates_parse_from_either_api_style() {
+        let legacy = dated("31 December 2019", "/Date(1577750400000+0000)/");
+        let iso = dated("1 Jan 2019 to 31 Dec 2019", "2019-12-31T00:00:00Z");
+        assert_eq!(legacy.updated_date_utc, iso.updated_date_utc);
+        assert_eq!(
+            legacy.report_end_date(),
+            NaiveDate::from_ymd_opt(2019, 12, 31)
+        );
+        assert_eq!(iso.report_end_date(), NaiveDate::from_ymd_opt(2019, 12, 31));
+        assert_eq!(dated("As at today", "/Date(0)/").report_end_date(), None);
+    }
+
+    #[test]
+    fn parses_1099_vendor_rows() {
+        let report: Report = serde_json::from_value(serde_json::json!({
+            "ReportName": "1099-NEC report",
+            "ReportDate": "1 Jan 2023 to 31 Dec 2023",
+            "Contacts": [{
+                "Box1": 1150.0,
+                "Box2": 0.0,
+                "Box4": 25.5,
+                "Name": "Bank West",
+                "FederalTaxIDType": "SSN",
+                "City": "Pinehaven",
+                "Zip": "12345",
+                "State": "CA",
+                "Email": "jack@bowest.com",
+                "StreetAddress": "GPO 1234",
+                "TaxID": "234-22-2223",
+                "ContactId": "81d5706a-8057-4338-8511-747cd85f4c68"
+            }]
+        }))
+        .unwrap();
+
+        let typed = TenNinetyNineReport::try_from_report(report)
+            .unwrap()
+            .to_typed()
+            .unwrap();
+        assert_eq!(typed.report_name, "1099-NEC report");
+        let vendor = &typed.vendors[0];
+        assert_eq!(vendor.name, "Bank West");
+        assert_eq!(vendor.tax_id.as_deref(), Some("234-22-2223"));
+        assert_eq!(vendor.box1, 1150.0);
+        assert_eq!(vendor.box4, 25.5);
+        assert_eq!(vendor.box14, 0.0);
+        assert!(vendor.contact_id.is_some());
+    }
+
+    #[test]
+    fn bank_summary_rows_become_account_summaries() {
+        let report: Report = serde_json::from_value(serde_json::json!({
+            "ReportName": "Bank Summary",
+            "ReportType": "BankSummary",
+            "ReportDate": "1 Jan 2024 to 31 Jan 2024",
+            "Rows": [
+                { "RowType": "Header", "Cells": [
+                    { "Value": "Bank Accounts" }, { "Value": "Opening Balance" },
+                    { "Value": "Cash Received" }, { "Value": "Cash Spent" },
+                    { "Value": "Closing Balance" }
+                ]},
+                { "RowType": "Section", "Rows": [
+                    { "RowType": "Row", "Cells": [
+                        { "Value": "Business Bank Account", "Attributes": [
+                            { "Value": "13918178-849a-4823-9a31-57b7eac713d7", "Id": "accountID" }
+                        ]},
+                        { "Value": "1000.00" }, { "Value": "250.00" },
+                        { "Value": "100.00" }, { "Value": "1150.00" }
+                    ]},
+                    { "RowType": "SummaryRow", "Cells": [
+                        { "Value": "Total" }, { "Value": "1000.00" }, { "Value": "250.00" },
+                        { "Value": "100.00" }, { "Value": "1150.00" }
+                    ]}
+                ]}
+            ]
+        }))
+        .unwrap();
+
+        let summary = BankSummaryReport::try_from_report(report)
+            .unwrap()
+            .to_typed();
+        assert_eq!(summary.report_date, "1 Jan 2024 to 31 Jan 2024");
+        assert_eq!(summary.accounts.len(), 1);
+        let account = &summary.accounts[0];
+        assert_eq!(account.account_name, "Business Bank Account");
+        assert!(account.account_id.is_some());
+        assert_eq!(account.opening, Money::from(1000));
+        assert_eq!(account.cash_received, Money::from(250));
+        assert_eq!(account.cash_spent, Money::from(100));
+        assert_eq!(account.closing, Money::from(1150));
+    }
+
+    fn cell(value: &str) -> ReportCell {
+        ReportCell {
+            value: Some(value.to_string()),
+            attributes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_regionally_formatted_cell_values() {
+        assert_eq!(
+            cell("1234.5").value_as_decimal(),
+            Some(Money::from(1234) + Money::from(1) / Money::from(2))
+        );
+        assert_eq!(
+            cell("1,234,567").value_as_decimal(),
+            Some(Money::from(1_234_567))
+        );
+        assert_eq!(cell("$1 000").value_as_decimal(), Some(Money::from(1000)));
+        assert_eq!(cell("NZ$(250)").value_as_decimal(), Some(Money::from(-250)));
+        assert_eq!(cell("-€75").value_as_decimal(), Some(Money::from(-75)));
+        assert_eq!(cell("").value_as_decimal(), None);
+        assert_eq!(cell("Total Income").value_as_decimal(), None);
+        assert_eq!(
+            ReportCell {
+                value: None,
+                attributes: Vec::new()
+            }
+            .value_as_decimal(),
+            None
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Model for the `TaxRate` resource.
 
+use super::common::impl_xero_value;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -10,8 +11,11 @@ pub enum TaxRateStatus {
     Archived,
 }
 
+impl_xero_value!(TaxRateStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct TaxRate {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,6 +43,7 @@ pub struct TaxRate {
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct TaxComponent {
     pub name: String,
     pub rate: f64,
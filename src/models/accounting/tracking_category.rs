@@ -1,5 +1,6 @@
 //! Model for the `TrackingCategory` resource.
 
+use super::common::impl_xero_value;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -10,6 +11,8 @@ pub enum TrackingCategoryStatus {
     Archived,
 }
 
+impl_xero_value!(TrackingCategoryStatus);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrackingCategory {
@@ -55,7 +58,6 @@ pub(crate) struct TrackingOptionsResponse {
 }
 
 // Wrapper for the options request
-#[allow(dead_code)]
 #[derive(Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct TrackingOptionsRequest {
@@ -1,5 +1,6 @@
 //! Model for the User resource.
 
+use super::common::impl_xero_value;
 use crate::util::xero_date_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -16,10 +17,16 @@ pub enum UserRole {
     Cashbookclient,
     Admin,
     Removed,
+    /// A role this crate does not know, including Xero's own `UNKNOWN`.
+    #[serde(other)]
+    Unknown,
 }
 
+impl_xero_value!(UserRole);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct User {
     // FIX: Made optional as API sometimes returns GlobalUserID instead
     #[serde(rename = "UserID", skip_serializing_if = "Option::is_none")]
@@ -40,9 +47,51 @@ pub struct User {
     pub organisation_role: Option<UserRole>,
 }
 
+impl User {
+    /// Returns `true` if the user has the financial adviser role.
+    #[must_use]
+    pub fn is_financial_adviser(&self) -> bool {
+        self.organisation_role == Some(UserRole::Financialadviser)
+    }
+}
+
 // Wrapper for the response
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct UsersResponse {
     pub users: Vec<User>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_round_trips_with_typed_role() {
+        let payload = r#"{
+            "UserID": "7cf47fe2-c3dd-4c6b-9895-7ba767ba529c",
+            "EmailAddress": "john.smith@mail.com",
+            "FirstName": "John",
+            "LastName": "Smith",
+            "UpdatedDateUTC": "/Date(1516230549137+0000)/",
+            "IsSubscriber": true,
+            "OrganisationRole": "FINANCIALADVISER"
+        }"#;
+
+        let user: User = serde_json::from_str(payload).unwrap();
+        assert!(user.is_financial_adviser());
+        assert_eq!(user.is_subscriber, Some(true));
+        assert_eq!(user.email_address.as_deref(), Some("john.smith@mail.com"));
+
+        let json = serde_json::to_value(&user).unwrap();
+        assert_eq!(json["OrganisationRole"], "FINANCIALADVISER");
+        assert_eq!(json["UpdatedDateUTC"], "/Date(1516230549137)/");
+        assert_eq!(serde_json::from_value::<User>(json).unwrap(), user);
+    }
+
+    #[test]
+    fn unknown_roles_do_not_fail_parsing() {
+        let role: UserRole = serde_json::from_str(r#""PAYROLLADMIN""#).unwrap();
+        assert_eq!(role, UserRole::Unknown);
+    }
+}
@@ -6,34 +6,72 @@ use uuid::Uuid;
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct FeedConnection {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
+    /// `PENDING` or `REJECTED` while Xero processes the connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BankFeedsError>,
+}
+
+/// Problem details returned by the Bank Feeds API for a rejected item.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BankFeedsError {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct FeedConnectionCreate {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct FeedConnectionDeleteRequestItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
 }
 
@@ -68,9 +106,14 @@ pub struct FeedConnectionsCreateResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct FeedConnectionResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
-    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BankFeedsError>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1,5 +1,6 @@
 //! Models for Bank Feeds Statements.
 
+use super::feed_connection::BankFeedsError;
 use crate::util::xero_naive_date_format_opt;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
@@ -8,33 +9,56 @@ use uuid::Uuid;
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct Balance {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub credit_debit_indicator: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct StatementLine {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub credit_debit_indicator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<String>,
     #[serde(with = "xero_naive_date_format_opt", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub posted_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payee_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cheque_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct Statement {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub feed_connection_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
     #[serde(with = "xero_naive_date_format_opt", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_date: Option<NaiveDate>,
     #[serde(with = "xero_naive_date_format_opt", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_balance: Option<Balance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub end_balance: Option<Balance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub statement_line_count: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub statement_lines: Vec<StatementLine>,
@@ -73,5 +97,65 @@ pub struct StatementsCreateResponse {
 pub struct StatementCreateResult {
     pub id: Option<Uuid>,
     pub status: Option<String>,
-    pub errors: Option<Vec<serde_json::Value>>,
+    pub errors: Option<Vec<BankFeedsError>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn new_statement_omits_unset_fields() {
+        let statement = Statement {
+            feed_connection_id: Some(Uuid::nil()),
+            start_date: NaiveDate::from_ymd_opt(2024, 3, 1),
+            end_date: NaiveDate::from_ymd_opt(2024, 3, 1),
+            start_balance: Some(Balance {
+                amount: Some("100.00".to_string()),
+                credit_debit_indicator: Some("CREDIT".to_string()),
+            }),
+            statement_lines: vec![StatementLine {
+                amount: Some("25.00".to_string()),
+                credit_debit_indicator: Some("DEBIT".to_string()),
+                description: Some("Coffee".to_string()),
+                transaction_id: Some("txn-1".to_string()),
+                posted_date: NaiveDate::from_ymd_opt(2024, 3, 1),
+                payee_name: Some("Cafe".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let body = serde_json::to_value(&statement).unwrap();
+        assert!(body.get("id").is_none());
+        assert!(body.get("status").is_none());
+        assert_eq!(body["feedConnectionId"], json!(Uuid::nil()));
+        assert_eq!(
+            body["startBalance"],
+            json!({ "amount": "100.00", "creditDebitIndicator": "CREDIT" })
+        );
+        assert_eq!(body["statementLines"][0]["payeeName"], "Cafe");
+        assert!(body["statementLines"][0].get("chequeNumber").is_none());
+    }
+
+    #[test]
+    fn create_result_parses_problem_details() {
+        let result: StatementsCreateResponse = serde_json::from_value(json!({
+            "items": [{
+                "id": null,
+                "status": "REJECTED",
+                "errors": [{
+                    "type": "invalid-start-date",
+                    "title": "Invalid Start Date",
+                    "status": 409,
+                    "detail": "Start date must be on or after the feed connection start date"
+                }]
+            }]
+        }))
+        .unwrap();
+        let errors = result.items.unwrap().remove(0).errors.unwrap();
+        assert_eq!(errors[0].error_type.as_deref(), Some("invalid-start-date"));
+        assert_eq!(errors[0].status, Some(409));
+    }
 }
@@ -4,6 +4,112 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Defines a string enum that parses case-insensitively and keeps values
+/// this crate does not know in an `Unknown` variant.
+macro_rules! open_string_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)+
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Returns the value Xero uses for this variant.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+
+            /// Parses a Xero value, ignoring case.
+            #[must_use]
+            pub fn from_value(value: &str) -> Self {
+                $(if value.eq_ignore_ascii_case($value) {
+                    return Self::$variant;
+                })+
+                Self::Unknown(value.to_string())
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                Ok(Self::from_value(&value))
+            }
+        }
+    };
+}
+
+open_string_enum!(
+    /// The kind of Accounting object a file is associated with.
+    ObjectGroup {
+        Account => "Account",
+        BankTransaction => "BankTransaction",
+        Contact => "Contact",
+        CreditNote => "CreditNote",
+        Invoice => "Invoice",
+        Item => "Item",
+        ManualJournal => "ManualJournal",
+        Overpayment => "Overpayment",
+        Payment => "Payment",
+        Prepayment => "Prepayment",
+        Quote => "Quote",
+        Receipt => "Receipt",
+    }
+);
+
+open_string_enum!(
+    /// The specific type of the associated object, e.g. a sales or purchase invoice.
+    ObjectType {
+        AccPay => "AccPay",
+        AccPayCredit => "AccPayCredit",
+        AccPayPayment => "AccPayPayment",
+        AccRec => "AccRec",
+        AccRecCredit => "AccRecCredit",
+        AccRecPayment => "AccRecPayment",
+        Adjustment => "Adjustment",
+        ApCreditPayment => "APCreditPayment",
+        ApOverpayment => "APOverPayment",
+        ApPrepayment => "APPrepayment",
+        ArCreditPayment => "ARCreditPayment",
+        ArOverpayment => "AROverPayment",
+        ArPrepayment => "ARPrepayment",
+        CashPaid => "CashPaid",
+        CashRec => "CashRec",
+        Contact => "Contact",
+        ExpPayment => "ExpPayment",
+        FixedAsset => "FixedAsset",
+        ManualJournal => "ManualJournal",
+        PayRun => "PayRun",
+        PriceListItem => "PriceListItem",
+        PurchaseOrder => "PurchaseOrder",
+        Receipt => "Receipt",
+        Transfer => "Transfer",
+    }
+);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Association {
@@ -11,9 +117,9 @@ pub struct Association {
     pub file_id: Uuid,
     #[serde(rename = "ObjectId")]
     pub object_id: Uuid,
-    pub object_group: String,
+    pub object_group: ObjectGroup,
     #[serde(rename = "ObjectType", skip_serializing_if = "Option::is_none")]
-    pub object_type: Option<String>,
+    pub object_type: Option<ObjectType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub send_with_object: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -43,3 +149,37 @@ impl AssociationsResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_kinds_parse_case_insensitively_with_fallback() {
+        let payload = r#"[{
+            "FileId": "d290f1ee-6c54-4b01-90e6-d701748f0851",
+            "ObjectId": "9a59ea90-942e-484d-9b71-d00ab607e03b",
+            "ObjectGroup": "INVOICE",
+            "ObjectType": "AccRec",
+            "SendWithObject": true
+        }, {
+            "FileId": "d290f1ee-6c54-4b01-90e6-d701748f0851",
+            "ObjectId": "4ff1e5cc-9835-40d5-bb18-09fdb118db9c",
+            "ObjectGroup": "Timesheet"
+        }]"#;
+
+        let parsed: AssociationsResponse = serde_json::from_str(payload).unwrap();
+        let associations = parsed.into_vec();
+        assert_eq!(associations[0].object_group, ObjectGroup::Invoice);
+        assert_eq!(associations[0].object_type, Some(ObjectType::AccRec));
+        assert_eq!(
+            associations[1].object_group,
+            ObjectGroup::Unknown("Timesheet".to_string())
+        );
+
+        let json = serde_json::to_value(&associations[0]).unwrap();
+        assert_eq!(json["ObjectGroup"], "Invoice");
+        assert_eq!(json["ObjectType"], "AccRec");
+        assert_eq!(ObjectType::ApOverpayment.to_string(), "APOverPayment");
+    }
+}
@@ -1,23 +1,44 @@
 //! Model for the File resource.
 
-use super::folder::User;
-use crate::util::xero_date_format_opt;
+use crate::util::iso_datetime_format_opt;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// The Xero user who uploaded a file.
+///
+/// Only `Id` and `Name` (the user's email address) are always present.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct User {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(rename = "Id")]
+    pub id: Uuid,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct File {
     pub name: String,
     pub mime_type: String,
     pub size: u64,
-    #[serde(with = "xero_date_format_opt", default, rename = "CreatedDateUTC")]
+    /// The Files API sends ISO 8601 timestamps in UTC without an offset,
+    /// under `CreatedDateUtc` rather than the Accounting API's `CreatedDateUTC`.
+    #[serde(with = "iso_datetime_format_opt", default)]
+    #[serde(rename = "CreatedDateUtc", alias = "CreatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_date_utc: Option<DateTime<Utc>>,
-    #[serde(with = "xero_date_format_opt", default, rename = "UpdatedDateUTC")]
+    #[serde(with = "iso_datetime_format_opt", default)]
+    #[serde(rename = "UpdatedDateUtc", alias = "UpdatedDateUTC")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_date_utc: Option<DateTime<Utc>>,
+    /// Who uploaded the file.
     pub user: User,
     #[serde(rename = "FolderId", skip_serializing_if = "Option::is_none")]
     pub folder_id: Option<Uuid>,
@@ -31,3 +52,69 @@ pub struct File {
 pub(crate) struct FilesResponse {
     pub items: Vec<File>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn deserializes_files_api_payload() {
+        let payload = r#"{
+            "TotalCount": 1,
+            "Page": 1,
+            "PerPage": 50,
+            "Items": [{
+                "Name": "sample.jpg",
+                "MimeType": "image/jpeg",
+                "Size": 2878711,
+                "CreatedDateUtc": "2020-12-03T19:04:58.1030000",
+                "UpdatedDateUtc": "2020-12-03T19:04:58.1030000",
+                "User": {
+                    "Id": "4ff1e5cc-9835-40d5-bb18-09fdb118db9c",
+                    "Name": "api@xero.com",
+                    "FirstName": "API",
+                    "LastName": "User",
+                    "FullName": "API User"
+                },
+                "Id": "d290f1ee-6c54-4b01-90e6-d701748f0851",
+                "FolderId": "0f8ccf21-7267-4268-9167-a1e2c40c84c8"
+            }]
+        }"#;
+
+        let parsed: FilesResponse =
+            serde_json::from_str(payload).expect("files response should deserialize");
+        let file = &parsed.items[0];
+        assert_eq!(file.size, 2_878_711);
+        assert_eq!(file.mime_type, "image/jpeg");
+        assert_eq!(file.user.full_name.as_deref(), Some("API User"));
+        assert_eq!(
+            file.folder_id,
+            Some(Uuid::parse_str("0f8ccf21-7267-4268-9167-a1e2c40c84c8").unwrap())
+        );
+        let created = Utc.with_ymd_and_hms(2020, 12, 3, 19, 4, 58).unwrap()
+            + chrono::Duration::milliseconds(103);
+        assert_eq!(file.created_date_utc, Some(created));
+        assert_eq!(file.updated_date_utc, Some(created));
+    }
+
+    #[test]
+    fn user_needs_only_id_and_name() {
+        let payload = r#"{
+            "Name": "receipt.pdf",
+            "MimeType": "application/pdf",
+            "Size": 1024,
+            "User": {
+                "Id": "4ff1e5cc-9835-40d5-bb18-09fdb118db9c",
+                "Name": "inbox@example.com"
+            },
+            "Id": "d290f1ee-6c54-4b01-90e6-d701748f0851"
+        }"#;
+
+        let file: File = serde_json::from_str(payload).expect("file should deserialize");
+        assert_eq!(file.user.name, "inbox@example.com");
+        assert_eq!(file.user.full_name, None);
+        let value = serde_json::to_value(&file.user).unwrap();
+        assert!(value.get("FirstName").is_none());
+    }
+}
@@ -1,13 +1,29 @@
 //! Implements a robust, per-tenant rate limiting mechanism to comply with all Xero API limits.
+//!
+//! Request history is kept by a [`RateLimitBackend`]. The default
+//! [`InMemoryBackend`] only sees the current process; plug in a shared one
+//! (e.g. backed by Redis) with [`RateLimiter::with_backend`] so that several
+//! workers calling Xero for the same tenants stay within its limits together.
+//!
+//! [`RateLimiter::export_state`] and [`RateLimiter::import_state`] hand the
+//! recent history from one process to another, e.g. during a zero-downtime
+//! deploy, so the new instance does not start with a clean slate.
+//!
+//! The client only depends on the [`RequestLimiter`] trait. Pass a
+//! [`NoOpRateLimiter`] instead of a [`RateLimiter`] when requests are already
+//! throttled elsewhere, such as by a central rate-limiting proxy.
 
 use crate::error::XeroError;
 use dashmap::DashMap;
 use log::{debug, trace, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 // Import the async Mutex
-use tokio::sync::{Mutex, Semaphore};
+use tokio::sync::{Mutex, Notify, Semaphore, SemaphorePermit};
 use tokio::time::{sleep, Duration};
 use uuid::Uuid;
 
@@ -15,11 +31,116 @@ const CONCURRENT_LIMIT: usize = 5;
 const MINUTE_LIMIT: u32 = 60;
 const DAILY_LIMIT: u32 = 5000;
 const RATE_LIMIT_BUFFER: u32 = 2;
+const DAY_SECONDS: i64 = 86400;
 
-/// Represents the request history for a single tenant.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
-struct TenantRateLimitState {
-    requests: VecDeque<i64>, // Timestamps of requests in seconds
+/// The future returned by [`RateLimitBackend`] and [`RequestLimiter`] methods.
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, XeroError>> + Send + 'a>>;
+
+/// Storage for each tenant's request timestamps, in Unix seconds.
+///
+/// [`RateLimiter`] queries the last day of a tenant's requests before
+/// granting a permit and records the request once it does. Requests for one
+/// tenant are serialized within a process, but processes sharing a backend
+/// check and record independently, so a burst can briefly overshoot by a
+/// request or two per process; Xero's `429` handling absorbs the rest.
+pub trait RateLimitBackend: Send + Sync + std::fmt::Debug {
+    /// Returns the timestamps of `tenant_id`'s requests made after `since`.
+    fn request_times(&self, tenant_id: Uuid, since: i64) -> BackendFuture<'_, Vec<i64>>;
+
+    /// Records a request for `tenant_id` made at `at`.
+    ///
+    /// Backends may discard timestamps older than a day.
+    fn record_request(&self, tenant_id: Uuid, at: i64) -> BackendFuture<'_, ()>;
+
+    /// Returns every tenant's request timestamps after `since`.
+    ///
+    /// Used by [`RateLimiter::export_state`]. Shared backends have no need to
+    /// hand their state over, so the default reports that it is unsupported.
+    fn snapshot(&self, since: i64) -> BackendFuture<'_, HashMap<Uuid, Vec<i64>>> {
+        let _ = since;
+        Box::pin(async {
+            Err(XeroError::RateLimiter(
+                "This rate limit backend cannot export its state.".to_string(),
+            ))
+        })
+    }
+}
+
+/// Recent request history of every tenant, as Unix-second timestamps.
+///
+/// Produced by [`RateLimiter::export_state`] and restored with
+/// [`RateLimiter::import_state`]; serialize it to move it between processes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimiterState {
+    pub tenants: HashMap<Uuid, Vec<i64>>,
+}
+
+/// The default backend: request history held in this process's memory.
+#[derive(Debug, Default)]
+pub struct InMemoryBackend {
+    tenants: DashMap<Uuid, VecDeque<i64>>,
+}
+
+impl RateLimitBackend for InMemoryBackend {
+    fn request_times(&self, tenant_id: Uuid, since: i64) -> BackendFuture<'_, Vec<i64>> {
+        let times = self
+            .tenants
+            .get(&tenant_id)
+            .map(|requests| requests.iter().copied().filter(|&t| t > since).collect())
+            .unwrap_or_default();
+        Box::pin(async move { Ok(times) })
+    }
+
+    fn record_request(&self, tenant_id: Uuid, at: i64) -> BackendFuture<'_, ()> {
+        let mut requests = self.tenants.entry(tenant_id).or_default();
+        requests.retain(|&t| at - t < DAY_SECONDS);
+        requests.push_back(at);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn snapshot(&self, since: i64) -> BackendFuture<'_, HashMap<Uuid, Vec<i64>>> {
+        let tenants = self
+            .tenants
+            .iter()
+            .map(|entry| {
+                let times = entry.value().iter().copied().filter(|&t| t > since);
+                (*entry.key(), times.collect::<Vec<_>>())
+            })
+            .filter(|(_, times)| !times.is_empty())
+            .collect();
+        Box::pin(async move { Ok(tenants) })
+    }
+}
+
+/// Permission to send one request, held until the response arrives.
+///
+/// Dropping it frees any concurrency slot it holds.
+#[derive(Debug)]
+pub struct RequestPermit<'a> {
+    _slot: Option<SemaphorePermit<'a>>,
+}
+
+/// Decides when the client may send each request.
+///
+/// The client calls [`RequestLimiter::acquire`] before every request and
+/// holds the permit until the response arrives. [`RateLimiter`] enforces
+/// Xero's limits; [`NoOpRateLimiter`] grants every permit immediately.
+pub trait RequestLimiter: Send + Sync + std::fmt::Debug {
+    /// Waits until a request for `tenant_id` may be sent.
+    fn acquire(&self, tenant_id: Uuid) -> BackendFuture<'_, RequestPermit<'_>>;
+}
+
+/// A limiter that never waits, for callers that rate limit requests themselves.
+///
+/// Xero still answers with `429 Too Many Requests` once a limit is exceeded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpRateLimiter;
+
+impl RequestLimiter for NoOpRateLimiter {
+    fn acquire(&self, tenant_id: Uuid) -> BackendFuture<'_, RequestPermit<'_>> {
+        trace!("Rate limiting disabled; granting permit for tenant {tenant_id}");
+        Box::pin(async { Ok(RequestPermit { _slot: None }) })
+    }
 }
 
 /// Manages and enforces Xero API rate limits across multiple tenants concurrently.
@@ -27,45 +148,120 @@ struct TenantRateLimitState {
 pub struct RateLimiter {
     // Manages the 5 concurrent request limit globally for this client instance.
     concurrent_semaphore: Semaphore,
-    // Stores the rate limit state for each tenant in memory.
-    tenant_states: DashMap<Uuid, Arc<Mutex<TenantRateLimitState>>>,
+    // Where each tenant's request history is kept.
+    backend: Arc<dyn RateLimitBackend>,
+    // Serializes the check-and-record for each tenant within this process.
+    tenant_locks: DashMap<Uuid, Arc<Mutex<()>>>,
+    // Set by `begin_shutdown`; waiters are woken through `shutdown_notify`.
+    shutting_down: AtomicBool,
+    shutdown_notify: Notify,
 }
 
 impl RateLimiter {
     /// Creates a new `RateLimiter` with in-memory state.
     pub async fn new() -> Result<Self, XeroError> {
         debug!("Initializing RateLimiter with in-memory state");
-        let tenant_states: DashMap<Uuid, Arc<Mutex<TenantRateLimitState>>> = DashMap::new();
+        Ok(Self::with_backend(Arc::new(InMemoryBackend::default())))
+    }
 
-        Ok(Self {
+    /// Creates a `RateLimiter` that keeps request history in `backend`.
+    ///
+    /// Give every process the same shared backend to enforce the per-tenant
+    /// minute and daily limits across all of them. The limit of 5 concurrent
+    /// requests still applies per `RateLimiter`.
+    pub fn with_backend(backend: Arc<dyn RateLimitBackend>) -> Self {
+        Self {
             concurrent_semaphore: Semaphore::new(CONCURRENT_LIMIT),
-            tenant_states,
-        })
+            backend,
+            tenant_locks: DashMap::new(),
+            shutting_down: AtomicBool::new(false),
+            shutdown_notify: Notify::new(),
+        }
+    }
+
+    /// Snapshots the last day of every tenant's requests.
+    ///
+    /// Fails with [`XeroError::RateLimiter`] if the backend cannot enumerate
+    /// its state; see [`RateLimitBackend::snapshot`].
+    pub async fn export_state(&self) -> Result<RateLimiterState, XeroError> {
+        let since = chrono::Utc::now().timestamp() - DAY_SECONDS;
+        let tenants = self.backend.snapshot(since).await?;
+        Ok(RateLimiterState { tenants })
+    }
+
+    /// Records the requests in `state` as if this limiter had made them.
+    ///
+    /// Timestamps older than a day are skipped. Import into a fresh limiter:
+    /// requests it already knows about would be counted twice.
+    pub async fn import_state(&self, state: RateLimiterState) -> Result<(), XeroError> {
+        let since = chrono::Utc::now().timestamp() - DAY_SECONDS;
+        for (tenant_id, mut times) in state.tenants {
+            times.retain(|&t| t > since);
+            times.sort_unstable();
+            debug!(
+                "Importing {} rate limit entries for tenant {tenant_id}",
+                times.len()
+            );
+            for at in times {
+                self.backend.record_request(tenant_id, at).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops granting permits so the process can exit promptly.
+    ///
+    /// Callers waiting in [`Self::acquire_permit`], whether for a concurrency
+    /// slot or for the per-minute window, return a [`XeroError::RateLimiter`]
+    /// straight away, as does every later call. Requests already holding a
+    /// permit are unaffected. Shutdown cannot be undone.
+    pub fn begin_shutdown(&self) {
+        debug!("RateLimiter shutting down; rejecting pending and new permits");
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.concurrent_semaphore.close();
+        self.shutdown_notify.notify_waiters();
+    }
+
+    /// Returns `true` once [`Self::begin_shutdown`] has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    fn shutdown_error() -> XeroError {
+        XeroError::RateLimiter("Rate limiter is shutting down.".to_string())
     }
 
     /// Acquires a permit to make a request for a specific tenant, waiting if necessary.
-    pub async fn acquire_permit(
-        &self,
-        tenant_id: Uuid,
-    ) -> Result<tokio::sync::SemaphorePermit<'_>, XeroError> {
+    ///
+    /// The wait can last up to about a minute when the tenant is near its
+    /// per-minute limit. The future is cancellation-safe: dropping it (for
+    /// example from a `tokio::select!` branch) releases the concurrency slot
+    /// and tenant lock, and nothing is recorded against the tenant's limits
+    /// until the permit is actually granted. Use [`Self::acquire_permit_timeout`]
+    /// to bound the wait.
+    pub async fn acquire_permit(&self, tenant_id: Uuid) -> Result<SemaphorePermit<'_>, XeroError> {
         trace!("Attempting to acquire permit for tenant {tenant_id}");
-        let permit = self.concurrent_semaphore.acquire().await.unwrap();
+        let permit = self
+            .concurrent_semaphore
+            .acquire()
+            .await
+            .map_err(|_| Self::shutdown_error())?;
         debug!("Acquired concurrency permit for tenant {tenant_id}");
 
-        let tenant_state_lock = self.tenant_states.entry(tenant_id).or_default().clone();
-
-        let mut state = tenant_state_lock.lock().await;
-        let now = chrono::Utc::now().timestamp();
-
-        state.requests.retain(|&t| now - t < 86400);
+        let tenant_lock = self.tenant_locks.entry(tenant_id).or_default().clone();
+        let _tenant_guard = tenant_lock.lock().await;
 
-        loop {
+        let now = loop {
             let now = chrono::Utc::now().timestamp();
             let minute_ago = now - 60;
+            let requests = self
+                .backend
+                .request_times(tenant_id, now - DAY_SECONDS)
+                .await?;
 
             let requests_in_last_minute =
-                state.requests.iter().filter(|&&t| t > minute_ago).count() as u32;
-            let requests_in_last_day = state.requests.len() as u32;
+                requests.iter().filter(|&&t| t > minute_ago).count() as u32;
+            let requests_in_last_day = requests.len() as u32;
 
             if requests_in_last_day >= DAILY_LIMIT - RATE_LIMIT_BUFFER {
                 return Err(XeroError::RateLimiter(format!(
@@ -74,24 +270,176 @@ impl RateLimiter {
             }
 
             if requests_in_last_minute >= MINUTE_LIMIT - RATE_LIMIT_BUFFER {
-                if let Some(oldest_in_minute) = state.requests.iter().find(|&&t| t > minute_ago) {
+                if let Some(oldest_in_minute) = requests.iter().filter(|&&t| t > minute_ago).min() {
                     let wait_seconds = (oldest_in_minute + 61 - now).max(1);
                     warn!(
                         "Minute rate limit approaching for tenant {tenant_id}. Waiting for {wait_seconds} seconds."
                     );
-                    sleep(Duration::from_secs(wait_seconds as u64)).await;
-                    continue;
+                    // Register for the shutdown wake-up before checking the
+                    // flag, so a shutdown in between cannot be missed.
+                    let shutdown = self.shutdown_notify.notified();
+                    tokio::pin!(shutdown);
+                    shutdown.as_mut().enable();
+                    if self.is_shutting_down() {
+                        return Err(Self::shutdown_error());
+                    }
+                    tokio::select! {
+                        () = sleep(Duration::from_secs(wait_seconds as u64)) => continue,
+                        () = shutdown => return Err(Self::shutdown_error()),
+                    }
                 }
             }
-            break;
-        }
+            break now;
+        };
 
         trace!("Permit granted. Recording request for tenant {tenant_id}");
-        state.requests.push_back(now);
-
-        // Drop the lock on the individual tenant's state
-        drop(state);
+        self.backend.record_request(tenant_id, now).await?;
 
         Ok(permit)
     }
+
+    /// Like [`Self::acquire_permit`], but gives up after `timeout`.
+    ///
+    /// Returns [`XeroError::RateLimiter`] if no permit was granted in time.
+    pub async fn acquire_permit_timeout(
+        &self,
+        tenant_id: Uuid,
+        timeout: Duration,
+    ) -> Result<SemaphorePermit<'_>, XeroError> {
+        tokio::time::timeout(timeout, self.acquire_permit(tenant_id))
+            .await
+            .map_err(|_| {
+                XeroError::RateLimiter(format!(
+                    "Timed out after {timeout:?} waiting for a permit for tenant {tenant_id}."
+                ))
+            })?
+    }
+}
+
+impl RequestLimiter for RateLimiter {
+    fn acquire(&self, tenant_id: Uuid) -> BackendFuture<'_, RequestPermit<'_>> {
+        Box::pin(async move {
+            let slot = self.acquire_permit(tenant_id).await?;
+            Ok(RequestPermit { _slot: Some(slot) })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fills `tenant_id`'s current minute in `backend`.
+    async fn reach_minute_limit(backend: &dyn RateLimitBackend, tenant_id: Uuid) {
+        let now = chrono::Utc::now().timestamp();
+        for _ in 0..MINUTE_LIMIT {
+            backend.record_request(tenant_id, now).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn acquire_permit_timeout_gives_up_at_minute_limit() {
+        let limiter = RateLimiter::new().await.unwrap();
+        let tenant_id = Uuid::new_v4();
+        reach_minute_limit(limiter.backend.as_ref(), tenant_id).await;
+
+        let err = limiter
+            .acquire_permit_timeout(tenant_id, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XeroError::RateLimiter(msg) if msg.contains("Timed out")));
+
+        // The abandoned wait must not have been recorded against the tenant.
+        let recorded = limiter.backend.request_times(tenant_id, 0).await.unwrap();
+        assert_eq!(recorded.len(), MINUTE_LIMIT as usize);
+
+        assert!(limiter
+            .acquire_permit_timeout(Uuid::new_v4(), Duration::from_millis(50))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn no_op_limiter_grants_permits_immediately() {
+        let limiter: Arc<dyn RequestLimiter> = Arc::new(NoOpRateLimiter);
+        let tenant_id = Uuid::new_v4();
+        let mut held = Vec::new();
+        for _ in 0..=CONCURRENT_LIMIT {
+            let permit =
+                tokio::time::timeout(Duration::from_millis(50), limiter.acquire(tenant_id))
+                    .await
+                    .expect("no-op limiter should not wait")
+                    .unwrap();
+            held.push(permit);
+        }
+        assert_eq!(held.len(), CONCURRENT_LIMIT + 1);
+    }
+
+    #[tokio::test]
+    async fn begin_shutdown_releases_waiters_at_minute_limit() {
+        let limiter = Arc::new(RateLimiter::new().await.unwrap());
+        let tenant_id = Uuid::new_v4();
+        reach_minute_limit(limiter.backend.as_ref(), tenant_id).await;
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire_permit(tenant_id).await.map(drop) })
+        };
+        // Let the waiter reach its minute-limit sleep.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        limiter.begin_shutdown();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should return promptly after shutdown")
+            .unwrap();
+        assert!(
+            matches!(result, Err(XeroError::RateLimiter(msg)) if msg.contains("shutting down"))
+        );
+
+        assert!(limiter.is_shutting_down());
+        assert!(limiter.acquire_permit(Uuid::new_v4()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn limiters_sharing_a_backend_share_the_limits() {
+        let backend = Arc::new(InMemoryBackend::default());
+        let worker_a = RateLimiter::with_backend(backend.clone());
+        let worker_b = RateLimiter::with_backend(backend.clone());
+        let tenant_id = Uuid::new_v4();
+
+        for _ in 0..MINUTE_LIMIT - RATE_LIMIT_BUFFER {
+            worker_a.acquire_permit(tenant_id).await.map(drop).unwrap();
+        }
+        let err = worker_b
+            .acquire_permit_timeout(tenant_id, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XeroError::RateLimiter(msg) if msg.contains("Timed out")));
+    }
+
+    #[tokio::test]
+    async fn exported_state_carries_the_limits_to_another_limiter() {
+        let old = RateLimiter::new().await.unwrap();
+        let tenant_id = Uuid::new_v4();
+        reach_minute_limit(old.backend.as_ref(), tenant_id).await;
+        let stale = chrono::Utc::now().timestamp() - 2 * DAY_SECONDS;
+        old.backend
+            .record_request(Uuid::new_v4(), stale)
+            .await
+            .unwrap();
+
+        let state = old.export_state().await.unwrap();
+        assert_eq!(state.tenants.len(), 1, "day-old history is not exported");
+        let state: RateLimiterState =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+
+        let new = RateLimiter::new().await.unwrap();
+        new.import_state(state).await.unwrap();
+        let err = new
+            .acquire_permit_timeout(tenant_id, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XeroError::RateLimiter(msg) if msg.contains("Timed out")));
+    }
 }
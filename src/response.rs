@@ -0,0 +1,107 @@
+//! Metadata Xero returns alongside the records of a response.
+//!
+//! The simple getters return only the records. The `send_detailed` variants of
+//! the list builders wrap them in a [`Response`] that also carries the rate
+//! limit headroom, the correlation id to quote to Xero support, and any
+//! response-level warnings.
+
+use crate::error::ApiValidationError;
+use reqwest::header::HeaderMap;
+
+/// Header carrying the id Xero support uses to trace a request.
+const CORRELATION_ID_HEADER: &str = "xero-correlation-id";
+
+/// Remaining API calls Xero reported on a response.
+///
+/// Each count is `None` if its header was missing or unreadable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateLimitSnapshot {
+    /// Calls left this minute for the tenant (`X-MinLimit-Remaining`).
+    pub minute_remaining: Option<u32>,
+    /// Calls left today for the tenant (`X-DayLimit-Remaining`).
+    pub day_remaining: Option<u32>,
+    /// Calls left this minute for the app across all tenants
+    /// (`X-AppMinLimit-Remaining`).
+    pub app_minute_remaining: Option<u32>,
+}
+
+impl RateLimitSnapshot {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let count = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u32>().ok();
+        Self {
+            minute_remaining: count("x-minlimit-remaining"),
+            day_remaining: count("x-daylimit-remaining"),
+            app_minute_remaining: count("x-appminlimit-remaining"),
+        }
+    }
+}
+
+/// Parsed records together with the response's metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response<T> {
+    pub items: T,
+    pub rate_limit: RateLimitSnapshot,
+    /// The `Xero-Correlation-Id` header, if Xero sent one.
+    pub correlation_id: Option<String>,
+    /// Response-level warnings; warnings about one record stay on that record.
+    pub warnings: Vec<ApiValidationError>,
+}
+
+/// The header-derived part of a [`Response`], captured before the body is read.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ResponseMeta {
+    rate_limit: RateLimitSnapshot,
+    correlation_id: Option<String>,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            rate_limit: RateLimitSnapshot::from_headers(headers),
+            correlation_id: headers
+                .get(CORRELATION_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+
+    pub(crate) fn into_response<T>(
+        self,
+        items: T,
+        warnings: Vec<ApiValidationError>,
+    ) -> Response<T> {
+        Response {
+            items,
+            rate_limit: self.rate_limit,
+            correlation_id: self.correlation_id,
+            warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn metadata_is_read_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-MinLimit-Remaining", HeaderValue::from_static("57"));
+        headers.insert("X-DayLimit-Remaining", HeaderValue::from_static("4990"));
+        headers.insert("X-AppMinLimit-Remaining", HeaderValue::from_static("oops"));
+        headers.insert("Xero-Correlation-Id", HeaderValue::from_static("abc-123"));
+
+        let response = ResponseMeta::from_headers(&headers).into_response(vec![1], Vec::new());
+        assert_eq!(
+            response.rate_limit,
+            RateLimitSnapshot {
+                minute_remaining: Some(57),
+                day_remaining: Some(4990),
+                app_minute_remaining: None,
+            }
+        );
+        assert_eq!(response.correlation_id.as_deref(), Some("abc-123"));
+        assert_eq!(response.items, vec![1]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Fixtures shared by the unit tests: a client with placeholder credentials
+//! and a local HTTP server that plays back scripted responses.
+
+#[cfg(feature = "accounting")]
+use crate::api::accounting::AccountingApi;
+use crate::auth::TokenSet;
+use crate::client::{TenantClient, XeroClient};
+use crate::rate_limiter::RateLimiter;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// The tenant that [`tenant`] is bound to.
+pub(crate) const TENANT_ID: Uuid = Uuid::from_u128(1);
+
+/// A code-flow client that is never pointed at Xero's identity service.
+pub(crate) async fn client() -> XeroClient {
+    let rate_limiter = Arc::new(RateLimiter::new().await.unwrap());
+    XeroClient::new(
+        "id".to_string(),
+        "secret".to_string(),
+        "http://localhost/cb".to_string(),
+        rate_limiter,
+    )
+    .await
+    .unwrap()
+}
+
+/// A bearer token obtained now and valid for half an hour.
+pub(crate) fn token(access_token: &str) -> TokenSet {
+    TokenSet {
+        access_token: access_token.to_string(),
+        expires_in: 1800,
+        token_type: "Bearer".to_string(),
+        obtained_at: chrono::Utc::now(),
+        ..Default::default()
+    }
+}
+
+/// A handle for [`TENANT_ID`] on a fresh [`client`], sending [`token`].
+pub(crate) async fn tenant() -> TenantClient {
+    client().await.tenant_with_token(TENANT_ID, token("token"))
+}
+
+/// The accounting API of a fresh [`tenant`], sending its requests to `server`.
+#[cfg(feature = "accounting")]
+pub(crate) async fn accounting_api(server: &MockServer) -> AccountingApi {
+    tenant().await.accounting().with_base_url(server.url())
+}
+
+/// A `Content-Length` delimited response, e.g. `response("404 Not Found", "{}")`.
+pub(crate) fn response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Accepts one connection per scripted response and records each request.
+pub(crate) struct MockServer {
+    addr: SocketAddr,
+    requests: JoinHandle<Vec<String>>,
+}
+
+impl MockServer {
+    /// Answers `connections` requests in turn with whatever `respond` returns
+    /// for the raw request text.
+    pub(crate) async fn respond_with<F, Fut>(connections: usize, mut respond: F) -> Self
+    where
+        F: FnMut(String) -> Fut + Send + 'static,
+        Fut: Future<Output = String> + Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = tokio::spawn(async move {
+            let mut requests = Vec::with_capacity(connections);
+            for _ in 0..connections {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut socket).await;
+                let response = respond(request.clone()).await;
+                socket.write_all(response.as_bytes()).await.unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+        Self { addr, requests }
+    }
+
+    /// Plays back `responses`, each a complete HTTP/1.1 response, in order.
+    pub(crate) async fn start(responses: Vec<String>) -> Self {
+        let mut responses = responses.into_iter();
+        Self::respond_with(responses.len(), move |_| {
+            let response = responses.next().unwrap();
+            async move { response }
+        })
+        .await
+    }
+
+    /// Plays back each of `bodies` with a `200 OK`.
+    pub(crate) async fn ok<I>(bodies: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let responses = bodies
+            .into_iter()
+            .map(|body| response("200 OK", body.as_ref()))
+            .collect();
+        Self::start(responses).await
+    }
+
+    /// The base URL to point an API handle at.
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Waits for every scripted response to be sent and returns the raw
+    /// requests, headers and body included.
+    pub(crate) async fn requests(self) -> Vec<String> {
+        self.requests.await.unwrap()
+    }
+
+    /// Like [`Self::requests`], keeping only each request's path and query.
+    pub(crate) async fn paths(self) -> Vec<String> {
+        self.request_lines()
+            .await
+            .into_iter()
+            .map(|line| line.split_once(' ').unwrap().1.to_string())
+            .collect()
+    }
+
+    /// Like [`Self::requests`], keeping only each request's method and path,
+    /// e.g. `"GET /Folders"`.
+    pub(crate) async fn request_lines(self) -> Vec<String> {
+        self.requests()
+            .await
+            .iter()
+            .map(|request| {
+                let mut line = request.split_whitespace();
+                format!("{} {}", line.next().unwrap(), line.next().unwrap())
+            })
+            .collect()
+    }
+}
+
+/// Reads the request head and as much body as its `Content-Length` promises.
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        let Some(head_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            if n == 0 {
+                break;
+            }
+            continue;
+        };
+        let content_length = String::from_utf8_lossy(&buf[..head_end])
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .map_or(0, |(_, value)| value.trim().parse().unwrap());
+        if n == 0 || buf.len() - head_end - 4 >= content_length {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&buf).into_owned()
+}
@@ -1,7 +1,63 @@
 //! Provides utility functions, including custom deserializers for Xero's unique data formats.
 
+use crate::error::XeroError;
 use chrono::{DateTime, NaiveDate, TimeZone, Utc};
-use serde::{self, de::Visitor, Deserialize, Deserializer, Serializer};
+use serde::{self, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes a create payload the way Xero expects: a bare object for a single
+/// record, or the plural wrapper (e.g. `{"Invoices": [...]}`) for several.
+pub(crate) fn single_or_wrapped<T, W>(
+    mut items: Vec<T>,
+    wrap: impl FnOnce(Vec<T>) -> W,
+) -> Result<serde_json::Value, serde_json::Error>
+where
+    T: Serialize,
+    W: Serialize,
+{
+    if items.len() == 1 {
+        serde_json::to_value(items.remove(0))
+    } else {
+        serde_json::to_value(wrap(items))
+    }
+}
+
+/// Splits `items`, in order, into batches whose JSON array fits in
+/// `max_bytes` once `overhead` bytes of wrapper are added around it.
+///
+/// Fails with [`XeroError::Validation`] if an item is too large to send even
+/// on its own.
+pub(crate) fn split_by_json_size<T: Serialize>(
+    items: Vec<T>,
+    max_bytes: usize,
+    overhead: usize,
+) -> Result<Vec<Vec<T>>, XeroError> {
+    // Room for the array brackets.
+    let budget = max_bytes.saturating_sub(overhead + 2);
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    for (index, item) in items.into_iter().enumerate() {
+        let size = serde_json::to_vec(&item)?.len();
+        if size > budget {
+            return Err(XeroError::Validation(format!(
+                "Item {index} serializes to {size} bytes, over the {max_bytes}-byte request limit"
+            )));
+        }
+        // Every item after the first is preceded by a comma.
+        let added = if batch.is_empty() { size } else { size + 1 };
+        if batch_bytes + added > budget {
+            batches.push(std::mem::take(&mut batch));
+            batch_bytes = size;
+        } else {
+            batch_bytes += added;
+        }
+        batch.push(item);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    Ok(batches)
+}
 
 // Helper to parse the inner Xero date string like "/Date(1750136176637+0000)/"
 fn parse_xero_date(s: &str) -> Result<DateTime<Utc>, String> {
@@ -9,7 +65,12 @@ fn parse_xero_date(s: &str) -> Result<DateTime<Utc>, String> {
     let s = s.trim_start_matches("/Date(").trim_end_matches(")/");
 
     // Find the end of the numeric timestamp part. It could be followed by a '+' or '-' for the timezone, or nothing.
-    let end_of_timestamp = s.find(['+', '-']).unwrap_or(s.len());
+    // Skip the first character so the sign of a pre-1970 timestamp is not mistaken for an offset.
+    let end_of_timestamp = s
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '+' || c == '-')
+        .map_or(s.len(), |(i, _)| i);
 
     // Extract and parse the timestamp
     let ts_str = &s[..end_of_timestamp];
@@ -68,19 +129,42 @@ pub mod xero_date_format_opt {
     }
 }
 
-// A flexible deserializer that can handle a field that is sometimes a string, sometimes a number.
-#[allow(dead_code)]
+/// Deserialize an optional string that Xero sometimes sends as a JSON number.
+///
+/// Numbers are kept in their JSON text form, so `12.5` becomes `"12.5"`. Use
+/// with `#[serde(default)]` so a missing field is also `None`.
 pub fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
     struct StringOrNumberVisitor;
 
-    impl Visitor<'_> for StringOrNumberVisitor {
+    impl<'de> Visitor<'de> for StringOrNumberVisitor {
         type Value = Option<String>;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a string or a number")
+            formatter.write_str("a string, a number, or null")
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_string_or_number(deserializer)
         }
 
         fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -90,6 +174,13 @@ where
             Ok(Some(value.to_owned()))
         }
 
+        fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(Some(value))
+        }
+
         fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
@@ -104,22 +195,60 @@ where
             Ok(Some(value.to_string()))
         }
 
-        // Handle null values
-        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            Ok(None)
+            Ok(Some(value.to_string()))
         }
     }
-    // Handle missing fields by deserializing into an Option first
-    use serde::de::Error as SerdeError;
-    let opt = Option::<serde::de::IgnoredAny>::deserialize(deserializer);
-    match opt {
-        Ok(_) => Err(SerdeError::custom(
-            "deserialize_string_or_number only supports string, number, or null",
-        )),
-        Err(_) => Ok(None),
+
+    deserializer.deserialize_any(StringOrNumberVisitor)
+}
+
+/// Deserialize a required integer that Xero sometimes sends as a string.
+pub fn deserialize_i32_from_string_or_number<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IntOrString {
+        Int(i32),
+        String(String),
+    }
+
+    match IntOrString::deserialize(deserializer)? {
+        IntOrString::Int(value) => Ok(value),
+        IntOrString::String(value) => value
+            .trim()
+            .parse()
+            .map_err(|e| serde::de::Error::custom(format!("invalid integer string: {e}"))),
+    }
+}
+
+/// Deserialize a flag Xero may send as a JSON bool, `"true"`/`"false"`, or
+/// `"ALLOWED"`/`"NOT-ALLOWED"`.
+pub fn deserialize_bool_from_string_or_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+
+    match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(value) => Ok(value),
+        BoolOrString::String(value) => match value.trim().to_ascii_uppercase().as_str() {
+            "TRUE" | "ALLOWED" => Ok(true),
+            "FALSE" | "NOT-ALLOWED" | "NOTALLOWED" => Ok(false),
+            other => Err(serde::de::Error::custom(format!(
+                "invalid boolean string: {other}"
+            ))),
+        },
     }
 }
 
@@ -260,6 +389,67 @@ fn parse_iso_datetime(s: &str) -> Result<DateTime<Utc>, String> {
     Ok(Utc.from_utc_datetime(&naive))
 }
 
+/// Parses either Xero's `/Date(ms)/` form or an ISO-8601 datetime.
+fn parse_flexible_datetime(s: &str) -> Result<DateTime<Utc>, String> {
+    if s.starts_with("/Date(") {
+        parse_xero_date(s)
+    } else {
+        parse_iso_datetime(s)
+    }
+}
+
+/// For fields Xero sends as `/Date(ms)/` on some endpoints and ISO-8601 on
+/// others. Serializes in the `/Date(ms)/` form the Accounting API expects.
+pub mod flexible_datetime {
+    use super::{
+        parse_flexible_datetime, serde, xero_date_format, DateTime, Deserialize, Deserializer,
+        Serializer, Utc,
+    };
+
+    #[allow(dead_code)]
+    pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        xero_date_format::serialize(date, serializer)
+    }
+
+    #[allow(dead_code)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse_flexible_datetime(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Optional counterpart of [`flexible_datetime`].
+pub mod flexible_datetime_opt {
+    use super::{
+        parse_flexible_datetime, serde, xero_date_format_opt, DateTime, Deserialize, Deserializer,
+        Serializer, Utc,
+    };
+
+    pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        xero_date_format_opt::serialize(date, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s_opt: Option<String> = Option::deserialize(deserializer)?;
+        s_opt
+            .map(|s| parse_flexible_datetime(&s))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 pub mod iso_datetime_format {
     use super::{parse_iso_datetime, serde, DateTime, Deserialize, Deserializer, Serializer, Utc};
 
@@ -309,3 +499,153 @@ pub mod iso_datetime_format_opt {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Dated {
+        #[serde(with = "xero_date_format")]
+        at: DateTime<Utc>,
+        #[serde(with = "xero_date_format_opt", default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        maybe: Option<DateTime<Utc>>,
+    }
+
+    fn round_trip(millis: i64) {
+        let at = DateTime::from_timestamp_millis(millis).unwrap();
+        let dated = Dated {
+            at,
+            maybe: Some(at),
+        };
+        let json = serde_json::to_string(&dated).unwrap();
+        let back: Dated = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, dated, "round trip failed for {millis}ms via {json}");
+    }
+
+    #[test]
+    fn xero_dates_round_trip_to_the_millisecond() {
+        for millis in [
+            0,
+            1,
+            -1,
+            -86_400_000,
+            951_868_799_999,     // 2000-02-29T23:59:59.999Z
+            1_750_136_176_637,   // a timestamp seen in a real response
+            253_402_300_799_999, // 9999-12-31T23:59:59.999Z
+            -62_135_596_800_000, // 0001-01-01T00:00:00Z
+        ] {
+            round_trip(millis);
+        }
+
+        // Deterministic pseudo-random sweep across roughly 1900..2100.
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..1_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let span = 6_311_390_400_000_i64; // 200 years in ms
+            let millis = (state >> 1) as i64 % span - 2_208_988_800_000; // from 1900-01-01
+            round_trip(millis);
+        }
+    }
+
+    #[test]
+    fn xero_date_offset_suffix_is_ignored() {
+        let dated: Dated = serde_json::from_str(r#"{"at":"/Date(1750136176637+1200)/"}"#).unwrap();
+        assert_eq!(dated.at.timestamp_millis(), 1_750_136_176_637);
+        let dated: Dated = serde_json::from_str(r#"{"at":"/Date(-1000-0500)/"}"#).unwrap();
+        assert_eq!(dated.at.timestamp_millis(), -1000);
+    }
+
+    #[test]
+    fn missing_optional_date_is_omitted() {
+        let dated = Dated {
+            at: DateTime::from_timestamp_millis(0).unwrap(),
+            maybe: None,
+        };
+        let json = serde_json::to_value(&dated).unwrap();
+        assert_eq!(json, serde_json::json!({ "at": "/Date(0)/" }));
+
+        let back: Dated = serde_json::from_value(json).unwrap();
+        assert_eq!(back.maybe, None);
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Flexible {
+        #[serde(with = "flexible_datetime")]
+        at: DateTime<Utc>,
+        #[serde(with = "flexible_datetime_opt", default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        maybe: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn flexible_dates_accept_both_formats() {
+        let expected = DateTime::from_timestamp_millis(1_750_136_176_637).unwrap();
+        for text in [
+            "/Date(1750136176637+0000)/",
+            "2025-06-17T04:56:16.637Z",
+            "2025-06-17T16:56:16.637+12:00",
+            "2025-06-17T04:56:16.637",
+        ] {
+            let json = serde_json::json!({ "at": text, "maybe": text });
+            let parsed: Flexible = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed.at, expected, "{text}");
+            assert_eq!(parsed.maybe, Some(expected), "{text}");
+        }
+
+        let parsed: Flexible = serde_json::from_str(r#"{"at":"/Date(0)/","maybe":null}"#).unwrap();
+        assert_eq!(parsed.maybe, None);
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::json!({ "at": "/Date(0)/" })
+        );
+        assert!(serde_json::from_str::<Flexible>(r#"{"at":"yesterday"}"#).is_err());
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Numbered {
+        #[serde(deserialize_with = "deserialize_i32_from_string_or_number")]
+        number: i32,
+        #[serde(default, deserialize_with = "deserialize_string_or_number")]
+        label: Option<String>,
+    }
+
+    #[test]
+    fn numbers_accept_either_representation() {
+        let from_numbers: Numbered =
+            serde_json::from_str(r#"{"number": 42, "label": 12.5}"#).unwrap();
+        assert_eq!(from_numbers.number, 42);
+        assert_eq!(from_numbers.label.as_deref(), Some("12.5"));
+
+        let from_strings: Numbered =
+            serde_json::from_str(r#"{"number": " 42 ", "label": "12.50"}"#).unwrap();
+        assert_eq!(from_strings.number, 42);
+        assert_eq!(from_strings.label.as_deref(), Some("12.50"));
+
+        let missing: Numbered = serde_json::from_str(r#"{"number": 1, "label": null}"#).unwrap();
+        assert_eq!(missing.label, None);
+        let absent: Numbered = serde_json::from_str(r#"{"number": 1}"#).unwrap();
+        assert_eq!(absent.label, None);
+        assert!(serde_json::from_str::<Numbered>(r#"{"number": "x"}"#).is_err());
+    }
+
+    #[test]
+    fn json_size_batches_stay_within_the_limit() {
+        // Each string serializes to 10 bytes: 8 characters plus quotes.
+        let items: Vec<String> = (0..5).map(|i| format!("item-{i:03}")).collect();
+        // 2 bytes of wrapper + 2 brackets leaves 21: two items and a comma.
+        let batches = split_by_json_size(items.clone(), 25, 2).unwrap();
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [2, 2, 1]);
+        for batch in &batches {
+            assert!(serde_json::to_vec(batch).unwrap().len() + 2 <= 25);
+        }
+        assert_eq!(batches.concat(), items);
+
+        let err = split_by_json_size(items, 12, 2).unwrap_err();
+        assert!(matches!(err, XeroError::Validation(msg) if msg.contains("Item 0")));
+    }
+}
@@ -1,7 +1,10 @@
+#![cfg(feature = "accounting")]
+
 // tests/accounting_get.rs
 
 mod common;
 use common::{assert_non_empty_accounting, log_raw_accounting_response, XeroTestResult};
+use xero_rs_async::models::accounting::account::AccountType;
 use xero_rs_async::models::accounting::invoice::InvoiceType;
 
 #[tokio::test]
@@ -21,6 +24,20 @@ async fn get_organisation() {
     println!("Successfully retrieved organisation: {}", org.name);
 }
 
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn get_organisation_single() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let org = api
+        .get_organisation_single()
+        .await
+        .expect_xero("API call to get single organisation failed");
+    assert!(!org.name.is_empty(), "Organisation name should be set.");
+}
+
 #[tokio::test]
 #[ignore = "Requires Xero API credentials"]
 async fn get_accounts() {
@@ -59,6 +76,26 @@ async fn get_accounts() {
     );
 }
 
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn get_bank_accounts() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let accounts = api
+        .get_bank_accounts()
+        .await
+        .expect_xero("API call to get bank accounts failed");
+    assert!(
+        accounts
+            .iter()
+            .all(|a| a.account_type == Some(AccountType::Bank)),
+        "Expected only BANK accounts."
+    );
+    println!("Successfully retrieved {} bank accounts.", accounts.len());
+}
+
 #[tokio::test]
 #[ignore = "Requires Xero API credentials"]
 async fn get_invoices() {
@@ -117,6 +154,42 @@ async fn get_contacts() {
     println!("Successfully retrieved {} contacts.", contacts.len());
 }
 
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn get_contact_attachments() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let contacts = api
+        .get_contacts(
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(1),
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect_xero("API call to get contacts failed");
+    let Some(contact_id) = contacts.first().and_then(|c| c.contact_id) else {
+        println!("No contacts found; skipping attachment listing.");
+        return;
+    };
+    let attachments = api
+        .get_contact_attachments(contact_id)
+        .await
+        .expect_xero("API call to get contact attachments failed");
+    println!(
+        "Successfully retrieved {} attachments for contact {contact_id}.",
+        attachments.len()
+    );
+}
+
 #[tokio::test]
 #[ignore = "Requires Xero API credentials"]
 async fn get_bank_transactions() {
@@ -235,6 +308,15 @@ async fn get_items() {
     let items = result.expect_xero("API call to get items failed");
     // Demo or real companies may have zero items.
     println!("Successfully retrieved {} items.", items.len());
+
+    if let Some(first) = items.first() {
+        let by_code = api
+            .get_item_by_code(&first.code)
+            .await
+            .expect_xero("Failed to get item by code");
+        let by_code = by_code.expect("Expected item to be found by its code");
+        assert_eq!(by_code.item_id, first.item_id);
+    }
 }
 
 #[tokio::test]
@@ -465,9 +547,7 @@ async fn get_history_and_online_url_for_invoice() {
             None,
             Some(format!(
                 "Type == \"{}\"",
-                serde_json::to_string(&InvoiceType::Accrec)
-                    .unwrap()
-                    .replace('\"', "")
+                InvoiceType::Accrec.as_xero_value()
             )),
             None,
             Some(1),
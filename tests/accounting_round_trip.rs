@@ -0,0 +1,238 @@
+#![cfg(feature = "accounting")]
+
+// tests/accounting_round_trip.rs
+//
+// Creates records in the connected (demo) organisation, fetches them back by
+// ID and checks every field we set survived the trip. A field that is
+// serialized under the wrong name, or read back from a different one, fails
+// here even though both directions parse without error.
+
+mod common;
+use chrono::{Days, Utc};
+use common::XeroTestResult;
+use uuid::Uuid;
+use xero_rs_async::models::accounting::bank_transaction::{BankTransaction, BankTransactionType};
+use xero_rs_async::models::accounting::common::{LineAmountType, LineItem, Money};
+use xero_rs_async::models::accounting::contact::Contact;
+use xero_rs_async::models::accounting::invoice::{Invoice, InvoiceStatus, InvoiceType};
+use xero_rs_async::models::accounting::item::{Item, ItemDetails};
+
+/// A short suffix that keeps names and codes unique across runs.
+fn unique_suffix() -> String {
+    Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+fn sales_line(description: &str) -> LineItem {
+    LineItem {
+        description: Some(description.to_string()),
+        quantity: Some(2.0),
+        unit_amount: Some(Money::from(25)),
+        account_code: Some("200".to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn contact_round_trips() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let suffix = unique_suffix();
+    let contact = Contact {
+        name: format!("Round Trip {suffix}"),
+        first_name: Some("Round".to_string()),
+        last_name: Some("Trip".to_string()),
+        email_address: Some(format!("round.trip.{suffix}@example.com")),
+        account_number: Some(format!("RT-{suffix}")),
+        ..Default::default()
+    };
+
+    let created = api
+        .create_contacts(vec![contact.clone()])
+        .await
+        .expect_xero("Failed to create contact");
+    let contact_id = created[0].contact_id.expect("created contact has an ID");
+    let fetched = api
+        .contacts()
+        .get(contact_id)
+        .await
+        .expect_xero("Failed to fetch contact")
+        .remove(0);
+
+    assert_eq!(fetched.name, contact.name);
+    assert_eq!(fetched.first_name, contact.first_name);
+    assert_eq!(fetched.last_name, contact.last_name);
+    assert_eq!(fetched.email_address, contact.email_address);
+    assert_eq!(fetched.account_number, contact.account_number);
+}
+
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn item_round_trips() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let suffix = unique_suffix();
+    let item = Item {
+        item_id: None,
+        code: format!("RT-{suffix}"),
+        name: Some(format!("Round Trip {suffix}")),
+        is_sold: Some(true),
+        is_purchased: Some(false),
+        description: Some("Sold in round trip test".to_string()),
+        purchase_description: None,
+        purchase_details: None,
+        sales_details: Some(ItemDetails {
+            unit_price: Some(12.5),
+            account_code: Some("200".to_string()),
+            cogs_account_code: None,
+            tax_type: None,
+        }),
+        is_tracked_as_inventory: None,
+        inventory_asset_account_code: None,
+        total_cost_pool: None,
+        quantity_on_hand: None,
+        updated_date_utc: None,
+    };
+
+    let created = api
+        .create_items(vec![item.clone()])
+        .await
+        .expect_xero("Failed to create item");
+    let item_id = created[0].item_id.expect("created item has an ID");
+    let fetched = api
+        .items()
+        .get(item_id)
+        .await
+        .expect_xero("Failed to fetch item")
+        .remove(0);
+
+    assert_eq!(fetched.code, item.code);
+    assert_eq!(fetched.name, item.name);
+    assert_eq!(fetched.is_sold, item.is_sold);
+    assert_eq!(fetched.description, item.description);
+    let sales = fetched.sales_details.expect("sales details read back");
+    assert_eq!(sales.unit_price, Some(12.5));
+    assert_eq!(sales.account_code.as_deref(), Some("200"));
+}
+
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn draft_invoice_round_trips() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let suffix = unique_suffix();
+    let contact = api
+        .create_contacts(vec![Contact {
+            name: format!("Round Trip Invoice {suffix}"),
+            ..Default::default()
+        }])
+        .await
+        .expect_xero("Failed to create contact")
+        .remove(0);
+    let date = Utc::now();
+    let invoice = Invoice::builder(InvoiceType::Accrec, contact.clone())
+        .date(date)
+        .due_date(date + Days::new(30))
+        .status(InvoiceStatus::Draft)
+        .line_amount_types(LineAmountType::Exclusive)
+        .reference(format!("RT-{suffix}"))
+        .line_item(sales_line("Round trip consulting"))
+        .build();
+
+    let created = api
+        .create_invoices(vec![invoice.clone()], None)
+        .await
+        .expect_xero("Failed to create invoice");
+    let invoice_id = created[0].invoice_id.expect("created invoice has an ID");
+    let fetched = api
+        .invoices()
+        .get(invoice_id)
+        .await
+        .expect_xero("Failed to fetch invoice")
+        .remove(0);
+
+    assert_eq!(fetched.invoice_type, InvoiceType::Accrec);
+    assert_eq!(fetched.contact.contact_id, contact.contact_id);
+    assert_eq!(fetched.status, Some(InvoiceStatus::Draft));
+    assert_eq!(fetched.reference, invoice.reference);
+    assert_eq!(fetched.line_amount_types, Some(LineAmountType::Exclusive));
+    assert_eq!(fetched.date.date_naive(), date.date_naive());
+    assert_eq!(fetched.line_items.len(), 1);
+    let line = &fetched.line_items[0];
+    assert_eq!(line.description.as_deref(), Some("Round trip consulting"));
+    assert_eq!(line.quantity, Some(2.0));
+    assert_eq!(line.unit_amount, Some(Money::from(25)));
+    assert_eq!(line.account_code.as_deref(), Some("200"));
+
+    api.delete_invoice(invoice_id)
+        .await
+        .expect_xero("Failed to delete draft invoice");
+}
+
+#[tokio::test]
+#[ignore = "Requires Xero API credentials"]
+async fn bank_transaction_round_trips() {
+    let test_client = common::get_test_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+    let suffix = unique_suffix();
+    let bank_account = api
+        .get_bank_accounts()
+        .await
+        .expect_xero("Failed to fetch bank accounts")
+        .into_iter()
+        .next()
+        .expect("the organisation has a bank account");
+    let contact = api
+        .create_contacts(vec![Contact {
+            name: format!("Round Trip Spend {suffix}"),
+            ..Default::default()
+        }])
+        .await
+        .expect_xero("Failed to create contact")
+        .remove(0);
+    let mut line = sales_line("Round trip supplies");
+    line.account_code = Some("400".to_string());
+    let transaction = BankTransaction::spend(bank_account.clone(), contact.clone())
+        .reference(format!("RT-{suffix}"))
+        .line_amount_types(LineAmountType::Exclusive)
+        .line_item(line)
+        .build()
+        .expect_xero("Bank transaction should be valid");
+
+    let created = api
+        .create_bank_transactions(vec![transaction.clone()], None)
+        .await
+        .expect_xero("Failed to create bank transaction");
+    let transaction_id = created[0]
+        .bank_transaction_id
+        .expect("created bank transaction has an ID");
+    let fetched = api
+        .bank_transactions()
+        .get(transaction_id)
+        .await
+        .expect_xero("Failed to fetch bank transaction")
+        .remove(0);
+
+    assert_eq!(fetched.transaction_type, Some(BankTransactionType::Spend));
+    assert_eq!(fetched.bank_account.account_id, bank_account.account_id);
+    assert_eq!(fetched.contact.contact_id, contact.contact_id);
+    assert_eq!(fetched.reference, transaction.reference);
+    assert_eq!(fetched.line_amount_types, Some(LineAmountType::Exclusive));
+    assert_eq!(fetched.line_items.len(), 1);
+    let line = &fetched.line_items[0];
+    assert_eq!(line.description.as_deref(), Some("Round trip supplies"));
+    assert_eq!(line.unit_amount, Some(Money::from(25)));
+    assert_eq!(line.account_code.as_deref(), Some("400"));
+
+    api.delete_bank_transaction(transaction_id)
+        .await
+        .expect_xero("Failed to delete bank transaction");
+}
@@ -9,6 +9,7 @@ use std::sync::{Arc, Once};
 use tokio::sync::OnceCell;
 use uuid::Uuid;
 use xero_rs_async::auth::TokenSet;
+use xero_rs_async::cassette::{Cassette, CassetteMode};
 use xero_rs_async::client::XeroClient;
 use xero_rs_async::error::XeroError;
 use xero_rs_async::rate_limiter::RateLimiter;
@@ -16,6 +17,23 @@ use xero_rs_async::rate_limiter::RateLimiter;
 pub struct TestClient {
     pub client: XeroClient,
     pub tenant_id: Uuid,
+    pub cassette: Option<Arc<Cassette>>,
+}
+
+impl Drop for TestClient {
+    /// Writes the cassette of a recording test that passed.
+    fn drop(&mut self) {
+        if let Some(cassette) = &self.cassette {
+            if cassette.mode() == CassetteMode::Record && !std::thread::panicking() {
+                cassette.save().unwrap_or_else(|err| {
+                    panic!(
+                        "Failed to save cassette {}: {err}",
+                        cassette.path().display()
+                    )
+                });
+            }
+        }
+    }
 }
 
 pub trait XeroTestResult<T> {
@@ -33,7 +51,7 @@ impl<T> XeroTestResult<T> for Result<T, XeroError> {
                         error!("Xero API error status: {status}");
                         error!("Xero raw response: {message}");
                     }
-                    XeroError::SerdeWithBody { body, .. } => {
+                    XeroError::SerdeWithBody { body, .. } | XeroError::Deserialize { body, .. } => {
                         error!("Xero raw response: {body}");
                     }
                     _ => {
@@ -81,6 +99,10 @@ async fn log_raw_response(
     path: &str,
     query: Option<&[(String, String)]>,
 ) {
+    if client.cassette.is_some() {
+        // Live calls would bypass the cassette (and fail offline).
+        return;
+    }
     let access_token = match client.client.token_manager.get_access_token().await {
         Ok(token) => token,
         Err(err) => {
@@ -175,14 +197,34 @@ fn setup_logger() {
     });
 }
 
-/// Creates a new, isolated TestClient that uses a shared RateLimiter.
-pub async fn get_test_client() -> TestClient {
-    setup_logger();
+/// Cassette mode selected by `XERO_CASSETTE` (`record` or `replay`), if any.
+fn cassette_mode() -> Option<CassetteMode> {
+    match env::var("XERO_CASSETTE").ok()?.to_lowercase().as_str() {
+        "record" => Some(CassetteMode::Record),
+        "replay" => Some(CassetteMode::Replay),
+        "" | "off" => None,
+        other => panic!("XERO_CASSETTE must be `record`, `replay` or `off`, not `{other}`."),
+    }
+}
 
-    dotenvy::dotenv().expect("Failed to load .env file. Make sure it exists at the project root.");
+/// The cassette file for the running test: `tests/cassettes/<test name>.json`.
+///
+/// libtest names each test's thread after the test, and `#[tokio::test]`
+/// runs the test body on that thread.
+fn cassette_path() -> PathBuf {
+    let thread = std::thread::current();
+    let name = thread
+        .name()
+        .expect("cassettes need the test's thread name; run tests with the default harness")
+        .replace("::", "__");
+    [env!("CARGO_MANIFEST_DIR"), "tests", "cassettes"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{name}.json"))
+}
 
-    // Get or initialize the single, shared RateLimiter instance.
-    let rate_limiter = RATE_LIMITER
+async fn shared_rate_limiter() -> Arc<RateLimiter> {
+    RATE_LIMITER
         .get_or_init(|| async {
             Arc::new(
                 RateLimiter::new()
@@ -191,7 +233,64 @@ pub async fn get_test_client() -> TestClient {
             )
         })
         .await
-        .clone();
+        .clone()
+}
+
+/// Creates a client that answers every call from the test's cassette, with no
+/// credentials or network access.
+pub async fn get_replay_client() -> TestClient {
+    let path = cassette_path();
+    let cassette = Arc::new(Cassette::replay(&path).unwrap_or_else(|err| {
+        panic!(
+            "Failed to load cassette {}: {err}. Record it with XERO_CASSETTE=record.",
+            path.display()
+        )
+    }));
+    let client = XeroClient::new(
+        "replay-client".to_string(),
+        "replay-secret".to_string(),
+        "http://localhost/callback".to_string(),
+        shared_rate_limiter().await,
+    )
+    .await
+    .expect("Failed to create XeroClient")
+    .with_cassette(cassette.clone());
+    // Never expires, so no refresh is attempted.
+    let token = TokenSet {
+        access_token: "replay-token".to_string(),
+        refresh_token: None,
+        id_token: None,
+        expires_in: u32::MAX.into(),
+        token_type: "Bearer".to_string(),
+        obtained_at: chrono::Utc::now(),
+    };
+    client.token_manager.set_token(&token).await;
+
+    TestClient {
+        client,
+        tenant_id: Uuid::from_u128(1),
+        cassette: Some(cassette),
+    }
+}
+
+/// Creates a new, isolated TestClient that uses a shared RateLimiter.
+///
+/// With `XERO_CASSETTE=replay` the client is served from
+/// `tests/cassettes/<test name>.json` and needs no `.env`; with
+/// `XERO_CASSETTE=record` it calls Xero as usual and saves that file when the
+/// test passes.
+pub async fn get_test_client() -> TestClient {
+    setup_logger();
+
+    let mode = cassette_mode();
+    if mode == Some(CassetteMode::Replay) {
+        return get_replay_client().await;
+    }
+
+    dotenvy::dotenv().expect("Failed to load .env file. Make sure it exists at the project root.");
+
+    // Get or initialize the single, shared RateLimiter instance.
+    let rate_limiter = shared_rate_limiter().await;
 
     let client_id = env::var("XERO_CLIENT_ID").expect("XERO_CLIENT_ID must be set.");
     let client_secret = env::var("XERO_CLIENT_SECRET").expect("XERO_CLIENT_SECRET must be set.");
@@ -225,7 +324,18 @@ pub async fn get_test_client() -> TestClient {
     });
     client.token_manager.set_token(&token_set).await;
 
+    let cassette =
+        (mode == Some(CassetteMode::Record)).then(|| Arc::new(Cassette::record(cassette_path())));
+    let client = match &cassette {
+        Some(cassette) => client.with_cassette(cassette.clone()),
+        None => client,
+    };
+
     info!("✅ Test client initialized for tenant: {tenant_id}");
 
-    TestClient { client, tenant_id }
+    TestClient {
+        client,
+        tenant_id,
+        cassette,
+    }
 }
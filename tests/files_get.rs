@@ -3,7 +3,7 @@
 // tests/files_get.rs
 
 mod common;
-use common::{log_raw_accounting_response, log_raw_files_response, XeroTestResult};
+use common::{log_raw_files_response, XeroTestResult};
 
 #[tokio::test]
 async fn get_folders_and_by_id() {
@@ -75,11 +75,32 @@ async fn get_files_and_content() {
             single_file.name,
             content.len()
         );
+
+        // Client-side lookups by name and folder
+        let by_name = api
+            .find_file_by_name(&single_file.name)
+            .await
+            .expect_xero("Failed to find file by name");
+        assert!(
+            by_name.is_some(),
+            "Expected to find '{}' by name",
+            single_file.name
+        );
+
+        if let Some(folder_id) = single_file.folder_id {
+            let in_folder = api
+                .get_files_in_folder(folder_id)
+                .await
+                .expect_xero("Failed to get files in folder");
+            assert!(in_folder.iter().any(|f| f.id == file_id));
+            assert!(in_folder.iter().all(|f| f.folder_id == Some(folder_id)));
+        }
     } else {
         println!("Skipping get_file_by_id and get_file_content tests as no files were found.");
     }
 }
 
+#[cfg(feature = "accounting")]
 #[tokio::test]
 async fn get_associations() {
     let test_client = common::get_test_client().await;
@@ -106,7 +127,7 @@ async fn get_associations() {
         .await
         .expect_xero("Failed to get invoices to test associations");
     if invoices.is_empty() {
-        log_raw_accounting_response(&test_client, "/Invoices", None).await;
+        common::log_raw_accounting_response(&test_client, "/Invoices", None).await;
     }
 
     if let Some(invoice) = invoices.first() {
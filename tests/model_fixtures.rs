@@ -0,0 +1,123 @@
+#![cfg(feature = "accounting")]
+
+// tests/model_fixtures.rs
+//
+// Round-trips captured Xero responses in tests/fixtures through the models.
+// Each fixture must deserialize, survive serialize -> deserialize unchanged,
+// and keep every populated field apart from the response-only keys listed
+// per fixture.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use xero_rs_async::models::accounting::{
+    account::Account, bank_transaction::BankTransaction, contact::Contact, invoice::Invoice,
+    report::Report,
+};
+
+/// Keys Xero adds to responses that the models deliberately do not keep.
+const RESPONSE_ONLY: &[&str] = &[
+    "DateString",
+    "DueDateString",
+    "HasErrors",
+    "HasValidationErrors",
+    "ValidationErrors",
+];
+
+fn load(name: &str) -> Value {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name]
+        .iter()
+        .collect();
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{name} is not valid JSON: {e}"))
+}
+
+/// Returns the paths of populated fields in `original` that are absent from `written`.
+fn dropped_fields(original: &Value, written: &Value, path: &str, ignore: &[&str]) -> Vec<String> {
+    match (original, written) {
+        (Value::Object(original), Value::Object(written)) => original
+            .iter()
+            .filter(|(key, _)| {
+                !RESPONSE_ONLY.contains(&key.as_str()) && !ignore.contains(&key.as_str())
+            })
+            .flat_map(|(key, value)| {
+                let field = format!("{path}.{key}");
+                match written.get(key) {
+                    Some(written) => dropped_fields(value, written, &field, ignore),
+                    None if is_empty(value) => Vec::new(),
+                    None => vec![field],
+                }
+            })
+            .collect(),
+        (Value::Array(original), Value::Array(written)) => original
+            .iter()
+            .zip(written)
+            .enumerate()
+            .flat_map(|(i, (original, written))| {
+                dropped_fields(original, written, &format!("{path}[{i}]"), ignore)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn round_trip<T>(name: &str, ignore: &[&str]) -> T
+where
+    T: DeserializeOwned + Serialize + PartialEq + Debug,
+{
+    let original = load(name);
+    let parsed: T = serde_json::from_value(original.clone())
+        .unwrap_or_else(|e| panic!("{name} does not deserialize: {e}"));
+    let written = serde_json::to_value(&parsed).unwrap();
+    let reparsed: T = serde_json::from_value(written.clone())
+        .unwrap_or_else(|e| panic!("{name} does not deserialize after re-serializing: {e}"));
+    assert_eq!(parsed, reparsed, "{name} changed across a round trip");
+
+    let dropped = dropped_fields(&original, &written, "", ignore);
+    assert!(dropped.is_empty(), "{name} lost fields: {dropped:?}");
+    parsed
+}
+
+#[test]
+fn invoice_fixture() {
+    let invoice: Invoice = round_trip("invoice.json", &["HasAccount", "IsDiscounted"]);
+    assert_eq!(invoice.invoice_number.as_deref(), Some("OIT00546"));
+    assert_eq!(invoice.line_items.len(), 1);
+}
+
+#[test]
+fn contact_fixture() {
+    let contact: Contact = round_trip("contact.json", &[]);
+    assert_eq!(contact.addresses.len(), 2);
+    assert_eq!(contact.contact_persons.len(), 1);
+}
+
+#[test]
+fn account_fixture() {
+    let account: Account = round_trip("account.json", &[]);
+    assert_eq!(account.code.as_deref(), Some("091"));
+}
+
+#[test]
+fn bank_transaction_fixture() {
+    let transaction: BankTransaction = round_trip("bank_transaction.json", &[]);
+    assert_eq!(transaction.line_items.len(), 1);
+}
+
+#[test]
+fn report_fixture() {
+    let report: Report = round_trip("report.json", &[]);
+    assert_eq!(report.rows.len(), 2);
+    assert_eq!(report.rows[1].rows.len(), 2);
+}
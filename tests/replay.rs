@@ -0,0 +1,24 @@
+#![cfg(feature = "accounting")]
+
+// tests/replay.rs
+//
+// Runs offline against tests/cassettes/<test name>.json, checking the replay
+// wiring that `XERO_CASSETTE=replay` uses for the live suites.
+
+mod common;
+use common::XeroTestResult;
+
+#[tokio::test]
+async fn replayed_organisation() {
+    let test_client = common::get_replay_client().await;
+    let api = test_client
+        .client
+        .accounting_for_tenant(test_client.tenant_id);
+
+    let org = api
+        .get_organisation_single()
+        .await
+        .expect_xero("Replayed organisation call failed");
+    assert_eq!(org.name, "Demo Company (NZ)");
+    assert!(org.is_demo_company);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "accounting")]
+
 use serde_json::from_str;
 use xero_rs_async::models::accounting::report::ReportsResponse;
 
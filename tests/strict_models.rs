@@ -0,0 +1,132 @@
+#![cfg(all(feature = "strict", feature = "accounting"))]
+
+// tests/strict_models.rs
+//
+// Runs sample Xero payloads through the models with unknown fields denied, so
+// fields Xero adds to these resources show up as test failures.
+
+use serde::de::DeserializeOwned;
+use xero_rs_async::models::accounting::{
+    account::Account, attachment::Attachment, branding_theme::BrandingTheme, currency::Currency,
+    tax_rate::TaxRate, user::User,
+};
+
+fn parse_strict<T: DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        panic!(
+            "{} no longer matches the Xero payload: {e}",
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+#[test]
+fn account_fixture() {
+    let account: Account = parse_strict(
+        r#"{
+            "AccountID": "ebd06280-af70-4bed-97c6-7451a454ad85",
+            "Code": "091",
+            "Name": "Business Savings Account",
+            "Status": "ACTIVE",
+            "Type": "BANK",
+            "TaxType": "NONE",
+            "Class": "ASSET",
+            "EnablePaymentsToAccount": false,
+            "ShowInExpenseClaims": false,
+            "BankAccountNumber": "0209087654321050",
+            "BankAccountType": "BANK",
+            "CurrencyCode": "NZD",
+            "ReportingCode": "ASS",
+            "ReportingCodeName": "Assets",
+            "HasAttachments": false,
+            "UpdatedDateUTC": "/Date(1578539236000+0000)/",
+            "AddToWatchlist": false
+        }"#,
+    );
+    assert_eq!(account.code.as_deref(), Some("091"));
+}
+
+#[test]
+fn tax_rate_fixture() {
+    let tax_rate: TaxRate = parse_strict(
+        r#"{
+            "Name": "15% GST on Expenses",
+            "TaxType": "INPUT2",
+            "ReportTaxType": "INPUT",
+            "CanApplyToAssets": true,
+            "CanApplyToEquity": true,
+            "CanApplyToExpenses": true,
+            "CanApplyToLiabilities": true,
+            "CanApplyToRevenue": false,
+            "DisplayTaxRate": 15.0,
+            "EffectiveRate": 15.0,
+            "Status": "ACTIVE",
+            "TaxComponents": [
+                { "Name": "GST", "Rate": 15.0, "IsCompound": false, "IsNonRecoverable": false }
+            ]
+        }"#,
+    );
+    assert_eq!(tax_rate.tax_components.len(), 1);
+}
+
+#[test]
+fn currency_fixture() {
+    let currency: Currency =
+        parse_strict(r#"{ "Code": "NZD", "Description": "New Zealand Dollar" }"#);
+    assert_eq!(currency.code, "NZD");
+}
+
+#[test]
+fn branding_theme_fixture() {
+    let theme: BrandingTheme = parse_strict(
+        r#"{
+            "BrandingThemeID": "dfe23d27-a3a6-4ef3-a5ca-b9e02b142dde",
+            "Name": "Special Projects",
+            "LogoUrl": "https://in.xero.com/logo?id=abc",
+            "Type": "INVOICE",
+            "SortOrder": 1,
+            "CreatedDateUTC": "/Date(946684800000+0000)/"
+        }"#,
+    );
+    assert_eq!(theme.name.as_deref(), Some("Special Projects"));
+}
+
+#[test]
+fn user_fixture() {
+    let user: User = parse_strict(
+        r#"{
+            "UserID": "7cf47fe2-c3dd-4c6b-9895-7ba767ba529c",
+            "GlobalUserID": "9d6a2f2e-1b1f-4c2a-8a1e-6f3f1c4d2b7a",
+            "EmailAddress": "john.smith@mail.com",
+            "FirstName": "John",
+            "LastName": "Smith",
+            "UpdatedDateUTC": "/Date(1516230549137+0000)/",
+            "IsSubscriber": false,
+            "OrganisationRole": "FINANCIALADVISER"
+        }"#,
+    );
+    assert_eq!(user.first_name, "John");
+}
+
+#[test]
+fn attachment_fixture() {
+    let attachment: Attachment = parse_strict(
+        r#"{
+            "AttachmentID": "44dc5d9e-7488-4db1-8185-177be8ab7c6f",
+            "FileName": "invoice.pdf",
+            "Url": "https://api.xero.com/api.xro/2.0/Invoices/abc/Attachments/invoice.pdf",
+            "MimeType": "application/pdf",
+            "ContentLength": 256570,
+            "IncludeOnline": true
+        }"#,
+    );
+    assert_eq!(attachment.include_online, Some(true));
+}
+
+#[test]
+fn unknown_field_is_rejected() {
+    let result = serde_json::from_str::<Currency>(
+        r#"{ "Code": "NZD", "Description": "New Zealand Dollar", "Symbol": "$" }"#,
+    );
+    assert!(result.is_err(), "strict models must reject unknown fields");
+}